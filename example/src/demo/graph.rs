@@ -1,22 +1,20 @@
 use tekenen::shapes::rect::Rect;
-use tekenen::{colors, DrawableSurface, Surface, SurfaceView};
+use tekenen::{colors, DrawableSurface, OverflowBehavior, Surface, SurfaceView};
 use tekenen::platform::{Event, IntervalDecision, Platform, PlatformTrait};
 
 
 pub struct GraphDemo {
     graph: SurfaceView,
-    original: SurfaceView,
 }
 
 impl GraphDemo {
     pub fn new() -> Self {
-        let original = SurfaceView::new(800, 600, Surface::new(800, 600).into());
-        let graph = original.tee();
+        let graph = SurfaceView::new(800, 600, Surface::new(800, 600).into());
         graph.clip(Rect::new(100, 0, 700, 500));
+        graph.set_overflow_behavior(OverflowBehavior::Hidden);
 
         Self {
-            graph,
-            original
+            graph
         }
     }
 }
@@ -59,11 +57,6 @@ impl super::Demo for GraphDemo {
         self.graph.fill_color(colors::GREEN);
         self.graph.rect(100, 100, 10, 10);
 
-        // Draw over borders
-        self.original.fill_color(colors::BLACK);
-        self.original.rect(0, 0, 100, self.original.height());
-        self.original.rect(100, self.original.height() - 100, self.original.width() - 100, 100);
-
         // let rect = self.graph.get_world_screen();
         // println!("World: {}", rect);
 
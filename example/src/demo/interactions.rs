@@ -52,7 +52,7 @@ impl InteractionsDemo {
             fui,
             text,
             canvas,
-            shapes: vec![Rc::new(RefCell::new(Rect::new(0, 0, 100, 100)))],
+            shapes: vec![Rc::new(RefCell::new(Rect::new(0.0, 0.0, 100.0, 100.0)))],
             creator: None
         }
     }
@@ -76,15 +76,15 @@ impl Demo for InteractionsDemo {
 
         match event {
             Event::MouseDown{ x, y, key: MouseButton::Left } => {
-                let shape = Rc::new(RefCell::new(Rect::new(x, y, 1, 1)));
+                let shape = Rc::new(RefCell::new(Rect::new(x as f32, y as f32, 1.0, 1.0)));
                 self.creator = Some(shape.clone());
                 self.shapes.push(shape);
             },
             Event::MouseMove{ x, y, .. } => {
                 if let Some(rect) = self.creator.as_ref() {
                     let mut rect = rect.borrow_mut();
-                    rect.size.x = x - rect.position.x;
-                    rect.size.y = y - rect.position.y;
+                    rect.size.x = x as f32 - rect.position.x;
+                    rect.size.y = y as f32 - rect.position.y;
                 }
             },
             Event::MouseUp{ .. }  => {
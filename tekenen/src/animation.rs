@@ -0,0 +1,361 @@
+use std::time::Duration;
+
+use crate::{platform::{fpia::{decode_fpia, encode_fpia, take, take_u32}, ImageLoadingError}, Surface};
+
+// Fritz Preloaded ANimation
+const ANIMATION_MAGIC: [u8; 4] = *b"FPAN";
+
+const VERSION: u8 = 1;
+
+/// How many times an animation plays before it stops on its last frame
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LoopCount {
+    Infinite,
+    Finite(u32),
+}
+
+#[derive(Debug, Clone)]
+pub struct Frame {
+    pub surface: Surface,
+
+    /// How long the frame is shown
+    pub duration: Duration,
+}
+
+/// Surfaces shown one after the other, every frame for its own duration.
+/// Play it with a Player, which keeps track of the elapsed time
+#[derive(Debug, Clone)]
+pub struct AnimatedImage {
+    pub frames: Vec<Frame>,
+    pub loop_count: LoopCount,
+}
+
+impl AnimatedImage {
+    /// Infinitely looping animation
+    pub fn new(frames: Vec<Frame>) -> Self {
+        Self {
+            frames,
+            loop_count: LoopCount::Infinite,
+        }
+    }
+
+    /// Time to play every frame once
+    pub fn duration(&self) -> Duration {
+        self.frames.iter().map(|frame| frame.duration).sum()
+    }
+
+    /// Index of the frame shown elapsed after the start
+    pub fn frame_at(&self, elapsed: Duration) -> usize {
+        let duration = self.duration();
+        let last = self.frames.len().saturating_sub(1);
+
+        if duration.is_zero() || self.is_finished(elapsed) {
+            return last
+        }
+
+        let mut time = Duration::from_nanos((elapsed.as_nanos() % duration.as_nanos()) as u64);
+
+        for (index, frame) in self.frames.iter().enumerate() {
+            if time < frame.duration {
+                return index
+            }
+
+            time -= frame.duration
+        }
+
+        last
+    }
+
+    /// Every loop has been played
+    pub fn is_finished(&self, elapsed: Duration) -> bool {
+        match self.loop_count {
+            LoopCount::Infinite => false,
+            LoopCount::Finite(plays) => elapsed >= self.duration() * plays,
+        }
+    }
+
+    /// Serialize as FPAN: magic, version, three zero bytes, the number of plays (0 for infinite) and the number of frames.
+    /// Every frame follows as its duration in milliseconds, the length of its FPIA data and the data itself.
+    /// All numbers are big endian u32
+    pub fn encode(&self) -> Vec<u8> {
+        let plays = match self.loop_count {
+            LoopCount::Infinite => 0,
+            LoopCount::Finite(plays) => plays.max(1),
+        };
+
+        let mut data = Vec::new();
+        data.extend_from_slice(&ANIMATION_MAGIC);
+        data.extend_from_slice(&[VERSION, 0, 0, 0]);
+        data.extend_from_slice(&plays.to_be_bytes());
+        data.extend_from_slice(&(self.frames.len() as u32).to_be_bytes());
+
+        for frame in &self.frames {
+            let image = encode_fpia(&frame.surface);
+
+            data.extend_from_slice(&(frame.duration.as_millis() as u32).to_be_bytes());
+            data.extend_from_slice(&(image.len() as u32).to_be_bytes());
+            data.extend_from_slice(&image);
+        }
+
+        data
+    }
+
+    /// Inverse of encode
+    pub fn decode(data: &[u8]) -> Result<Self, ImageLoadingError> {
+        let mut data = data;
+
+        if take(&mut data, 4)? != ANIMATION_MAGIC {
+            return Err(ImageLoadingError::UnknownFormat)
+        }
+
+        let version = take(&mut data, 4)?[0];
+        if version != VERSION {
+            return Err(ImageLoadingError::UnknownVersion(version))
+        }
+
+        let loop_count = match take_u32(&mut data)? {
+            0 => LoopCount::Infinite,
+            plays => LoopCount::Finite(plays),
+        };
+
+        let count = take_u32(&mut data)?;
+        let mut frames = Vec::new();
+
+        for _ in 0..count {
+            let duration = Duration::from_millis(take_u32(&mut data)? as u64);
+            let length = take_u32(&mut data)? as usize;
+            let surface = decode_fpia(take(&mut data, length)?)?;
+
+            frames.push(Frame { surface, duration });
+        }
+
+        if !data.is_empty() {
+            return Err(ImageLoadingError::InvalidData)
+        }
+
+        Ok(Self { frames, loop_count })
+    }
+
+    /// Read an animated GIF or APNG, FPAN, or any still image as a single frame
+    #[cfg(feature = "image")]
+    pub fn import(data: &[u8]) -> Result<Self, ImageLoadingError> {
+        use std::io::Cursor;
+
+        use image::{codecs::{gif::GifDecoder, png::PngDecoder}, AnimationDecoder};
+
+        use crate::platform::{Platform, PlatformTrait};
+
+        if data.starts_with(&ANIMATION_MAGIC) {
+            return Self::decode(data)
+        }
+
+        let (frames, loop_count) = if data.starts_with(b"GIF8") {
+            let decoder = GifDecoder::new(Cursor::new(data)).map_err(ImageLoadingError::ImageError)?;
+            (decoder.into_frames(), gif_loop_count(data))
+        } else if data.starts_with(&PNG_SIGNATURE) && apng_loop_count(data).is_some() {
+            let decoder = PngDecoder::new(Cursor::new(data)).map_err(ImageLoadingError::ImageError)?;
+            (decoder.apng().into_frames(), apng_loop_count(data).unwrap())
+        } else {
+            let surface = Platform::parse_image(data)?;
+            return Ok(Self::new(vec![Frame { surface, duration: Duration::ZERO }]))
+        };
+
+        // Both decoders return whole frames, already composed over the previous ones
+        let frames = frames.map(|frame| {
+            let frame = frame.map_err(ImageLoadingError::ImageError)?;
+            let (numerator, denominator) = frame.delay().numer_denom_ms();
+            let mut duration = Duration::from_micros(numerator as u64 * 1000 / denominator.max(1) as u64);
+
+            // Like browsers, show GIF frames without a real delay for 100ms
+            if data.starts_with(b"GIF8") && duration <= Duration::from_millis(10) {
+                duration = Duration::from_millis(100)
+            }
+
+            let buffer = frame.into_buffer();
+            let (width, height) = (buffer.width() as usize, buffer.height() as usize);
+            let pixels = buffer.pixels().map(|pixel| pixel.0).collect();
+
+            Ok(Frame { surface: Surface::from_pixels(width, height, pixels), duration })
+        }).collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self { frames, loop_count })
+    }
+}
+
+#[cfg(feature = "image")]
+const PNG_SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', 0x0d, 0x0a, 0x1a, 0x0a];
+
+/// GIF stores the number of extra repetitions in the NETSCAPE2.0 extension, without it the animation plays once
+#[cfg(feature = "image")]
+fn gif_loop_count(data: &[u8]) -> LoopCount {
+    let Some(start) = data.windows(11).position(|window| window == b"NETSCAPE2.0") else {
+        return LoopCount::Finite(1)
+    };
+
+    match data.get(start + 11..start + 15) {
+        Some(&[3, 1, low, high]) => match u16::from_le_bytes([low, high]) {
+            0 => LoopCount::Infinite,
+            repetitions => LoopCount::Finite(repetitions as u32 + 1),
+        },
+        _ => LoopCount::Finite(1),
+    }
+}
+
+/// Number of plays from the acTL chunk, None if the png is not animated
+#[cfg(feature = "image")]
+fn apng_loop_count(data: &[u8]) -> Option<LoopCount> {
+    let mut chunks = data.get(PNG_SIGNATURE.len()..)?;
+
+    // Every chunk is its length, type, data and a crc, acTL has to come before the image data
+    while let Some((header, rest)) = chunks.split_at_checked(8) {
+        let length = u32::from_be_bytes(header[0..4].try_into().unwrap()) as usize;
+
+        match &header[4..8] {
+            b"acTL" => {
+                let plays = u32::from_be_bytes(rest.get(4..8)?.try_into().unwrap());
+                return Some(if plays == 0 { LoopCount::Infinite } else { LoopCount::Finite(plays) })
+            },
+            b"IDAT" => return None,
+            _ => chunks = rest.get(length.checked_add(4)?..)?,
+        }
+    }
+
+    None
+}
+
+/// Keeps track of the time an animation has been playing.
+/// Advance it every tick of `Platform::set_interval`, one player per sprite can share the same AnimatedImage.
+#[derive(Debug, Clone, Default)]
+pub struct Player {
+    elapsed: Duration,
+    paused: bool,
+}
+
+impl Player {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Move time forward by delta, unless paused
+    pub fn advance(&mut self, delta: Duration) {
+        if !self.paused {
+            self.elapsed += delta
+        }
+    }
+
+    /// Advance by one tick of an interval running at fps
+    pub fn tick(&mut self, fps: u32) {
+        self.advance(Duration::from_secs(1) / fps.max(1))
+    }
+
+    pub fn pause(&mut self) {
+        self.paused = true
+    }
+
+    pub fn resume(&mut self) {
+        self.paused = false
+    }
+
+    /// Start from the first frame again
+    pub fn restart(&mut self) {
+        self.elapsed = Duration::ZERO
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+
+    pub fn frame_index(&self, animation: &AnimatedImage) -> usize {
+        animation.frame_at(self.elapsed)
+    }
+
+    /// Surface to draw right now, None if the animation has no frames
+    pub fn frame<'a>(&self, animation: &'a AnimatedImage) -> Option<&'a Surface> {
+        animation.frames.get(self.frame_index(animation)).map(|frame| &frame.surface)
+    }
+
+    pub fn is_finished(&self, animation: &AnimatedImage) -> bool {
+        animation.is_finished(self.elapsed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::Surface;
+
+    use super::{AnimatedImage, Frame, LoopCount, Player};
+
+    fn animation() -> AnimatedImage {
+        let frames = [(1, 100), (2, 50), (3, 250)].map(|(value, millis)| Frame {
+            surface: Surface::from_pixels(2, 1, vec![[value, 0, 0, 255], [0, value, 0, 128]]),
+            duration: Duration::from_millis(millis),
+        });
+
+        AnimatedImage::new(frames.to_vec())
+    }
+
+    #[test]
+    fn timing() {
+        let mut animation = animation();
+        let at = |animation: &AnimatedImage, millis| animation.frame_at(Duration::from_millis(millis));
+
+        assert_eq!([0, 99, 100, 149, 150, 399, 400, 520].map(|millis| at(&animation, millis)), [0, 0, 1, 1, 2, 2, 0, 1]);
+
+        animation.loop_count = LoopCount::Finite(2);
+        assert_eq!(at(&animation, 520), 1);
+        assert_eq!(at(&animation, 800), 2);
+        assert!(animation.is_finished(Duration::from_millis(800)));
+
+        // 10 ticks at 50 fps are 200 ms
+        let mut player = Player::new();
+        (0..10).for_each(|_| player.tick(50));
+        assert_eq!(player.frame(&animation).unwrap().pixels[0], [3, 0, 0, 255]);
+        assert!(player.frame(&AnimatedImage::new(Vec::new())).is_none());
+
+        player.pause();
+        player.tick(50);
+        assert_eq!(player.elapsed(), Duration::from_millis(200));
+    }
+
+    #[test]
+    fn encode_decode() {
+        let mut animation = animation();
+        animation.loop_count = LoopCount::Finite(3);
+
+        let data = animation.encode();
+        let decoded = AnimatedImage::decode(&data).unwrap();
+
+        assert_eq!(decoded.loop_count, LoopCount::Finite(3));
+        assert_eq!(decoded.frames.len(), 3);
+        assert_eq!(decoded.frames[2].duration, Duration::from_millis(250));
+        assert_eq!(decoded.frames[1].surface.as_slice(), animation.frames[1].surface.as_slice());
+
+        assert!(AnimatedImage::decode(&data[..data.len() - 1]).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "image")]
+    fn import_gif() {
+        use image::{codecs::gif::{GifEncoder, Repeat}, Delay, RgbaImage};
+
+        let mut data = Vec::new();
+
+        {
+            let mut encoder = GifEncoder::new(&mut data);
+            encoder.set_repeat(Repeat::Finite(2)).unwrap();
+
+            for (color, millis) in [([255, 0, 0, 255], 70), ([0, 0, 255, 255], 0)] {
+                let buffer = RgbaImage::from_pixel(3, 2, image::Rgba(color));
+                encoder.encode_frame(image::Frame::from_parts(buffer, 0, 0, Delay::from_numer_denom_ms(millis, 1))).unwrap();
+            }
+        }
+
+        let animation = AnimatedImage::import(&data).unwrap();
+
+        assert_eq!(animation.loop_count, LoopCount::Finite(3));
+        assert_eq!(animation.frames.iter().map(|frame| frame.duration.as_millis()).collect::<Vec<_>>(), [70, 100]);
+        assert_eq!(animation.frames[1].surface.pixels[5], [0, 0, 255, 255]);
+    }
+}
@@ -0,0 +1,436 @@
+use std::{collections::HashMap, ops::Range};
+
+use crate::{platform::{fpia::{decode_fpia, encode_fpia, take, take_u32}, ImageLoadingError}, shapes::rect::Rect, Surface};
+
+// Fritz Preloaded ATlas
+const ATLAS_MAGIC: [u8; 4] = *b"FPAT";
+
+/// Places rects inside a fixed area, keeping track of the skyline formed by the lowest free row of every column.
+/// Every rect is placed as high as possible, then as far left as possible.
+#[derive(Debug, Clone)]
+pub struct Packer {
+    width: i32,
+    height: i32,
+
+    /// Segments of the skyline from left to right as x, y and width, together they span the full width
+    skyline: Vec<(i32, i32, i32)>,
+}
+
+impl Packer {
+    pub fn new(width: i32, height: i32) -> Self {
+        Self {
+            width,
+            height,
+            skyline: vec![(0, 0, width)],
+        }
+    }
+
+    /// Top of a w by h rect starting at segment index, None if it does not fit
+    fn fit(&self, index: usize, w: i32, h: i32) -> Option<i32> {
+        let x = self.skyline[index].0;
+
+        if x + w > self.width {
+            return None
+        }
+
+        let top = self.skyline[index..].iter()
+            .take_while(|(sx, _, _)| *sx < x + w)
+            .map(|(_, sy, _)| *sy)
+            .max()?;
+
+        (top + h <= self.height).then_some(top)
+    }
+
+    /// Reserve a w by h rect, None if there is no room left
+    pub fn insert(&mut self, w: i32, h: i32) -> Option<Rect<i32>> {
+        // Empty rects take no room, the skyline would never find a segment for them
+        if w == 0 || h == 0 {
+            return (w <= self.width && h <= self.height).then(|| Rect::new(0, 0, w, h))
+        }
+
+        let (index, y) = (0..self.skyline.len())
+            .filter_map(|index| self.fit(index, w, h).map(|y| (index, y)))
+            .min_by_key(|(index, y)| (*y, self.skyline[*index].0))?;
+
+        let x = self.skyline[index].0;
+
+        // Raise the skyline under the new rect, cutting off the segments it covers
+        self.skyline.insert(index, (x, y + h, w));
+
+        while let Some(&(sx, sy, sw)) = self.skyline.get(index + 1) {
+            let end = x + w;
+
+            if sx >= end {
+                break
+            }
+
+            if sx + sw <= end {
+                self.skyline.remove(index + 1);
+            } else {
+                self.skyline[index + 1] = (end, sy, sx + sw - end);
+                break
+            }
+        }
+
+        // Merge neighbours at the same height
+        self.skyline.dedup_by(|right, left| {
+            if left.1 == right.1 {
+                left.2 += right.2;
+                true
+            } else {
+                false
+            }
+        });
+
+        Some(Rect::new(x, y, w, h))
+    }
+}
+
+/// Many images stored in one surface, every frame is a rect of that surface.
+/// Frames are drawn with `DrawableSurface::draw_frame`, by index or after looking up their name.
+#[derive(Debug, Clone)]
+pub struct Atlas {
+    surface: Surface,
+    frames: Vec<Rect<i32>>,
+    names: HashMap<String, usize>,
+}
+
+impl Atlas {
+    /// Atlas without any frames, add them with add_frame
+    pub fn new(surface: Surface) -> Self {
+        Self {
+            surface,
+            frames: Vec::new(),
+            names: HashMap::new(),
+        }
+    }
+
+    /// Sprite sheet of equally sized frames, numbered left to right and then top to bottom.
+    /// Leftover pixels at the right and bottom edges are ignored
+    pub fn from_grid(surface: Surface, frame_width: i32, frame_height: i32) -> Self {
+        assert!(frame_width > 0 && frame_height > 0, "Grid frames must have a size, got {}x{}", frame_width, frame_height);
+
+        let columns = surface.width() / frame_width;
+        let rows = surface.height() / frame_height;
+        let mut atlas = Self::new(surface);
+
+        for row in 0..rows {
+            for column in 0..columns {
+                atlas.add_frame(Rect::new(column * frame_width, row * frame_height, frame_width, frame_height));
+            }
+        }
+
+        atlas
+    }
+
+    /// Pack all images into a new surface, frame i holds images[i]
+    pub fn pack(images: &[&Surface]) -> Self {
+        // Tallest first leaves the fewest gaps under the skyline
+        let mut order: Vec<usize> = (0..images.len()).collect();
+        order.sort_by_key(|&index| (-images[index].height(), -images[index].width()));
+
+        let area: i32 = images.iter().map(|image| image.width() * image.height()).sum();
+        let widest = images.iter().map(|image| image.width()).max().unwrap_or(0);
+        let tallest = images.iter().map(|image| image.height()).max().unwrap_or(0);
+
+        let side = ((area as f32).sqrt().ceil() as u32).next_power_of_two() as i32;
+        let (mut width, mut height) = (side.max(widest), side.max(tallest));
+
+        let frames = loop {
+            let mut packer = Packer::new(width, height);
+            let mut frames = vec![Rect::new(0, 0, 0, 0); images.len()];
+
+            let packed = order.iter().all(|&index| {
+                packer.insert(images[index].width(), images[index].height())
+                    .map(|rect| frames[index] = rect)
+                    .is_some()
+            });
+
+            if packed {
+                break frames
+            }
+
+            if width <= height {
+                width *= 2
+            } else {
+                height *= 2
+            }
+        };
+
+        // Only keep the used part
+        let used_width = frames.iter().map(|rect| rect.position.x + rect.size.x).max().unwrap_or(0);
+        let used_height = frames.iter().map(|rect| rect.position.y + rect.size.y).max().unwrap_or(0);
+        let mut surface = Surface::new(used_width as usize, used_height as usize);
+
+        for (image, frame) in images.iter().zip(&frames) {
+            for y in 0..image.height() {
+                for x in 0..image.width() {
+                    surface.set_pixel(frame.position.x + x, frame.position.y + y, image.pixels[(y * image.width() + x) as usize]);
+                }
+            }
+        }
+
+        Self {
+            surface,
+            frames,
+            names: HashMap::new(),
+        }
+    }
+
+    /// Pack all images into a new surface, every frame can also be found by its name
+    pub fn pack_named(images: &[(&str, &Surface)]) -> Self {
+        let mut atlas = Self::pack(&images.iter().map(|(_, image)| *image).collect::<Vec<_>>());
+
+        for (index, (name, _)) in images.iter().enumerate() {
+            atlas.names.insert(name.to_string(), index);
+        }
+
+        atlas
+    }
+
+    /// Add a frame, returns its index
+    pub fn add_frame(&mut self, rect: Rect<i32>) -> usize {
+        self.frames.push(rect);
+        self.frames.len() - 1
+    }
+
+    /// Add a frame that can also be found by name, returns its index
+    pub fn add_named_frame(&mut self, name: &str, rect: Rect<i32>) -> usize {
+        let index = self.add_frame(rect);
+        self.names.insert(name.to_string(), index);
+        index
+    }
+
+    pub fn frame(&self, index: usize) -> Option<Rect<i32>> {
+        self.frames.get(index).copied()
+    }
+
+    pub fn index_of(&self, name: &str) -> Option<usize> {
+        self.names.get(name).copied()
+    }
+
+    pub fn named_frame(&self, name: &str) -> Option<Rect<i32>> {
+        self.index_of(name).and_then(|index| self.frame(index))
+    }
+
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    pub fn surface(&self) -> &Surface {
+        &self.surface
+    }
+
+    /// Copy of a single frame
+    pub fn to_surface(&self, index: usize) -> Option<Surface> {
+        self.frame(index).map(|rect| self.surface.crop(rect))
+    }
+
+    /// Serialize the atlas, the frames followed by the surface as FPIA:
+    /// magic, frame count, and for every frame x, y, width, height and the length of its name followed by the name.
+    /// All numbers are big endian u32, unnamed frames have an empty name
+    pub fn encode(&self) -> Vec<u8> {
+        let mut names = vec![""; self.frames.len()];
+        for (name, index) in &self.names {
+            names[*index] = name;
+        }
+
+        let mut data = Vec::new();
+        data.extend_from_slice(&ATLAS_MAGIC);
+        data.extend_from_slice(&(self.frames.len() as u32).to_be_bytes());
+
+        for (frame, name) in self.frames.iter().zip(names) {
+            for value in [frame.position.x, frame.position.y, frame.size.x, frame.size.y, name.len() as i32] {
+                data.extend_from_slice(&(value as u32).to_be_bytes());
+            }
+
+            data.extend_from_slice(name.as_bytes());
+        }
+
+        data.extend_from_slice(&encode_fpia(&self.surface));
+        data
+    }
+
+    /// Inverse of encode, typically of an atlas built by the preloader
+    pub fn decode(data: &[u8]) -> Result<Self, ImageLoadingError> {
+        let mut data = data;
+
+        if take(&mut data, 4)? != ATLAS_MAGIC {
+            return Err(ImageLoadingError::InvalidAtlas)
+        }
+
+        let count = take_u32(&mut data)?;
+        let mut frames = Vec::new();
+        let mut names = HashMap::new();
+
+        for index in 0..count as usize {
+            let [x, y, w, h] = [(); 4].map(|_| take_u32(&mut data).map(|value| value as i32));
+            frames.push(Rect::new(x?, y?, w?, h?));
+
+            let length = take_u32(&mut data)? as usize;
+            let name = std::str::from_utf8(take(&mut data, length)?).map_err(|_| ImageLoadingError::InvalidAtlas)?;
+
+            if !name.is_empty() {
+                names.insert(name.to_string(), index);
+            }
+        }
+
+        let surface = decode_fpia(data)?;
+
+        // The frames come from the data, so their far edge may not even fit an i32
+        let inside = |rect: &Rect<i32>| rect.position.x >= 0 && rect.position.y >= 0 && rect.size.x >= 0 && rect.size.y >= 0 &&
+            rect.position.x.checked_add(rect.size.x).is_some_and(|right| right <= surface.width()) &&
+            rect.position.y.checked_add(rect.size.y).is_some_and(|bottom| bottom <= surface.height());
+
+        if !frames.iter().all(inside) {
+            return Err(ImageLoadingError::InvalidAtlas)
+        }
+
+        Ok(Self {
+            surface,
+            frames,
+            names,
+        })
+    }
+}
+
+/// Frames of an atlas played one after the other, every frame is shown for the same number of ticks
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Animation {
+    frames: Vec<usize>,
+    ticks_per_frame: i32,
+    looping: bool,
+}
+
+impl Animation {
+    /// Looping animation through the given atlas frames
+    pub fn new(frames: Vec<usize>, ticks_per_frame: i32) -> Self {
+        assert!(!frames.is_empty(), "An animation needs at least one frame");
+
+        Self {
+            frames,
+            ticks_per_frame: ticks_per_frame.max(1),
+            looping: true,
+        }
+    }
+
+    /// Looping animation through consecutive atlas frames, like a row of a grid sheet
+    pub fn range(frames: Range<usize>, ticks_per_frame: i32) -> Self {
+        Self::new(frames.collect(), ticks_per_frame)
+    }
+
+    /// Play only once, then keep showing the last frame
+    pub fn once(self) -> Self {
+        Self { looping: false, ..self }
+    }
+
+    /// Number of ticks to play every frame once
+    pub fn duration(&self) -> i32 {
+        self.frames.len() as i32 * self.ticks_per_frame
+    }
+
+    /// Atlas frame to show tick ticks after the start
+    pub fn frame(&self, tick: i32) -> usize {
+        let step = tick.max(0) / self.ticks_per_frame;
+        let last = self.frames.len() - 1;
+
+        let index = if self.looping {
+            step as usize % self.frames.len()
+        } else {
+            (step as usize).min(last)
+        };
+
+        self.frames[index]
+    }
+
+    pub fn is_finished(&self, tick: i32) -> bool {
+        !self.looping && tick >= self.duration()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{platform::ImageLoadingError, shapes::{rect::Rect, Intersect}, Surface};
+
+    use super::{Animation, Atlas, Packer};
+
+    fn filled(width: usize, height: usize, value: u8) -> Surface {
+        Surface::from_pixels(width, height, vec![[value, value, value, 255]; width * height])
+    }
+
+    #[test]
+    fn packer() {
+        let mut packer = Packer::new(16, 16);
+        let rects: Vec<_> = [(8, 8), (8, 4), (8, 4), (16, 4), (4, 4)].iter().map(|(w, h)| packer.insert(*w, *h).unwrap()).collect();
+
+        assert_eq!(rects[0], Rect::new(0, 0, 8, 8));
+        assert_eq!(rects[1], Rect::new(8, 0, 8, 4));
+        assert_eq!(rects[2], Rect::new(8, 4, 8, 4));
+        assert_eq!(rects[3], Rect::new(0, 8, 16, 4));
+        assert_eq!(rects[4], Rect::new(0, 12, 4, 4));
+
+        assert_eq!(packer.insert(16, 1), None);
+    }
+
+    #[test]
+    fn pack() {
+        let images: Vec<_> = (1..=6).map(|i| filled(i * 3, 10 - i, i as u8)).collect();
+        let atlas = Atlas::pack(&images.iter().collect::<Vec<_>>());
+
+        for (index, image) in images.iter().enumerate() {
+            let frame = atlas.frame(index).unwrap();
+            assert_eq!(atlas.to_surface(index).unwrap().as_slice(), image.as_slice());
+
+            // Shrink by half a pixel, rects sharing an edge should not count
+            let inner = Rect::new(frame.position.x as f32 + 0.5, frame.position.y as f32 + 0.5, frame.size.x as f32 - 1.0, frame.size.y as f32 - 1.0);
+
+            for other in (0..images.len()).filter(|other| *other != index) {
+                assert!(!inner.intersect(&Rect::from(atlas.frame(other).unwrap())));
+            }
+        }
+
+        // Empty images take no room
+        let atlas = Atlas::pack(&[&Surface::new(0, 3), &images[0], &Surface::new(4, 0)]);
+        assert_eq!(atlas.frame(0), Some(Rect::new(0, 0, 0, 3)));
+        assert_eq!(atlas.frame(2), Some(Rect::new(0, 0, 4, 0)));
+        assert_eq!(atlas.to_surface(1).unwrap().as_slice(), images[0].as_slice());
+    }
+
+    #[test]
+    fn grid_animation() {
+        let atlas = Atlas::from_grid(filled(34, 16, 0), 8, 8);
+        assert_eq!(atlas.len(), 8);
+        assert_eq!(atlas.frame(5), Some(Rect::new(8, 8, 8, 8)));
+
+        let walk = Animation::range(4..8, 3);
+        assert_eq!([0, 2, 3, 11, 12].map(|tick| walk.frame(tick)), [4, 4, 5, 7, 4]);
+
+        let jump = Animation::new(vec![1, 0], 2).once();
+        assert_eq!([0, 2, 100].map(|tick| jump.frame(tick)), [1, 0, 0]);
+        assert!(jump.is_finished(4) && !jump.is_finished(3));
+    }
+
+    #[test]
+    fn encode_decode() {
+        let (a, b) = (filled(2, 3, 10), filled(4, 1, 20));
+        let mut atlas = Atlas::pack_named(&[("a", &a), ("b", &b)]);
+        atlas.add_frame(Rect::new(0, 0, 1, 1));
+
+        let decoded = Atlas::decode(&atlas.encode()).unwrap();
+        assert_eq!(decoded.len(), 3);
+        assert_eq!(decoded.named_frame("b"), atlas.frame(1));
+        assert_eq!(decoded.surface().as_slice(), atlas.surface().as_slice());
+
+        assert!(Atlas::decode(&atlas.encode()[..20]).is_err());
+
+        // A frame reaching past i32::MAX
+        let mut data = atlas.encode();
+        data[8..16].copy_from_slice(&[0x7f, 0xff, 0xff, 0xff, 0, 0, 0, 0]);
+        data[16..20].copy_from_slice(&1u32.to_be_bytes());
+        assert!(matches!(Atlas::decode(&data), Err(ImageLoadingError::InvalidAtlas)));
+    }
+}
@@ -3,7 +3,7 @@ use std::{cell::Cell, fmt::Debug, rc::Rc};
 mod inner_element;
 use inner_element::InnerElement;
 
-use crate::{platform::Event, printer::Print, shapes::rect::Rect, SurfaceView};
+use crate::{platform::Event, printer::Print, shapes::rect::Rect, OverflowBehavior, SurfaceView};
 
 pub mod div;
 pub mod text;
@@ -110,7 +110,9 @@ impl FUI {
 
         // TODO: Should not always have to redraw
         // if invalidation.needs_redraw() {
+            // Children clip to their own bounding box
             let ctx = ctx.tee();
+            ctx.set_overflow_behavior(OverflowBehavior::Hidden);
             self.element.draw(&ctx);
         // }
     }
@@ -1,6 +1,6 @@
 use std::{cell::{Cell, RefCell}, rc::Rc};
 
-use crate::{colors, platform::Event, printer::Print, DrawableSurface};
+use crate::{colors, math::Vec2, platform::Event, printer::Print, Color, DrawableSurface, Gradient};
 
 use super::{Element, Invalidation};
 
@@ -63,7 +63,14 @@ impl Element for Button {
     }
 
     fn draw(&self, ctx: &crate::SurfaceView) {
-        let color = if self.pressed.get() { colors::MAGENTA } else { colors::RED };
-        ctx.background(color)
+        let color = Color::from(if self.pressed.get() { colors::MAGENTA } else { colors::RED });
+
+        // Lit from above, the style is shared with everything drawn after the button
+        let style = ctx.style();
+        ctx.fill_paint(Gradient::linear(Vec2::new(0, 0), Vec2::new(0, self.height))
+            .stop(0.0, color.lighten(0.15))
+            .stop(1.0, color.darken(0.15)));
+        ctx.rect(0, 0, self.width, self.height);
+        ctx.set_style(style);
     }
 }
\ No newline at end of file
@@ -1,12 +1,12 @@
 use std::{cell::Cell, rc::Rc};
 
-use crate::{math::Transform, platform::Event, printer::{Print, Printer}, shapes::{point::Point, rect::Rect, Intersect}, SurfaceView};
+use crate::{math::{Transform, Vec2}, platform::Event, printer::{Print, Printer}, shapes::{point::Point, rect::Rect, Intersect}, SurfaceView};
 
 use super::{Element, Invalidation};
 
 pub struct InnerElement {
     element: Rc<dyn Element>,
-    clip: Cell<Option<Rect>>,
+    clip: Cell<Option<Rect<i32>>>,
     width: Cell<Option<i32>>,
     height: Cell<Option<(i32, i32)>>,
 }
@@ -32,18 +32,18 @@ impl Element for InnerElement {
         };
 
         if let Event::MouseDown { x, y, .. } = event {
-            if !clip.encloses_point(&Point::new(x, y)) {
+            if !Rect::from(clip).encloses_point(&Point::from(Vec2::new(x, y))) {
                 return
             }
         }
 
         if let Event::MouseMove { x, y, .. } = event {
-            if !clip.encloses_point(&Point::new(x, y)) {
+            if !Rect::from(clip).encloses_point(&Point::from(Vec2::new(x, y))) {
                 return
             }
         }
 
-        event.translate(-clip.position);
+        event.translate((-clip.position).into());
 
         self.element.event(event);
     }
@@ -88,7 +88,7 @@ impl Element for InnerElement {
 }
 
 impl InnerElement {
-    pub fn clip(&self, clip: Rect) {
+    pub fn clip(&self, clip: Rect<i32>) {
         self.clip.set(Some(clip));
     }
 }
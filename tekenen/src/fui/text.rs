@@ -1,38 +1,67 @@
 use std::{cell::{Cell, RefCell}, rc::Rc};
 
-use crate::{colors, platform::Event, printer::Print, DrawableSurface, SurfaceView};
+use crate::{colors, platform::Event, printer::Print, DrawableSurface, Font, RichText, SurfaceView, TextLayout};
 
 use super::{Element, Invalidation};
 
+/// Height of the text in pixels
+const TEXT_HEIGHT: i32 = 16;
+
 pub struct Text {
-    text: RefCell<String>,
+    text: RefCell<RichText>,
+    font: RefCell<Font>,
+
+    /// Width the text was wrapped at during the last layout
+    max_width: Cell<Option<i32>>,
+
     dirty: Cell<Invalidation>,
 }
 
 impl Text {
     pub fn new(text: &str) -> Rc<Self> {
+        Self::new_rich(RichText::from(text))
+    }
+
+    /// Runs without a color are white
+    pub fn new_rich(text: RichText) -> Rc<Self> {
         Rc::new(Self {
-            text: RefCell::new(text.to_string()),
+            text: RefCell::new(text),
+            font: RefCell::new(Font::builtin()),
+            max_width: Cell::new(None),
             dirty: Cell::new(Invalidation::Layout),
         })
     }
 
     pub fn set_text(&self, text: String) {
+        self.set_rich_text(RichText::from(text))
+    }
+
+    pub fn set_rich_text(&self, text: RichText) {
         let mut old_text = self.text.borrow_mut();
+        let font = self.font.borrow();
 
-        // Length chaged, relayout
-        if old_text.len() != text.len() {
+        // Width changed, relayout
+        if font.measure(old_text.as_str(), TEXT_HEIGHT as f32) != font.measure(text.as_str(), TEXT_HEIGHT as f32) {
             *old_text = text;
             self.dirty.set(Invalidation::Layout);
             return
         }
 
-        // Same length but text changed, redraw only
+        // Same width but text or styles changed, redraw only
         if *old_text != text {
             *old_text = text;
             self.dirty.set(Invalidation::Draw);
         }
     }
+
+    pub fn set_font(&self, font: Font) {
+        *self.font.borrow_mut() = font;
+        self.dirty.set(Invalidation::Layout);
+    }
+
+    fn layout(&self) -> TextLayout {
+        TextLayout { max_width: self.max_width.get(), ..Default::default() }
+    }
 }
 
 impl Print for Text {
@@ -40,7 +69,7 @@ impl Print for Text {
         printer.println(&"<Text>")?;
         printer.indent(2);
         printer.print_previous()?;
-        printer.property("text", &self.text)?;
+        printer.property("text", &self.text.borrow().to_string())?;
         printer.debug().property("dirty", &self.dirty)
     }
 }
@@ -55,15 +84,24 @@ impl Element for Text {
     }
 
     fn get_width(&self) -> i32 {
-        16 * self.text.borrow().len() as i32
+        self.font.borrow().measure(self.text.borrow().as_str(), TEXT_HEIGHT as f32).width.ceil() as i32
     }
 
     fn get_height(&self, width: i32) -> i32 {
-        (self.get_width() + width - 1) / width * 16
+        self.max_width.set(Some(width));
+
+        let font = self.font.borrow();
+        let lines = self.layout().lines(&font, self.text.borrow().as_str(), TEXT_HEIGHT as f32);
+
+        (lines.len() as f32 * font.line_height(TEXT_HEIGHT as f32)).ceil() as i32
     }
 
     fn draw(&self, tekenen: &SurfaceView) {
+        let previous = tekenen.font();
+
+        tekenen.set_font(self.font.borrow().clone());
         tekenen.fill_color(colors::WHITE);
-        tekenen.text(&self.text.borrow(), 0, 0, 16);
+        tekenen.rich_text_layout(&self.text.borrow(), 0, 0, TEXT_HEIGHT, &self.layout());
+        tekenen.set_font(previous);
     }
 }
\ No newline at end of file
@@ -12,7 +12,7 @@ pub use text_fragment::TextNode;
 pub mod p;
 pub use p::P;
 
-use crate::{math::{IndefRange, Vec2}, platform::Event, shapes::rect::Rect, DrawableSurface, SurfaceView};
+use crate::{math::{IndefRange, Vec2}, platform::Event, shapes::rect::Rect, SurfaceView};
 
 
 use super::{style::{CSSDisplay, FormattingInfo, Style}, tree::{Tree, TreeData}};
@@ -85,7 +85,7 @@ pub trait LayoutBox: Stylable {
     fn get_width_from_height(&self, height: i32, context: &FormattingInfo) -> i32;
     fn get_height_from_width(&self, width: i32, context: &FormattingInfo) -> i32;
 
-    fn get_painter(self: Rc<Self>, content_box: Rect, context: &FormattingInfo) -> Rc<dyn PaintElement>;
+    fn get_painter(self: Rc<Self>, content_box: Rect<i32>, context: &FormattingInfo) -> Rc<dyn PaintElement>;
 
     fn is_inline(&self) -> bool {
         todo!();
@@ -343,6 +343,12 @@ impl FormattingContext for BlockBlockFormattingContext {
     fn run(&self, node: &Rc<LayoutNode>, info: &FormattingInfo) -> Option<PainterTree> {
         assert!(!*node.children_are_inline.borrow());
 
+        // The color of the element is the currentColor for itself and everything inside
+        let info = &match node.element.as_ref() {
+            Some(element) => info.with_style(&element.get_style().borrow()),
+            None => info.clone(),
+        };
+
         if node.has_children() {
             return self.run_parent(node, info)
         }
@@ -387,6 +393,7 @@ impl BlockBlockFormattingContext {
         for child in node.tree_data.iter() {
             let child_info = FormattingInfo {
                 containing_block: Rect::new_vec(Vec2::new(available_content_rect.position.x, current_y), available_content_rect.size),
+                current_color: info.current_color,
             };
 
             // TODO: Can be better
@@ -445,8 +452,6 @@ impl BlockBlockFormattingContext {
 
         let element = element.clone().get_painter(content_box, info);
 
-        dbg!(content_box);
-
         PainterTree {
             margin_box: content_box,
             border_box: content_box,
@@ -590,7 +595,7 @@ impl<'a> FormattingContext for InlineFormattingContext<'a> {
         let margin_box = Rect::new_vec(info.containing_block.position, Vec2::new(info.containing_block.size.x, current_y - start_y));
 
         Some(PainterTree {
-            margin_box: margin_box,
+            margin_box,
             border_box: margin_box,
             padding_box: margin_box,
             content_box: margin_box,
@@ -900,10 +905,10 @@ pub trait PaintElement: Stylable {
 
 #[derive(Debug)]
 pub struct PainterTree {
-    pub margin_box: Rect,
-    pub border_box: Rect,
-    pub padding_box: Rect,
-    pub content_box: Rect,
+    pub margin_box: Rect<i32>,
+    pub border_box: Rect<i32>,
+    pub padding_box: Rect<i32>,
+    pub content_box: Rect<i32>,
     pub element: Option<Rc<dyn PaintElement>>,
     pub context: FormattingInfo,
     pub children: Vec<PainterTree>
@@ -932,21 +937,12 @@ impl Display for PainterTree {
 
 impl PainterTree {
     pub fn paint(&self, target: &mut SurfaceView) {
-        // let element = self.element.borrow();
-        // let style = element.get_style();
-
-        // let bg_color = style.background_color.solve(&self.context);
-
-        // if bg_color[3] > 0 {
-        //     target.set_translation_vec(self.border_box.position);
-        //     target.rect_vec(Vec2::zero(), self.border_box.size, bg_color);
-        // }
-
-        todo!("Read next line");
-        // target.set_translation_vec(self.content_box.position);
-
+        // Elements draw from their own top left corner
         if let Some(element) = self.element.as_ref() {
+            target.push();
+            target.translate(self.content_box.position);
             element.draw(target, &self.context, self.content_box.size);
+            target.pop();
         }
 
         for element in self.children.iter() {
@@ -1,6 +1,6 @@
 use std::{cell::RefCell, rc::Rc};
 
-use crate::{math::{IndefRange, Vec2, Zero}, shapes::rect::Rect, html::style::{CSSDisplayShorthand, FormattingInfo}, DrawableSurface, SurfaceView};
+use crate::{math::{IndefRange, Vec2}, shapes::rect::Rect, html::style::{CSSDisplayShorthand, FormattingInfo}, DrawableSurface, SurfaceView};
 
 use super::{BlockLayoutBox, DomElement, InlineFormattingContext, LayoutBox, PaintElement, Stylable, Style};
 
@@ -15,9 +15,7 @@ pub struct Div {
 
 impl Div {
     pub fn new(children: Vec<Rc<dyn DomElement>>) -> Rc<Self> {
-        let mut style = Style::default();
-
-        style.display = CSSDisplayShorthand::Block.into();
+        let style = Style { display: CSSDisplayShorthand::Block.into(), ..Default::default() };
 
         Rc::new(Self {
             style: RefCell::new(style),
@@ -26,9 +24,7 @@ impl Div {
     }
 
     pub fn new_fn(children: Vec<Rc<dyn DomElement>>, fun: impl FnOnce(&mut Div)) -> Rc<Self> {
-        let mut style = Style::default();
-
-        style.display = CSSDisplayShorthand::Block.into();
+        let style = Style { display: CSSDisplayShorthand::Block.into(), ..Default::default() };
 
         let mut div = Self {
             style: RefCell::new(style),
@@ -86,7 +82,7 @@ impl LayoutBox for Div {
         todo!()
     }
 
-    fn get_painter(self: Rc<Self>, content_box: Rect, context: &FormattingInfo) -> Rc<dyn PaintElement> {
+    fn get_painter(self: Rc<Self>, content_box: Rect<i32>, context: &FormattingInfo) -> Rc<dyn PaintElement> {
         self
     }
 
@@ -108,7 +104,10 @@ impl PaintElement for Div {
         let color = self.get_style().borrow().background_color.solve(context);
 
         if color[3] > 0 {
-            target.rect_vec(Vec2::zero(), space, color)
+            let style = target.style();
+            target.fill_color(color);
+            target.rect(0, 0, space.x, space.y);
+            target.set_style(style);
         }
     }
 }
\ No newline at end of file
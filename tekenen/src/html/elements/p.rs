@@ -1,7 +1,7 @@
 use std::{cell::RefCell, rc::Rc};
 
 use super::{DomElement, InlineFormattingContext, LayoutBox, PaintElement, Stylable, TextNode};
-use crate::{math::{IndefRange, Vec2, Zero}, platform::Event, shapes::rect::Rect, html::style::{FormattingInfo, Style}, DrawableSurface, SurfaceView};
+use crate::{math::{IndefRange, Vec2}, platform::Event, shapes::rect::Rect, html::style::{FormattingInfo, Style}, DrawableSurface, SurfaceView};
 
 #[derive(Debug)]
 pub struct P {
@@ -93,7 +93,7 @@ impl LayoutBox for P {
         todo!()
     }
 
-    fn get_painter(self: Rc<Self>, content_box: Rect, context: &FormattingInfo) -> Rc<dyn PaintElement> {
+    fn get_painter(self: Rc<Self>, content_box: Rect<i32>, context: &FormattingInfo) -> Rc<dyn PaintElement> {
         self.clone()
     }
 
@@ -111,7 +111,10 @@ impl PaintElement for P {
         let color = self.get_style().borrow().background_color.solve(context);
 
         if color[3] > 0 {
-            target.rect_vec(Vec2::zero(), space, color)
+            let style = target.style();
+            target.fill_color(color);
+            target.rect(0, 0, space.x, space.y);
+            target.set_style(style);
         }
     }
 }
\ No newline at end of file
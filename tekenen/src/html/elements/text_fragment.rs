@@ -2,20 +2,20 @@
 
 use std::{cell::RefCell, rc::Rc};
 
-use crate::{colors, math::{IndefRange, Vec2}, shapes::rect::Rect, html::style::{FormattingInfo, Style}, DrawableSurface, Font, SurfaceView};
+use crate::{math::{IndefRange, Vec2}, shapes::rect::Rect, html::style::{FormattingInfo, Style}, DrawableSurface, RichText, SurfaceView};
 
 use super::{DomElement, FormattingContext, InlineFormattingContext, LayoutBox, LineBox, PaintElement, Stylable};
 
 #[derive(Debug)]
 pub struct TextFragment {
-    pub text: RefCell<String>,
+    pub text: RefCell<RichText>,
 }
 
 
 impl TextFragment {
-    pub fn new(text: &str) -> Rc<Self> {
+    pub fn new(text: RichText) -> Rc<Self> {
         Rc::new(Self {
-            text: RefCell::new(text.to_owned()),
+            text: RefCell::new(text),
             // owner
         })
     }
@@ -37,7 +37,7 @@ impl LayoutBox for TextFragment {
     }
 
     fn get_width_from_height(&self, height: i32, context: &FormattingInfo) -> i32 {
-        self.text.borrow().len() as i32 * 16
+        self.text.borrow().as_str().chars().count() as i32 * 16
     }
 
     fn get_inner_min_max_content(&self, context: &FormattingInfo) -> Vec2<IndefRange> {
@@ -48,7 +48,7 @@ impl LayoutBox for TextFragment {
         todo!()
     }
 
-    fn get_painter(self: Rc<Self>, content_box: Rect, context: &FormattingInfo) -> Rc<dyn PaintElement> {
+    fn get_painter(self: Rc<Self>, content_box: Rect<i32>, context: &FormattingInfo) -> Rc<dyn PaintElement> {
         self
     }
 
@@ -64,20 +64,24 @@ impl LayoutBox for TextFragment {
 
 impl PaintElement for TextFragment {
     fn draw(&self, target: &mut SurfaceView, context: &FormattingInfo, space: Vec2) {
-        target.text(&self.text.borrow(), 0, 0, Font::new(16, colors::WHITE));
+        target.rich_text(&self.text.borrow(), 0, 0, 16);
     }
 }
 
 #[derive(Debug)]
 pub struct TextNode {
-    pub text: String,
+    pub text: RichText,
     // pub owner: Box<WeakWrapper<dyn DomElement>>
 }
 
 impl TextNode {
     pub fn new(text: &str) -> Rc<Self> {
+        Self::new_rich(RichText::from(text))
+    }
+
+    pub fn new_rich(text: RichText) -> Rc<Self> {
         Rc::new(Self {
-            text: text.to_owned(),
+            text,
             // owner
         })
     }
@@ -154,7 +158,7 @@ impl LayoutBox for TextNode {
         todo!()
     }
 
-    fn get_painter(self: Rc<Self>, content_box: Rect, context: &FormattingInfo) -> Rc<dyn PaintElement> {
+    fn get_painter(self: Rc<Self>, content_box: Rect<i32>, context: &FormattingInfo) -> Rc<dyn PaintElement> {
         self
     }
 
@@ -166,32 +170,38 @@ impl LayoutBox for TextNode {
         let mut current_line = inline.get_line(info).0;
         let mut lines: Vec<(Rc<LineBox>, Rc<dyn LayoutBox>)> = Vec::new();
 
-        let mut current_string = String::new();
+        let mut current_string = RichText::new();
         let mut current_width = 0;
 
-        for token in self.text.split_whitespace() {
+        let text = self.text.as_str();
+
+        for token in text.split_whitespace() {
             let token_width = token.chars().count() as i32 * 16;
 
             // Dispatch line if the token does not fit
             if current_width + token_width > current_line.available_width() && !current_string.is_empty() {
-                let fragment = TextFragment::new(&current_string) as Rc<dyn LayoutBox>;
+                let fragment = TextFragment::new(std::mem::take(&mut current_string)) as Rc<dyn LayoutBox>;
                 current_line.add(Rc::clone(&fragment));
                 lines.push((Rc::clone(&current_line), Rc::clone(&fragment)));
 
                 // Reset
                 current_line = inline.get_new_line(info).0;
-                current_string = String::new();
                 current_width = 0;
             }
 
-            current_string.push_str(token);
-            current_string.push(' ');
+            // Tokens keep their styles, the space after takes the style of the token
+            let index = token.as_ptr() as usize - text.as_ptr() as usize;
+            let end = index + token.len();
+            for (run, style) in self.text.slice(index..end).runs() {
+                current_string.push(run, style);
+            }
+            current_string.push(" ", self.text.style_at(end - 1));
             current_width += token_width + 16;
         }
 
         // Dispatch the last line
         if !current_string.is_empty() {
-            let fragment = TextFragment::new(&current_string);
+            let fragment = TextFragment::new(current_string);
             current_line.add(fragment.clone());
             lines.push((Rc::clone(&current_line), fragment.clone()));
         }
@@ -1,10 +1,31 @@
 use std::marker::PhantomData;
 
-use crate::{colors, math::{clamp, IndefRange, RangeTrait, Vec2}, shapes::{rect::Rect, Sides}, Pixel};
+use crate::{colors, math::{clamp, IndefRange, RangeTrait, Vec2}, shapes::{rect::Rect, Sides}, Color, ColorParseError, Pixel};
 
 #[derive(Debug, Clone)]
 pub struct FormattingInfo {
-    pub containing_block: Rect,
+    pub containing_block: Rect<i32>,
+
+    /// Computed value of the 'color' property, inherited from the parent
+    pub current_color: Color,
+}
+
+impl FormattingInfo {
+    /// Info for the root element, the initial 'color' is CanvasText
+    pub fn root(containing_block: Rect<i32>) -> Self {
+        Self {
+            containing_block,
+            current_color: Color::from_pixel(CSSSystemColor::CanvasText.get_color()),
+        }
+    }
+
+    /// Info for an element with this style, its 'color' becomes the currentColor
+    pub fn with_style(&self, style: &Style) -> Self {
+        Self {
+            current_color: style.color.solve(self),
+            ..self.clone()
+        }
+    }
 }
 
 // https://developer.mozilla.org/en-US/docs/Web/CSS/length
@@ -338,19 +359,110 @@ impl Default for CSSBorderStyle {
 #[derive(Debug, Clone)]
 enum CSSColor {
     /// A color value. See <color> values for the syntax of individual color values.
-    ColorBase(Pixel),
+    ColorBase(Color),
     /// The value of the 'color' property. The computed value of the 'currentColor' keyword is the computed value of the 'color' property. If the 'currentColor' keyword is set on the 'color' property itself, it is treated as 'color: inherit'.
     CurrentColor,
     /// A system color. See <system-color> for the list of possible system colors.
-    SystemColor
+    SystemColor(CSSSystemColor)
 }
 
 impl CSSColor {
     fn get_color(&self, context: &FormattingInfo) -> Pixel {
         match self {
-            Self::ColorBase(pixel) => *pixel,
-            Self::CurrentColor => todo!(),
-            Self::SystemColor => todo!(),
+            Self::ColorBase(color) => color.to_pixel(),
+            Self::CurrentColor => context.current_color.to_pixel(),
+            Self::SystemColor(system) => system.get_color(),
+        }
+    }
+}
+
+impl std::str::FromStr for CSSColor {
+    type Err = ColorParseError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let keyword = text.trim().to_ascii_lowercase();
+
+        if keyword == "currentcolor" {
+            return Ok(Self::CurrentColor)
+        }
+
+        if let Some(system) = CSSSystemColor::from_keyword(&keyword) {
+            return Ok(Self::SystemColor(system))
+        }
+
+        text.parse().map(Self::ColorBase)
+    }
+}
+
+// https://developer.mozilla.org/en-US/docs/Web/CSS/system-color
+// https://drafts.csswg.org/css-color/#css-system-colors
+
+// Colors of a light theme, there is no platform theme to follow
+
+#[derive(Debug, Clone, Copy)]
+enum CSSSystemColor {
+    AccentColor,
+    AccentColorText,
+    ActiveText,
+    ButtonBorder,
+    ButtonFace,
+    ButtonText,
+    Canvas,
+    CanvasText,
+    Field,
+    FieldText,
+    GrayText,
+    Highlight,
+    HighlightText,
+    LinkText,
+    Mark,
+    MarkText,
+    SelectedItem,
+    SelectedItemText,
+    VisitedText,
+}
+
+impl CSSSystemColor {
+    fn from_keyword(keyword: &str) -> Option<Self> {
+        let system = match keyword {
+            "accentcolor" => Self::AccentColor,
+            "accentcolortext" => Self::AccentColorText,
+            "activetext" => Self::ActiveText,
+            "buttonborder" => Self::ButtonBorder,
+            "buttonface" => Self::ButtonFace,
+            "buttontext" => Self::ButtonText,
+            "canvas" => Self::Canvas,
+            "canvastext" => Self::CanvasText,
+            "field" => Self::Field,
+            "fieldtext" => Self::FieldText,
+            "graytext" => Self::GrayText,
+            "highlight" => Self::Highlight,
+            "highlighttext" => Self::HighlightText,
+            "linktext" => Self::LinkText,
+            "mark" => Self::Mark,
+            "marktext" => Self::MarkText,
+            "selecteditem" => Self::SelectedItem,
+            "selecteditemtext" => Self::SelectedItemText,
+            "visitedtext" => Self::VisitedText,
+            _ => return None,
+        };
+
+        Some(system)
+    }
+
+    fn get_color(&self) -> Pixel {
+        match self {
+            Self::AccentColor | Self::Highlight | Self::SelectedItem => colors::css::DODGERBLUE,
+            Self::AccentColorText | Self::HighlightText | Self::SelectedItemText => colors::WHITE,
+            Self::ActiveText => colors::RED,
+            Self::ButtonBorder => colors::css::DARKGRAY,
+            Self::ButtonFace => colors::css::GAINSBORO,
+            Self::ButtonText | Self::CanvasText | Self::FieldText | Self::MarkText => colors::BLACK,
+            Self::Canvas | Self::Field => colors::WHITE,
+            Self::GrayText => colors::GRAY,
+            Self::LinkText => colors::css::MEDIUMBLUE,
+            Self::Mark => colors::YELLOW,
+            Self::VisitedText => colors::PURPLE,
         }
     }
 }
@@ -716,8 +828,14 @@ pub struct CSSBackgroundColor {
 }
 
 impl CSSBackgroundColor {
-    pub fn set(&mut self, color: Pixel) {
-        self.color = CSSColor::ColorBase(color);
+    pub fn set(&mut self, color: impl Into<Color>) {
+        self.color = CSSColor::ColorBase(color.into());
+    }
+
+    /// Any CSS <color>, including currentColor and the system colors
+    pub fn set_str(&mut self, color: &str) -> Result<(), ColorParseError> {
+        self.color = color.parse()?;
+        Ok(())
     }
 
     pub fn solve(&self, context: &FormattingInfo) -> Pixel {
@@ -728,7 +846,50 @@ impl CSSBackgroundColor {
 impl Default for CSSBackgroundColor {
     fn default() -> Self {
         Self {
-            color: CSSColor::ColorBase(colors::css::TRANSPARENT)
+            color: CSSColor::ColorBase(Color::from_pixel(colors::css::TRANSPARENT))
+        }
+    }
+}
+
+// https://developer.mozilla.org/en-US/docs/Web/CSS/color
+// https://drafts.csswg.org/css-color/#the-color-property
+
+// Name:	color
+// Value:	<color>
+// Initial:	CanvasText
+// Applies to:	all elements and text
+// Inherited:	yes
+// Percentages:	N/A
+// Computed value:	computed color
+// Canonical order:	per grammar
+// Animation type:	by computed value
+
+#[derive(Debug, Clone, Default)]
+pub struct CSSTextColor {
+    /// None inherits the color of the parent
+    color: Option<CSSColor>
+}
+
+impl CSSTextColor {
+    pub fn set(&mut self, color: impl Into<Color>) {
+        self.color = Some(CSSColor::ColorBase(color.into()));
+    }
+
+    /// Any CSS <color>, currentColor on 'color' itself inherits
+    pub fn set_str(&mut self, color: &str) -> Result<(), ColorParseError> {
+        self.color = match color.parse()? {
+            CSSColor::CurrentColor => None,
+            color => Some(color),
+        };
+
+        Ok(())
+    }
+
+    pub fn solve(&self, context: &FormattingInfo) -> Color {
+        match &self.color {
+            Some(CSSColor::ColorBase(color)) => *color,
+            Some(color) => Color::from_pixel(color.get_color(context)),
+            None => context.current_color,
         }
     }
 }
@@ -782,6 +943,7 @@ pub struct Style {
     pub display: CSSDisplay,
     pub flex_direction: CSSFlexDirection,
     pub background_color: CSSBackgroundColor,
+    pub color: CSSTextColor,
     pub float: CSSFloat,
 }
 
@@ -866,4 +1028,32 @@ impl Style {
 
     //     (base, self.get_computed_margin(context))
     // }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{colors, shapes::rect::Rect};
+
+    use super::{FormattingInfo, Style};
+
+    #[test]
+    fn current_color() {
+        let root = FormattingInfo::root(Rect::new(0, 0, 100, 100));
+        let mut style = Style::default();
+
+        style.background_color.set_str("currentColor").unwrap();
+        assert_eq!(style.background_color.solve(&root.with_style(&style)), colors::BLACK);
+
+        style.color.set_str("red").unwrap();
+        let info = root.with_style(&style);
+        assert_eq!(style.background_color.solve(&info), colors::RED);
+
+        // Children inherit the color unless they set their own
+        let child = Style::default();
+        assert_eq!(child.background_color.solve(&info.with_style(&child)), colors::css::TRANSPARENT);
+        assert_eq!(info.with_style(&child).current_color, info.current_color);
+
+        style.background_color.set_str("Canvas").unwrap();
+        assert_eq!(style.background_color.solve(&info), colors::WHITE);
+    }
+}
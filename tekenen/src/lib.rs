@@ -19,18 +19,28 @@ mod tekenen;
 
 
 
-pub use tekenen::{SurfaceView, Surface, colors, Pixel, DrawableSurface, OverflowBehavior};
+pub use tekenen::{SurfaceView, Surface, colors, Color, ColorParseError, ColorSpace, Pixel, Paint, Gradient, GradientKind, ColorStop, Pattern, SpreadMode, DrawableSurface, OverflowBehavior, CompositeMode, ImageFormat, ImageSavingError, Font, FontLoadingError, RichText, Sampling, TextAlign, TextBaseline, TextLayout, TextMetrics, TextStyle, Style, LineCap, LineJoin};
 
 /// UI, Describe layout in a 'css' manner
-// pub mod html;
+pub mod html;
 pub mod fui;
 
 pub mod shapes;
 
+/// Sprite sheets, many images packed into one surface
+pub mod atlas;
+
+/// Animated images made of timed frames, imported from GIF and APNG
+pub mod animation;
+
 pub mod math;
 
 pub mod printer;
 
+/// Golden image testing, compare rendered surfaces against stored references
+#[cfg(feature = "image")]
+pub mod snapshot;
+
 #[cfg(all(feature = "server", not(target_family = "wasm")))]
 pub fn server() {
     use rouille;
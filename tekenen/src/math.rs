@@ -10,8 +10,9 @@ pub use range::*;
 mod zero;
 pub use zero::*;
 
+/// Move and scale in place, shapes keep fractions of a pixel
 pub trait Transform {
-    fn translate(&mut self, translation: Vec2);
+    fn translate(&mut self, translation: Vec2<f32>);
     fn scale(&mut self, scale: f32);
 }
 
@@ -2,8 +2,9 @@ use std::ops::Mul;
 
 use super::Vec2;
 
-pub struct Mat<const COLS: usize, const ROWS: usize> {
-    pub matrix: [[i32; ROWS]; COLS]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Mat<const COLS: usize, const ROWS: usize, T = i32> {
+    pub matrix: [[T; ROWS]; COLS]
 }
 
 pub type TransfromMatrix = Mat<2, 3>;
@@ -17,4 +18,151 @@ impl Mul<Vec2> for TransfromMatrix {
             y: rhs.x * self.matrix[1][0] + rhs.y * self.matrix[1][1] + self.matrix[1][2],
         }
     }
-}
\ No newline at end of file
+}
+
+/// Affine transformation in 2D, the bottom row is always 0 0 1.
+/// Multiplying `a * b` gives the transformation that applies b first, then a.
+pub type Affine = Mat<2, 3, f32>;
+
+impl Default for Affine {
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+impl Affine {
+    pub fn new(a: f32, b: f32, c: f32, d: f32, e: f32, f: f32) -> Self {
+        // | a c e |
+        // | b d f |
+        Self {
+            matrix: [[a, c, e], [b, d, f]]
+        }
+    }
+
+    pub fn identity() -> Self {
+        Self::new(1.0, 0.0, 0.0, 1.0, 0.0, 0.0)
+    }
+
+    pub fn translation(x: f32, y: f32) -> Self {
+        Self::new(1.0, 0.0, 0.0, 1.0, x, y)
+    }
+
+    pub fn scale(x: f32, y: f32) -> Self {
+        Self::new(x, 0.0, 0.0, y, 0.0, 0.0)
+    }
+
+    /// Clockwise on screen, angle in radians
+    pub fn rotation(angle: f32) -> Self {
+        let (sin, cos) = angle.sin_cos();
+        Self::new(cos, sin, -sin, cos, 0.0, 0.0)
+    }
+
+    /// Shear along x and y, angles in radians
+    pub fn skew(x: f32, y: f32) -> Self {
+        Self::new(1.0, y.tan(), x.tan(), 1.0, 0.0, 0.0)
+    }
+
+    pub fn determinant(&self) -> f32 {
+        let [[a, c, _], [b, d, _]] = self.matrix;
+        a * d - b * c
+    }
+
+    /// None if the transformation flattens everything onto a line
+    pub fn inverse(&self) -> Option<Self> {
+        let det = self.determinant();
+
+        if det == 0.0 || !det.is_finite() {
+            return None
+        }
+
+        let [[a, c, e], [b, d, f]] = self.matrix;
+
+        Some(Self::new(
+            d / det,
+            -b / det,
+            -c / det,
+            a / det,
+            (c * f - d * e) / det,
+            (b * e - a * f) / det,
+        ))
+    }
+
+    pub fn get_translation(&self) -> Vec2<f32> {
+        Vec2::new(self.matrix[0][2], self.matrix[1][2])
+    }
+
+    /// The scale along x and y if there is no rotation, skew or mirroring, otherwise None
+    pub fn get_axis_scale(&self) -> Option<Vec2<f32>> {
+        let [[a, c, _], [b, d, _]] = self.matrix;
+
+        (b == 0.0 && c == 0.0 && a > 0.0 && d > 0.0).then_some(Vec2::new(a, d))
+    }
+
+    /// How much lengths grow on average, the square root of the area scale
+    pub fn get_scale_factor(&self) -> f32 {
+        self.determinant().abs().sqrt()
+    }
+
+    /// Transform a direction, the translation is ignored
+    pub fn transform_vector(&self, vector: Vec2<f32>) -> Vec2<f32> {
+        let [[a, c, _], [b, d, _]] = self.matrix;
+        Vec2::new(a * vector.x + c * vector.y, b * vector.x + d * vector.y)
+    }
+}
+
+impl Mul<Vec2<f32>> for Affine {
+    type Output = Vec2<f32>;
+
+    fn mul(self, rhs: Vec2<f32>) -> Self::Output {
+        let [[a, c, e], [b, d, f]] = self.matrix;
+        Vec2::new(a * rhs.x + c * rhs.y + e, b * rhs.x + d * rhs.y + f)
+    }
+}
+
+impl Mul for Affine {
+    type Output = Affine;
+
+    fn mul(self, rhs: Affine) -> Self::Output {
+        let [[a1, c1, e1], [b1, d1, f1]] = self.matrix;
+        let [[a2, c2, e2], [b2, d2, f2]] = rhs.matrix;
+
+        Self::new(
+            a1 * a2 + c1 * b2,
+            b1 * a2 + d1 * b2,
+            a1 * c2 + c1 * d2,
+            b1 * c2 + d1 * d2,
+            a1 * e2 + c1 * f2 + e1,
+            b1 * e2 + d1 * f2 + f1,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::FRAC_PI_2;
+
+    use crate::math::Vec2;
+
+    use super::Affine;
+
+    fn assert_close(a: Vec2<f32>, b: Vec2<f32>) {
+        assert!((a.x - b.x).abs() < 1e-4 && (a.y - b.y).abs() < 1e-4, "{a:?} != {b:?}");
+    }
+
+    #[test]
+    fn compose_and_invert() {
+        let matrix = Affine::translation(10.0, 5.0) * Affine::rotation(FRAC_PI_2) * Affine::scale(2.0, 3.0);
+
+        // Scaled to (2, 0), rotated clockwise to (0, 2), then moved
+        assert_close(matrix * Vec2::new(1.0, 0.0), Vec2::new(10.0, 7.0));
+        assert_close(matrix * Vec2::new(0.0, 1.0), Vec2::new(7.0, 5.0));
+
+        let inverse = matrix.inverse().unwrap();
+        assert_close(inverse * (matrix * Vec2::new(3.0, -4.0)), Vec2::new(3.0, -4.0));
+        assert_close((matrix * inverse) * Vec2::new(1.5, 2.5), Vec2::new(1.5, 2.5));
+
+        assert!(Affine::scale(0.0, 1.0).inverse().is_none());
+        assert_eq!(matrix.get_axis_scale(), None);
+        assert_eq!((Affine::translation(1.0, 1.0) * Affine::scale(2.0, 3.0)).get_axis_scale(), Some(Vec2::new(2.0, 3.0)));
+    }
+}
@@ -89,7 +89,56 @@ impl<T: SubAssign> SubAssign for Vec2<T> {
     }
 }
 
-// Multiplication
+// Component-wise multiplication and division
+impl<T: Mul<Output = T>> Mul for Vec2<T> {
+    type Output = Vec2<T>;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        Vec2::<T> {
+            x: self.x * rhs.x,
+            y: self.y * rhs.y
+        }
+    }
+}
+
+impl<T: MulAssign> MulAssign for Vec2<T> {
+    fn mul_assign(&mut self, rhs: Self) {
+        self.x *= rhs.x;
+        self.y *= rhs.y
+    }
+}
+
+impl<T: Div<Output = T>> Div for Vec2<T> {
+    type Output = Vec2<T>;
+
+    fn div(self, rhs: Self) -> Self::Output {
+        Vec2::<T> {
+            x: self.x / rhs.x,
+            y: self.y / rhs.y
+        }
+    }
+}
+
+impl<T: DivAssign> DivAssign for Vec2<T> {
+    fn div_assign(&mut self, rhs: Self) {
+        self.x /= rhs.x;
+        self.y /= rhs.y
+    }
+}
+
+// Products
+impl<T: Copy + Mul<Output = T> + Add<Output = T> + Sub<Output = T>> Vec2<T> {
+    pub fn dot(self, other: Self) -> T {
+        self.x * other.x + self.y * other.y
+    }
+
+    /// Z component of the 3D cross product, positive when other lies clockwise on screen
+    pub fn cross(self, other: Self) -> T {
+        self.x * other.y - self.y * other.x
+    }
+}
+
+// Multiplication, integer vectors are truncated towards zero
 impl Mul<f32> for Vec2 {
     type Output = Vec2;
 
@@ -108,21 +157,185 @@ impl MulAssign<f32> for Vec2 {
     }
 }
 
-// Multiplication
+// Division
 impl Div<f32> for Vec2 {
     type Output = Vec2;
 
     fn div(self, rhs: f32) -> Self::Output {
         Self {
-            x: (self.x as f32 * rhs) as i32,
-            y: (self.y as f32 * rhs) as i32,
+            x: (self.x as f32 / rhs) as i32,
+            y: (self.y as f32 / rhs) as i32,
         }
     }
 }
 
 impl DivAssign<f32> for Vec2 {
     fn div_assign(&mut self, rhs: f32) {
-        self.x = (self.x as f32 * rhs) as i32; 
-        self.y = (self.y as f32 * rhs) as i32; 
+        self.x = (self.x as f32 / rhs) as i32; 
+        self.y = (self.y as f32 / rhs) as i32; 
+    }
+}
+
+// Conversions
+impl From<Vec2> for Vec2<f32> {
+    fn from(value: Vec2) -> Self {
+        Self::new(value.x as f32, value.y as f32)
+    }
+}
+
+impl From<Vec2> for Vec2<f64> {
+    fn from(value: Vec2) -> Self {
+        Self::new(value.x as f64, value.y as f64)
+    }
+}
+
+impl From<Vec2<f32>> for Vec2<f64> {
+    fn from(value: Vec2<f32>) -> Self {
+        Self::new(value.x as f64, value.y as f64)
+    }
+}
+
+macro_rules! float_impl {
+    ($t:ty) => {
+        impl Vec2<$t> {
+            pub fn length(self) -> $t {
+                self.x.hypot(self.y)
+            }
+
+            pub fn length_squared(self) -> $t {
+                self.dot(self)
+            }
+
+            pub fn distance(self, other: Self) -> $t {
+                (other - self).length()
+            }
+
+            /// Same direction with length 1, the zero vector stays zero
+            pub fn normalize(self) -> Self {
+                let length = self.length();
+
+                if length == 0.0 {
+                    self
+                } else {
+                    self / length
+                }
+            }
+
+            /// Self at t = 0, other at t = 1
+            pub fn lerp(self, other: Self, t: $t) -> Self {
+                self + (other - self) * t
+            }
+
+            /// Clockwise on screen, angle in radians
+            pub fn rotate(self, angle: $t) -> Self {
+                let (sin, cos) = angle.sin_cos();
+                Self::new(self.x * cos - self.y * sin, self.x * sin + self.y * cos)
+            }
+
+            /// Angle from the positive x axis, clockwise on screen, in radians
+            pub fn angle(self) -> $t {
+                self.y.atan2(self.x)
+            }
+
+            pub fn abs(self) -> Self {
+                Self::new(self.x.abs(), self.y.abs())
+            }
+
+            pub fn min(self, other: Self) -> Self {
+                Self::new(self.x.min(other.x), self.y.min(other.y))
+            }
+
+            pub fn max(self, other: Self) -> Self {
+                Self::new(self.x.max(other.x), self.y.max(other.y))
+            }
+
+            /// To the nearest whole pixel
+            pub fn round(self) -> Vec2 {
+                Vec2::new(self.x.round() as i32, self.y.round() as i32)
+            }
+
+            pub fn floor(self) -> Vec2 {
+                Vec2::new(self.x.floor() as i32, self.y.floor() as i32)
+            }
+
+            pub fn ceil(self) -> Vec2 {
+                Vec2::new(self.x.ceil() as i32, self.y.ceil() as i32)
+            }
+        }
+
+        impl Mul<$t> for Vec2<$t> {
+            type Output = Self;
+
+            fn mul(self, rhs: $t) -> Self::Output {
+                Self::new(self.x * rhs, self.y * rhs)
+            }
+        }
+
+        impl MulAssign<$t> for Vec2<$t> {
+            fn mul_assign(&mut self, rhs: $t) {
+                self.x *= rhs;
+                self.y *= rhs;
+            }
+        }
+
+        impl Div<$t> for Vec2<$t> {
+            type Output = Self;
+
+            fn div(self, rhs: $t) -> Self::Output {
+                Self::new(self.x / rhs, self.y / rhs)
+            }
+        }
+
+        impl DivAssign<$t> for Vec2<$t> {
+            fn div_assign(&mut self, rhs: $t) {
+                self.x /= rhs;
+                self.y /= rhs;
+            }
+        }
+    };
+}
+
+float_impl!(f32);
+float_impl!(f64);
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::FRAC_PI_2;
+
+    use super::Vec2;
+
+    #[test]
+    fn divide_and_multiply() {
+        assert_eq!(Vec2::new(10, -7) / 2.0, Vec2::new(5, -3));
+        assert_eq!(Vec2::new(10, -7) * 0.5, Vec2::new(5, -3));
+
+        let mut vec = Vec2::new(9, 3);
+        vec /= 3.0;
+        assert_eq!(vec, Vec2::new(3, 1));
+
+        assert_eq!(Vec2::new(6, 8) / Vec2::new(2, 4), Vec2::new(3, 2));
+        assert_eq!(Vec2::new(1.5f32, 2.0) / 2.0, Vec2::new(0.75, 1.0));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn float_vectors() {
+        let vec = Vec2::new(3.0f32, 4.0);
+
+        assert_eq!(vec.length(), 5.0);
+        assert_eq!(vec.dot(Vec2::new(1.0, 1.0)), 7.0);
+        assert_eq!(Vec2::new(1, 0).cross(Vec2::new(0, 1)), 1);
+        assert_eq!(vec.normalize(), Vec2::new(0.6, 0.8));
+        assert_eq!(Vec2::<f32>::new(0.0, 0.0).normalize(), Vec2::new(0.0, 0.0));
+        assert_eq!(vec.lerp(Vec2::new(5.0, 8.0), 0.5), Vec2::new(4.0, 6.0));
+
+        // Clockwise on screen, x turns into y
+        let rotated = Vec2::new(1.0f32, 0.0).rotate(FRAC_PI_2);
+        assert!(rotated.x.abs() < 1e-6 && (rotated.y - 1.0).abs() < 1e-6);
+        assert_eq!(Vec2::new(0.0f32, 2.0).angle(), FRAC_PI_2);
+
+        assert_eq!(Vec2::new(1.5f32, -1.5).round(), Vec2::new(2, -2));
+        assert_eq!(Vec2::new(1.5f32, -1.5).floor(), Vec2::new(1, -2));
+        assert_eq!(Vec2::<f32>::from(Vec2::new(2, -3)), Vec2::new(2.0, -3.0));
+        assert_eq!(Vec2::<f64>::from(Vec2::new(0.5f32, 1.0)), Vec2::new(0.5, 1.0));
+    }
+}
@@ -3,8 +3,8 @@ mod sdl;
 #[cfg(all(feature = "native", not(target_family = "wasm")))]
 pub use sdl::SDLPlatform as Platform;
 
-#[cfg(all(not(feature = "native"), not(target_family = "wasm")))]
-mod mock;
+#[cfg(not(target_family = "wasm"))]
+pub mod mock;
 #[cfg(all(not(feature = "native"), not(target_family = "wasm")))]
 pub use mock::MockPlatform as Platform;
 
@@ -335,7 +335,10 @@ impl Event {
 }
 
 impl Transform for Event {
-    fn translate(&mut self, offset: Vec2) {
+    // Mouse positions are whole pixels
+    fn translate(&mut self, offset: Vec2<f32>) {
+        let offset = offset.round();
+
         match self {
             Event::MouseDown { x, y, .. } => {
                 *x += offset.x;
@@ -383,15 +386,37 @@ pub enum IntervalDecision {
 mod time_manager;
 
 #[derive(Debug)]
-#[cfg(feature = "image")]
 pub enum ImageLoadingError {
     IOError(std::io::Error),
+    #[cfg(feature = "image")]
     ImageError(image::ImageError),
-    MissingAssetError
+    MissingAssetError,
+    InvalidAtlas,
+
+    /// The data does not start with the FPIA magic
+    NotFpia,
+
+    /// The data is not in any format that can be read
+    UnknownFormat,
+
+    /// The data ends before the header or the pixels are complete
+    Truncated,
+
+    UnknownVersion(u8),
+    UnknownPixelFormat(u8),
+    UnknownCompression(u8),
+
+    /// Deflate compressed, but the deflate feature is disabled
+    UnsupportedCompression,
+
+    /// The stored checksum does not match the data, it got corrupted
+    ChecksumMismatch { expected: u32, actual: u32 },
+
+    /// The header is valid, but the pixels do not match it
+    InvalidData,
 }
 
-// Fritz Preloaded Image Asset
-const FPIA_MAGIC: [u8; 4] = [b'F', b'P', b'I', b'A'];
+pub mod fpia;
 
 pub trait PlatformTrait {
     fn new(width: u32, height: u32) -> Result<Self, PlatformError> where Self: Sized;
@@ -424,31 +449,13 @@ pub trait PlatformTrait {
             Surface::from_pixels(width, height, pixels)
         }
 
-        if data[0..4] == FPIA_MAGIC {
-            let (_magic, data) = data.split_at(4);
-
-            assert!(data.len() >= 8);
-
-            let (width, data) = data.split_at(4);
-            let (height, data) = data.split_at(4);
-
-            let width = u32::from_be_bytes(width.to_owned().try_into().unwrap()) as usize;
-            let height = u32::from_be_bytes(height.to_owned().try_into().unwrap()) as usize;
-
-            unsafe {
-                assert_eq!(data.len(), width * height * 4);
-
-                let data: &[[u8; 4]] = std::slice::from_raw_parts(data.as_ptr() as *const [u8; 4], data.len() / 4);
-                Ok(Surface::from_pixels(width, height, data.to_owned()))
-            }
+        if data.starts_with(&fpia::FPIA_MAGIC) {
+            fpia::decode_fpia(data)
         } else {
             let img = image::load_from_memory(data).map_err(ImageLoadingError::ImageError)?;
             Ok(image_to_tekenen(img))
         }
     }
-
-    // #[cfg(feature = "image")]
-    // fn save_image(path: &str, image: &Tekenen) -> Result<(), image::ImageError>;
 }
 
 use std::{cell::Ref, error::Error, fmt, time::Duration};
@@ -0,0 +1,424 @@
+//! Fritz Preloaded Image Asset, a simple image format that is fast to decode and needs no image crate.
+//!
+//! All numbers are big endian.
+//!
+//! | Offset | Size | Content |
+//! |--------|------|---------|
+//! | 0      | 4    | Magic `FPIA` |
+//! | 4      | 1    | Version, currently 2 |
+//! | 5      | 1    | Pixel format: 0 RGBA8, 1 RGB8, 2 grayscale, 3 indexed |
+//! | 6      | 1    | Compression: 0 none, 1 RLE, 2 deflate |
+//! | 7      | 1    | Number of palette colors minus one, zero if not indexed |
+//! | 8      | 4    | Width |
+//! | 12     | 4    | Height |
+//! | 16     | 4 per color | Palette as RGBA8, only for indexed images |
+//! |        | 4    | Payload length in bytes |
+//! |        |      | Payload, the compressed rows from top to bottom |
+//! |        | 4    | CRC-32 of every byte before it |
+//!
+//! Uncompressed, every pixel takes 4 (RGBA8), 3 (RGB8) or 1 byte (grayscale and palette index).
+//! RLE groups pixels: a control byte below 128 is followed by that many plus one literal pixels,
+//! from 128 upward it is followed by a single pixel repeated control - 126 times.
+//! Deflate is a raw deflate stream and needs the deflate feature.
+//!
+//! Version 1 files have no version field, the width follows the magic directly and the raw RGBA8 pixels the height.
+//! No image is 16 million pixels wide, so their version byte is always zero.
+
+use crate::{tekenen::Surface, ImageSavingError, Pixel};
+
+use super::ImageLoadingError;
+
+pub(crate) const FPIA_MAGIC: [u8; 4] = *b"FPIA";
+
+const VERSION: u8 = 2;
+
+/// How the pixels are stored
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PixelFormat {
+    Rgba8,
+
+    /// Alpha is dropped, every pixel is decoded as opaque
+    Rgb8,
+
+    /// Average of the color channels, decoded as opaque
+    Gray8,
+
+    /// Index into a palette of at most 256 RGBA8 colors
+    Indexed,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Compression {
+    None,
+
+    /// Run length encoding, fast and good for flat colors
+    Rle,
+
+    /// Smaller, but needs the deflate feature
+    Deflate,
+}
+
+impl PixelFormat {
+    fn from_byte(byte: u8) -> Result<Self, ImageLoadingError> {
+        match byte {
+            0 => Ok(Self::Rgba8),
+            1 => Ok(Self::Rgb8),
+            2 => Ok(Self::Gray8),
+            3 => Ok(Self::Indexed),
+            _ => Err(ImageLoadingError::UnknownPixelFormat(byte)),
+        }
+    }
+
+    fn bytes_per_pixel(&self) -> usize {
+        match self {
+            Self::Rgba8 => 4,
+            Self::Rgb8 => 3,
+            Self::Gray8 | Self::Indexed => 1,
+        }
+    }
+
+    /// Smallest format that stores the surface without loss
+    pub fn smallest(surface: &Surface) -> Self {
+        let opaque = surface.as_slice().iter().all(|pixel| pixel[3] == 255);
+
+        if opaque && surface.as_slice().iter().all(|[r, g, b, _]| r == g && g == b) {
+            Self::Gray8
+        } else if palette(surface).is_some() {
+            Self::Indexed
+        } else if opaque {
+            Self::Rgb8
+        } else {
+            Self::Rgba8
+        }
+    }
+}
+
+impl Compression {
+    fn from_byte(byte: u8) -> Result<Self, ImageLoadingError> {
+        match byte {
+            0 => Ok(Self::None),
+            1 => Ok(Self::Rle),
+            2 => Ok(Self::Deflate),
+            _ => Err(ImageLoadingError::UnknownCompression(byte)),
+        }
+    }
+}
+
+/// CRC-32 as used by zip and png
+fn crc32(data: &[u8]) -> u32 {
+    const TABLE: [u32; 256] = {
+        let mut table = [0; 256];
+        let mut i = 0;
+
+        while i < 256 {
+            let mut crc = i as u32;
+            let mut bit = 0;
+
+            while bit < 8 {
+                crc = if crc & 1 == 1 { 0xedb88320 ^ (crc >> 1) } else { crc >> 1 };
+                bit += 1;
+            }
+
+            table[i] = crc;
+            i += 1;
+        }
+
+        table
+    };
+
+    !data.iter().fold(!0, |crc, byte| TABLE[((crc ^ *byte as u32) & 0xff) as usize] ^ (crc >> 8))
+}
+
+/// Every distinct color, None if there are more than 256
+fn palette(surface: &Surface) -> Option<Vec<Pixel>> {
+    let mut colors = Vec::new();
+
+    for pixel in surface.as_slice() {
+        if !colors.contains(pixel) {
+            if colors.len() == 256 {
+                return None
+            }
+
+            colors.push(*pixel);
+        }
+    }
+
+    Some(colors)
+}
+
+fn rle_encode(data: &[u8], size: usize) -> Vec<u8> {
+    let pixels: Vec<&[u8]> = data.chunks(size).collect();
+    let mut encoded = Vec::new();
+    let mut i = 0;
+
+    while i < pixels.len() {
+        let run = pixels[i..].iter().take(129).take_while(|pixel| **pixel == pixels[i]).count();
+
+        if run >= 2 {
+            encoded.push((run + 126) as u8);
+            encoded.extend_from_slice(pixels[i]);
+            i += run;
+            continue
+        }
+
+        // Literals until the next run starts
+        let start = i;
+        while i < pixels.len() && i - start < 128 && !(i + 1 < pixels.len() && pixels[i] == pixels[i + 1]) {
+            i += 1;
+        }
+
+        encoded.push((i - start - 1) as u8);
+        pixels[start..i].iter().for_each(|pixel| encoded.extend_from_slice(pixel));
+    }
+
+    encoded
+}
+
+fn rle_decode(mut data: &[u8], size: usize, length: usize) -> Result<Vec<u8>, ImageLoadingError> {
+    // The length comes from the header, a run of 2 bytes expands to at most 129 bytes
+    let mut decoded = Vec::with_capacity(length.min(data.len() * 129));
+
+    while let Some((&control, rest)) = data.split_first() {
+        let (count, literal) = if control < 128 { (control as usize + 1, true) } else { (control as usize - 126, false) };
+        let bytes = if literal { count * size } else { size };
+
+        let (pixels, rest) = rest.split_at_checked(bytes).ok_or(ImageLoadingError::Truncated)?;
+
+        if literal {
+            decoded.extend_from_slice(pixels)
+        } else {
+            (0..count).for_each(|_| decoded.extend_from_slice(pixels))
+        }
+
+        if decoded.len() > length {
+            return Err(ImageLoadingError::InvalidData)
+        }
+
+        data = rest;
+    }
+
+    Ok(decoded)
+}
+
+impl Surface {
+    /// Encode as FPIA in the given pixel format, colors that do not fit the format are converted.
+    /// Fails if an indexed image has more than 256 colors or deflate is disabled
+    pub fn encode_fpia(&self, pixel_format: PixelFormat, compression: Compression) -> Result<Vec<u8>, ImageSavingError> {
+        let mut palette = match pixel_format {
+            PixelFormat::Indexed => palette(self).ok_or(ImageSavingError::TooManyColors)?,
+            _ => Vec::new(),
+        };
+
+        // The header always counts at least one color
+        if pixel_format == PixelFormat::Indexed && palette.is_empty() {
+            palette.push([0, 0, 0, 0])
+        }
+
+        let mut raw = Vec::with_capacity(self.pixels.len() * pixel_format.bytes_per_pixel());
+
+        for pixel in self.as_slice() {
+            match pixel_format {
+                PixelFormat::Rgba8 => raw.extend_from_slice(pixel),
+                PixelFormat::Rgb8 => raw.extend_from_slice(&pixel[..3]),
+                PixelFormat::Gray8 => raw.push(((pixel[0] as u32 + pixel[1] as u32 + pixel[2] as u32 + 1) / 3) as u8),
+                PixelFormat::Indexed => raw.push(palette.iter().position(|color| color == pixel).unwrap() as u8),
+            }
+        }
+
+        let payload = match compression {
+            Compression::None => raw,
+            Compression::Rle => rle_encode(&raw, pixel_format.bytes_per_pixel()),
+            #[cfg(feature = "deflate")]
+            Compression::Deflate => miniz_oxide::deflate::compress_to_vec(&raw, 6),
+            #[cfg(not(feature = "deflate"))]
+            Compression::Deflate => return Err(ImageSavingError::UnsupportedCompression),
+        };
+
+        let mut data = Vec::with_capacity(24 + palette.len() * 4 + payload.len());
+        data.extend_from_slice(&FPIA_MAGIC);
+        data.extend_from_slice(&[VERSION, pixel_format as u8, compression as u8, palette.len().saturating_sub(1) as u8]);
+        data.extend_from_slice(&(self.width() as u32).to_be_bytes());
+        data.extend_from_slice(&(self.height() as u32).to_be_bytes());
+        data.extend_from_slice(palette.as_flattened());
+        data.extend_from_slice(&(payload.len() as u32).to_be_bytes());
+        data.extend_from_slice(&payload);
+        data.extend_from_slice(&crc32(&data).to_be_bytes());
+
+        Ok(data)
+    }
+}
+
+/// Lossless FPIA in the smallest pixel format, compressed with deflate when enabled
+pub(crate) fn encode_fpia(surface: &Surface) -> Vec<u8> {
+    let compression = if cfg!(feature = "deflate") { Compression::Deflate } else { Compression::Rle };
+
+    surface.encode_fpia(PixelFormat::smallest(surface), compression)
+        .expect("The smallest format never has too many colors")
+}
+
+/// Split off length bytes from the front of data
+pub(crate) fn take<'a>(data: &mut &'a [u8], length: usize) -> Result<&'a [u8], ImageLoadingError> {
+    let (taken, rest) = data.split_at_checked(length).ok_or(ImageLoadingError::Truncated)?;
+    *data = rest;
+    Ok(taken)
+}
+
+pub(crate) fn take_u32(data: &mut &[u8]) -> Result<u32, ImageLoadingError> {
+    Ok(u32::from_be_bytes(take(data, 4)?.try_into().unwrap()))
+}
+
+/// Decode any version of FPIA
+pub fn decode_fpia(data: &[u8]) -> Result<Surface, ImageLoadingError> {
+    let all = data;
+    let mut data = data;
+
+    if take(&mut data, 4)? != FPIA_MAGIC {
+        return Err(ImageLoadingError::NotFpia)
+    }
+
+    // Version 1, the width directly follows the magic
+    if data.first() == Some(&0) {
+        let width = take_u32(&mut data)? as usize;
+        let height = take_u32(&mut data)? as usize;
+        let length = width.checked_mul(height).and_then(|length| length.checked_mul(4)).ok_or(ImageLoadingError::InvalidData)?;
+
+        if data.len() < length {
+            return Err(ImageLoadingError::Truncated)
+        } else if data.len() > length {
+            return Err(ImageLoadingError::InvalidData)
+        }
+
+        let pixels = data.chunks(4).map(|pixel| pixel.try_into().unwrap()).collect();
+        return Ok(Surface::from_pixels(width, height, pixels))
+    }
+
+    let [version, format, compression, palette_size]: [u8; 4] = take(&mut data, 4)?.try_into().unwrap();
+    let width = take_u32(&mut data)? as usize;
+    let height = take_u32(&mut data)? as usize;
+    let length = width.checked_mul(height).ok_or(ImageLoadingError::InvalidData)?;
+
+    if version != VERSION {
+        return Err(ImageLoadingError::UnknownVersion(version))
+    }
+
+    let format = PixelFormat::from_byte(format)?;
+    let compression = Compression::from_byte(compression)?;
+
+    let palette: Vec<Pixel> = match format {
+        PixelFormat::Indexed => take(&mut data, (palette_size as usize + 1) * 4)?.chunks(4).map(|color| color.try_into().unwrap()).collect(),
+        _ => Vec::new(),
+    };
+
+    let payload_length = take_u32(&mut data)? as usize;
+    let payload = take(&mut data, payload_length)?;
+    let checksum = take_u32(&mut data)?;
+
+    let actual = crc32(&all[..all.len() - data.len() - 4]);
+    if checksum != actual {
+        return Err(ImageLoadingError::ChecksumMismatch { expected: checksum, actual })
+    }
+
+    let size = format.bytes_per_pixel();
+    let raw_length = length.checked_mul(size).ok_or(ImageLoadingError::InvalidData)?;
+
+    let raw = match compression {
+        Compression::None => payload.to_vec(),
+        Compression::Rle => rle_decode(payload, size, raw_length)?,
+        #[cfg(feature = "deflate")]
+        Compression::Deflate => miniz_oxide::inflate::decompress_to_vec_with_limit(payload, raw_length).map_err(|_| ImageLoadingError::InvalidData)?,
+        #[cfg(not(feature = "deflate"))]
+        Compression::Deflate => return Err(ImageLoadingError::UnsupportedCompression),
+    };
+
+    if raw.len() != raw_length {
+        return Err(ImageLoadingError::InvalidData)
+    }
+
+    let pixels = raw.chunks(size).map(|pixel| match format {
+        PixelFormat::Rgba8 => Ok([pixel[0], pixel[1], pixel[2], pixel[3]]),
+        PixelFormat::Rgb8 => Ok([pixel[0], pixel[1], pixel[2], 255]),
+        PixelFormat::Gray8 => Ok([pixel[0], pixel[0], pixel[0], 255]),
+        PixelFormat::Indexed => palette.get(pixel[0] as usize).copied().ok_or(ImageLoadingError::InvalidData),
+    }).collect::<Result<Vec<_>, _>>()?;
+
+    Ok(Surface::from_pixels(width, height, pixels))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{platform::ImageLoadingError, Surface};
+
+    use super::{crc32, decode_fpia, encode_fpia, Compression, PixelFormat};
+
+    fn image() -> Surface {
+        let mut surface = Surface::new(20, 3);
+
+        for x in 0..20 {
+            surface.set_pixel(x, 0, [x as u8 * 10, 5, 5, 255]);
+            surface.set_pixel(x, 1, [30, 30, 30, 255]);
+            surface.set_pixel(x, 2, [x as u8 % 2 * 200, 0, 100, 128]);
+        }
+
+        surface
+    }
+
+    #[test]
+    fn round_trip() {
+        let surface = image();
+        let compressions = [Compression::None, Compression::Rle, #[cfg(feature = "deflate")] Compression::Deflate];
+
+        for compression in compressions {
+            for format in [PixelFormat::Rgba8, PixelFormat::Indexed] {
+                let decoded = decode_fpia(&surface.encode_fpia(format, compression).unwrap()).unwrap();
+                assert_eq!(decoded.as_slice(), surface.as_slice(), "{format:?} {compression:?}");
+            }
+
+            // Lossy formats keep what they can
+            let decoded = decode_fpia(&surface.encode_fpia(PixelFormat::Rgb8, compression).unwrap()).unwrap();
+            assert_eq!(decoded.as_slice()[40], [0, 0, 100, 255]);
+
+            let decoded = decode_fpia(&surface.encode_fpia(PixelFormat::Gray8, compression).unwrap()).unwrap();
+            assert_eq!(decoded.as_slice()[20], [30, 30, 30, 255]);
+        }
+
+        assert_eq!(PixelFormat::smallest(&surface), PixelFormat::Indexed);
+        assert_eq!(decode_fpia(&encode_fpia(&surface)).unwrap().as_slice(), surface.as_slice());
+    }
+
+    #[test]
+    fn version_1() {
+        let mut data = b"FPIA".to_vec();
+        data.extend_from_slice(&[0, 0, 0, 1, 0, 0, 0, 2, 1, 2, 3, 4, 5, 6, 7, 8]);
+
+        assert_eq!(decode_fpia(&data).unwrap().as_slice(), [[1, 2, 3, 4], [5, 6, 7, 8]]);
+        assert!(matches!(decode_fpia(&data[..data.len() - 1]), Err(ImageLoadingError::Truncated)));
+    }
+
+    #[test]
+    fn errors() {
+        let data = image().encode_fpia(PixelFormat::Rgba8, Compression::Rle).unwrap();
+
+        assert!(matches!(decode_fpia(b"FP"), Err(ImageLoadingError::Truncated)));
+        assert!(matches!(decode_fpia(b"PNG\0"), Err(ImageLoadingError::NotFpia)));
+
+        for length in 0..data.len() {
+            assert!(decode_fpia(&data[..length]).is_err());
+        }
+
+        let mut corrupt = data.clone();
+        corrupt[30] ^= 1;
+        assert!(matches!(decode_fpia(&corrupt), Err(ImageLoadingError::ChecksumMismatch { .. })));
+
+        let mut future = data.clone();
+        future[4] = 3;
+        assert!(matches!(decode_fpia(&future), Err(ImageLoadingError::UnknownVersion(3))));
+
+        // A huge size in the header with a tiny payload must not allocate the whole image
+        let mut huge = data.clone();
+        huge[8..16].copy_from_slice(&[0, 0, 0xff, 0xff, 0, 0, 0xff, 0xff]);
+        let end = huge.len() - 4;
+        let checksum = crc32(&huge[..end]).to_be_bytes();
+        huge[end..].copy_from_slice(&checksum);
+        assert!(matches!(decode_fpia(&huge), Err(ImageLoadingError::InvalidData)));
+    }
+}
@@ -1,29 +1,149 @@
-use super::PlatformTrait;
+use std::{cell::{Cell, Ref, RefCell}, collections::VecDeque, time::Duration};
 
-pub struct MockPlatform {}
+use crate::Surface;
+
+use super::{Event, IntervalDecision, PlatformError, PlatformTrait};
+
+/// Headless platform, everything happens in memory.
+/// - Displayed surfaces are kept as the last frame
+/// - Events are read from a scripted queue
+/// - Intervals run for a fixed number of ticks on a virtual clock
+pub struct MockPlatform {
+    width: u32,
+    height: u32,
+}
+
+thread_local! {
+    static LAST_FRAME: RefCell<Option<Surface>> = const { RefCell::new(None) };
+    static EVENT_QUEUE: RefCell<VecDeque<Event>> = const { RefCell::new(VecDeque::new()) };
+    static LOG: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+    static TICKS: Cell<u32> = const { Cell::new(1) };
+    static ELAPSED: Cell<Duration> = const { Cell::new(Duration::ZERO) };
+    static INTERVAL: Cell<Duration> = const { Cell::new(Duration::ZERO) };
+}
+
+impl MockPlatform {
+    /// Add an event at the end of the queue, it will be returned by read_events
+    pub fn push_event(event: Event) {
+        EVENT_QUEUE.with(|queue| queue.borrow_mut().push_back(event))
+    }
+
+    /// Add multiple events at the end of the queue
+    pub fn script_events(events: impl IntoIterator<Item = Event>) {
+        EVENT_QUEUE.with(|queue| queue.borrow_mut().extend(events))
+    }
+
+    /// Number of times set_interval calls the callback before returning
+    pub fn set_ticks(ticks: u32) {
+        TICKS.with(|t| t.set(ticks))
+    }
+
+    /// Time on the virtual clock, advanced by one interval for every tick
+    pub fn elapsed() -> Duration {
+        ELAPSED.with(|elapsed| elapsed.get())
+    }
+
+    /// Take the last surface passed to display_surface
+    pub fn take_last_frame() -> Option<Surface> {
+        LAST_FRAME.with(|frame| frame.borrow_mut().take())
+    }
+
+    /// Take all the messages passed to log
+    pub fn take_log() -> Vec<String> {
+        LOG.with(|log| std::mem::take(&mut *log.borrow_mut()))
+    }
+
+    /// Clear all the thread local state, useful between tests
+    pub fn reset() {
+        LAST_FRAME.with(|frame| *frame.borrow_mut() = None);
+        EVENT_QUEUE.with(|queue| queue.borrow_mut().clear());
+        LOG.with(|log| log.borrow_mut().clear());
+        TICKS.with(|t| t.set(1));
+        ELAPSED.with(|elapsed| elapsed.set(Duration::ZERO));
+        INTERVAL.with(|interval| interval.set(Duration::ZERO));
+    }
+}
 
 impl PlatformTrait for MockPlatform {
-    fn new(width: u32, height: u32) -> Result<Self, super::PlatformError> where Self: Sized {
-        unreachable!()
+    fn new(width: u32, height: u32) -> Result<Self, PlatformError> where Self: Sized {
+        Ok(Self {
+            width,
+            height
+        })
     }
 
-    fn display_surface(&mut self, pixels: std::cell::Ref<crate::tekenen::Surface>) {
-        unreachable!()
+    fn display_surface(&mut self, surface: Ref<Surface>) {
+        assert!(
+            self.width == surface.width() as u32 && self.height == surface.height() as u32,
+            "Cannot render pixels!, Expected: {}x{}, found: {}x{}",
+            self.width, self.height,
+            surface.width(), surface.height()
+        );
+
+        LAST_FRAME.with(|frame| *frame.borrow_mut() = Some(surface.clone()))
     }
 
-    fn get_remaining_time() -> std::time::Duration {
-        unreachable!()
+    fn get_remaining_time() -> Duration {
+        // Callbacks run instantly at the start of their slot on the virtual clock
+        INTERVAL.with(|interval| interval.get())
     }
 
     fn log(value: String) {
-        unreachable!()
+        LOG.with(|log| log.borrow_mut().push(value))
+    }
+
+    fn read_events(&mut self) -> Option<Event> {
+        EVENT_QUEUE.with(|queue| queue.borrow_mut().pop_front())
     }
 
-    fn read_events(&mut self) -> Option<super::Event> {
-        unreachable!()
+    fn set_interval(mut callback: impl FnMut() -> IntervalDecision + 'static, fps: u32) {
+        let interval = Duration::from_micros(1_000_000 / fps.max(1) as u64);
+        INTERVAL.with(|i| i.set(interval));
+
+        let ticks = TICKS.with(|t| t.get());
+
+        for _ in 0..ticks {
+            ELAPSED.with(|elapsed| elapsed.set(elapsed.get() + interval));
+
+            if let IntervalDecision::Stop = callback() {
+                break
+            }
+        }
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{colors, platform::{Event, IntervalDecision, PlatformTrait}, DrawableSurface, Surface, SurfaceView};
+
+    use super::MockPlatform;
+
+    #[test]
+    fn runs_fixed_ticks_and_keeps_last_frame() {
+        MockPlatform::reset();
+        MockPlatform::set_ticks(3);
+        MockPlatform::script_events([Event::Quit]);
+
+        let mut window = MockPlatform::new(4, 4).unwrap();
+        let view = SurfaceView::new(4, 4, Surface::new(4, 4).into());
+        let mut tick = 0;
+
+        MockPlatform::set_interval(move || {
+            while let Some(event) = window.read_events() {
+                MockPlatform::log(format!("{:?}", event));
+            }
+
+            view.background(if tick == 2 { colors::RED } else { colors::BLUE });
+            window.display_surface(view.get_surface());
+            tick += 1;
+
+            IntervalDecision::Repeat
+        }, 50);
+
+        assert_eq!(MockPlatform::elapsed().as_millis(), 60);
+        assert_eq!(MockPlatform::take_log(), vec!["Quit".to_owned()]);
 
-    fn set_interval(callback: impl FnMut() -> super::IntervalDecision + 'static, fps: u32) {
-        unreachable!()
+        let frame = MockPlatform::take_last_frame().unwrap();
+        assert!(frame.as_slice().iter().all(|pixel| *pixel == colors::RED));
     }
-}
\ No newline at end of file
+}
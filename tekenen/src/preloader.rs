@@ -1,29 +1,52 @@
 use image::{io::Reader as ImageReader, GenericImageView};
 
-// Fritz Preloaded Image Asset
-const FPIA_MAGIC: [u8; 4] = [b'F', b'P', b'I', b'A'];
+use crate::{animation::AnimatedImage, atlas::Atlas, platform::fpia::{encode_fpia, Compression, PixelFormat}, Font, Surface};
 
-pub fn parse_image(path: &str) -> Vec<u8> {
+fn load_surface(path: &str) -> Surface {
     let img = ImageReader::open(path).unwrap().decode().unwrap();
-
-    let mut vec = vec![];
-    vec.extend_from_slice(&FPIA_MAGIC);
-
-    let width = img.width().to_be_bytes();
-    let height = img.height().to_be_bytes();
-
-    vec.extend_from_slice(&width);
-    vec.extend_from_slice(&height);
+    let mut pixels = vec![];
 
     for y in 0..img.height() {
         for x in 0..img.width() {
             let color = img.get_pixel(x, y);
-            vec.push(color[0]);
-            vec.push(color[1]);
-            vec.push(color[2]);
-            vec.push(color[3]);
+            pixels.push([color[0], color[1], color[2], color[3]]);
         }
     };
 
-    vec
+    Surface::from_pixels(img.width() as usize, img.height() as usize, pixels)
+}
+
+/// Convert an image to FPIA, in the smallest lossless pixel format and deflate compressed
+pub fn parse_image(path: &str) -> Vec<u8> {
+    encode_fpia(&load_surface(path))
+}
+
+/// Convert an image to FPIA with a chosen pixel format and compression, see `Surface::encode_fpia`
+pub fn parse_image_as(path: &str, pixel_format: PixelFormat, compression: Compression) -> Vec<u8> {
+    load_surface(path).encode_fpia(pixel_format, compression).unwrap()
+}
+
+/// Pack all images into one atlas, every frame is named after its file without the extension.
+/// Load the result with Atlas::decode
+pub fn build_atlas(paths: &[&str]) -> Vec<u8> {
+    let images: Vec<_> = paths.iter().map(|path| load_surface(path)).collect();
+
+    let names: Vec<_> = paths.iter().map(|path| {
+        std::path::Path::new(path).file_stem().and_then(|stem| stem.to_str()).unwrap_or(path)
+    }).collect();
+
+    let named: Vec<_> = names.into_iter().zip(&images).collect();
+    Atlas::pack_named(&named).encode()
+}
+
+/// Convert an animated GIF or APNG to FPAN, load the result with AnimatedImage::decode
+pub fn parse_animation(path: &str) -> Vec<u8> {
+    let data = std::fs::read(path).unwrap();
+    AnimatedImage::import(&data).unwrap().encode()
+}
+
+/// Bake a BDF or PSF font into FPFN, load the result with Font::from_bytes
+pub fn build_font(path: &str) -> Vec<u8> {
+    let data = std::fs::read(path).unwrap();
+    Font::bake(&data).unwrap()
 }
@@ -4,6 +4,7 @@ pub mod point;
 use point::Point;
 
 pub mod line;
+use line::Line;
 
 pub mod rect;
 use rect::Rect;
@@ -14,14 +15,29 @@ use circle::Circle;
 pub mod triangle;
 use triangle::Triangle;
 
-// mod composed_shape;
-// pub use self::composed_shape::ComposedShape;
+pub mod polygon;
+use polygon::Polygon;
 
-use crate::{math::{Transform, Vec2}, tekenen::SurfaceDrawer};
+pub mod polyline;
+use polyline::Polyline;
+
+pub mod path;
+use path::Path;
+
+mod geometry;
+
+pub mod composed_shape;
+use composed_shape::ComposedShape;
+
+use crate::{math::{Affine, Transform, Vec2}, tekenen::SurfaceDrawer};
 
 pub trait Shape: Intersect + Transform + std::fmt::Debug {
     fn draw_yourself(&self, target: &SurfaceDrawer);
 
+    /// Copy in screen space for a transformation Transform cannot express, like rotation.
+    /// Shapes that lose their form become a Path.
+    fn transformed(&self, matrix: &Affine) -> Box<dyn Shape>;
+
     fn get_bounding_box(&self) -> Rect;
     fn dyn_clone(&self) -> Box<dyn Shape>;
     fn iter(&self) -> Box<dyn Iterator<Item = Vec2>>;
@@ -32,41 +48,28 @@ pub trait Intersect {
 
     fn intersect(&self, other: &dyn Intersect) -> bool;
     fn intersect_point(&self, other: &Point) -> bool;
+    fn intersect_line(&self, other: &Line) -> bool;
     fn intersect_rect(&self, other: &Rect) -> bool;
     fn intersect_circle(&self, other: &Circle) -> bool;
     fn intersect_triangle(&self, other: &Triangle) -> bool;
+    fn intersect_polygon(&self, other: &Polygon) -> bool;
+    fn intersect_polyline(&self, other: &Polyline) -> bool;
+    fn intersect_path(&self, other: &Path) -> bool;
+    fn intersect_composed(&self, other: &ComposedShape) -> bool;
 
     fn encloses(&self, other: &dyn Intersect) -> bool;
     fn is_enclosed_by(&self, other: &dyn Intersect) -> bool;
     fn encloses_point(&self, other: &Point) -> bool;
+    fn encloses_line(&self, other: &Line) -> bool;
     fn encloses_rect(&self, other: &Rect) -> bool;
     fn encloses_circle(&self, other: &Circle) -> bool;
     fn encloses_triangle(&self, other: &Triangle) -> bool;
+    fn encloses_polygon(&self, other: &Polygon) -> bool;
+    fn encloses_polyline(&self, other: &Polyline) -> bool;
+    fn encloses_path(&self, other: &Path) -> bool;
+    fn encloses_composed(&self, other: &ComposedShape) -> bool;
 }
 
-// pub trait BitShaping {
-//     fn bit_dyn_clone(&self) -> Box<dyn Shape>;
-    
-//     fn join_and(&self, other: &dyn BitShaping) -> ComposedShape {
-//         ComposedShape::and(self.bit_dyn_clone(), other.bit_dyn_clone())
-//     }
-//     // fn join_point(&self, other: &Point) -> ComposedShape { todo!() }
-//     // fn join_rect(&self, other: &Rect) -> ComposedShape { todo!() }
-//     // fn join_circle(&self, other: &Circle) -> ComposedShape { todo!() }
-//     // fn join_triangle(&self, other: &Triangle) -> ComposedShape { todo!() }
-// }
-
-// can &, |, ^, !
-// can get bounding boxes and test each pixel
-
-// impl std::ops::BitAnd for &dyn BitShaping {
-//     type Output = ComposedShape;
-
-//     fn bitand(self, rhs: &dyn BitShaping) -> Self::Output {
-//         rhs.join_and(self)
-//     }
-// }
-
 /// A helper to store all the directions
 #[derive(Debug, Clone)]
 pub struct Sides<T = i32> {
@@ -119,4 +122,194 @@ impl<T: Add<Output = T>> Add for Sides<T> {
             left: self.left + rhs.left
         }
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use crate::math::{Transform, Vec2};
+
+    use super::{Circle, Intersect, Line, Point, Rect, Triangle};
+
+    fn line() -> Line {
+        Line::new(0.0, 0.0, 10.0, 10.0)
+    }
+
+    fn rect() -> Rect {
+        Rect::new(0.0, 0.0, 10.0, 10.0)
+    }
+
+    fn circle() -> Circle {
+        Circle::new(5.0, 5.0, 5.0)
+    }
+
+    fn triangle() -> Triangle {
+        Triangle::new(0.0, 0.0, 10.0, 0.0, 0.0, 10.0)
+    }
+
+    #[test]
+    fn point_point() {
+        assert!(Point::new(1.0, 1.0).intersect(&Point::new(1.0, 1.0)));
+        assert!(!Point::new(1.0, 1.0).intersect(&Point::new(1.0, 2.0)));
+        assert!(Point::new(1.0, 1.0).encloses(&Point::new(1.0, 1.0)));
+    }
+
+    #[test]
+    fn point_line() {
+        assert!(line().intersect(&Point::new(5.0, 5.0)));
+        assert!(Point::new(5.0, 5.0).intersect(&line()));
+        assert!(!line().intersect(&Point::new(5.0, 6.0)));
+        assert!(line().encloses(&Point::new(10.0, 10.0)));
+        assert!(!Point::new(5.0, 5.0).encloses(&line()));
+        assert!(Point::new(3.0, 3.0).encloses(&Line::new(3.0, 3.0, 3.0, 3.0)));
+    }
+
+    #[test]
+    fn point_rect() {
+        assert!(rect().intersect(&Point::new(10.0, 10.0)));
+        assert!(!Point::new(11.0, 5.0).intersect(&rect()));
+        assert!(rect().encloses(&Point::new(0.0, 5.0)));
+        assert!(Point::new(5.0, 5.0).encloses(&Rect::new(5.0, 5.0, 0.0, 0.0)));
+        assert!(!Point::new(5.0, 5.0).encloses(&rect()));
+    }
+
+    #[test]
+    fn point_circle() {
+        assert!(circle().intersect(&Point::new(10.0, 5.0)));
+        assert!(!Point::new(9.0, 9.0).intersect(&circle()));
+        assert!(circle().encloses(&Point::new(5.0, 0.0)));
+        assert!(Point::new(5.0, 5.0).encloses(&Circle::new(5.0, 5.0, 0.0)));
+        assert!(!Point::new(5.0, 5.0).encloses(&circle()));
+    }
+
+    #[test]
+    fn point_triangle() {
+        assert!(triangle().intersect(&Point::new(5.0, 5.0)));
+        assert!(!Point::new(6.0, 5.0).intersect(&triangle()));
+        assert!(triangle().encloses(&Point::new(1.0, 1.0)));
+        assert!(!Point::new(0.0, 0.0).encloses(&triangle()));
+    }
+
+    #[test]
+    fn line_line() {
+        assert!(line().intersect(&Line::new(0.0, 10.0, 10.0, 0.0)));
+        assert!(!line().intersect(&Line::new(1.0, 0.0, 11.0, 10.0)));
+        assert!(line().intersect(&Line::new(10.0, 10.0, 20.0, 0.0)));
+        assert!(line().intersect(&Line::new(5.0, 5.0, 20.0, 20.0)));
+        assert!(line().encloses(&Line::new(2.0, 2.0, 8.0, 8.0)));
+        assert!(!line().encloses(&Line::new(5.0, 5.0, 20.0, 20.0)));
+    }
+
+    #[test]
+    fn line_rect() {
+        assert!(rect().intersect(&Line::new(-5.0, 5.0, 15.0, 5.0)));
+        assert!(!Line::new(-5.0, -1.0, 15.0, -1.0).intersect(&rect()));
+        assert!(rect().intersect(&Line::new(2.0, 2.0, 3.0, 3.0)));
+        assert!(Line::new(-5.0, 5.0, 5.0, -5.0).intersect(&rect()));
+        assert!(rect().encloses(&line()));
+        assert!(!rect().encloses(&Line::new(0.0, 0.0, 11.0, 10.0)));
+        assert!(line().encloses(&Rect::new(3.0, 3.0, 0.0, 0.0)));
+        assert!(!line().encloses(&Rect::new(2.0, 2.0, 3.0, 3.0)));
+    }
+
+    #[test]
+    fn line_circle() {
+        assert!(circle().intersect(&Line::new(0.0, 0.0, 10.0, 0.0)));
+        assert!(!Line::new(0.0, -1.0, 10.0, -1.0).intersect(&circle()));
+        assert!(!circle().intersect(&Line::new(20.0, 5.0, 30.0, 5.0)));
+        assert!(circle().encloses(&Line::new(1.0, 5.0, 9.0, 5.0)));
+        assert!(!circle().encloses(&line()));
+        assert!(line().encloses(&Circle::new(5.0, 5.0, 0.0)));
+        assert!(!line().encloses(&circle()));
+    }
+
+    #[test]
+    fn line_triangle() {
+        assert!(triangle().intersect(&Line::new(5.0, 5.0, 10.0, 10.0)));
+        assert!(!Line::new(6.0, 6.0, 10.0, 10.0).intersect(&triangle()));
+        assert!(Line::new(-1.0, 1.0, 1.0, -1.0).intersect(&triangle()));
+        assert!(triangle().encloses(&Line::new(0.0, 0.0, 10.0, 0.0)));
+        assert!(!triangle().encloses(&Line::new(0.0, 0.0, 6.0, 6.0)));
+        assert!(line().encloses(&Triangle::new(0.0, 0.0, 5.0, 5.0, 10.0, 10.0)));
+        assert!(!line().encloses(&triangle()));
+    }
+
+    #[test]
+    fn rect_rect() {
+        assert!(rect().intersect(&Rect::new(10.0, 10.0, 5.0, 5.0)));
+        assert!(!rect().intersect(&Rect::new(11.0, 0.0, 5.0, 5.0)));
+        assert!(rect().encloses(&Rect::new(2.0, 2.0, 8.0, 8.0)));
+        assert!(!rect().encloses(&Rect::new(2.0, 2.0, 9.0, 8.0)));
+    }
+
+    #[test]
+    fn rect_circle() {
+        assert!(rect().intersect(&Circle::new(-3.0, 5.0, 3.0)));
+        assert!(!Circle::new(-4.0, 5.0, 3.0).intersect(&rect()));
+        assert!(Circle::new(13.0, 14.0, 5.0).intersect(&rect()));
+        assert!(!rect().intersect(&Circle::new(13.0, 14.0, 4.0)));
+        assert!(rect().encloses(&circle()));
+        assert!(!rect().encloses(&Circle::new(5.0, 5.0, 6.0)));
+        assert!(circle().encloses(&Rect::new(2.0, 2.0, 6.0, 6.0)));
+        assert!(!circle().encloses(&Rect::new(1.0, 1.0, 8.0, 8.0)));
+        assert!(circle().encloses(&Rect::new(5.0, 0.0, 0.0, 10.0)));
+    }
+
+    #[test]
+    fn circle_circle() {
+        // Apart vertically, only dy tells them apart
+        assert!(!Circle::new(0.0, 0.0, 1.0).intersect(&Circle::new(0.0, 10.0, 1.0)));
+        assert!(Circle::new(0.0, 0.0, 5.0).intersect(&Circle::new(0.0, 10.0, 5.0)));
+        assert!(circle().encloses(&circle()));
+        assert!(circle().encloses(&Circle::new(6.0, 5.0, 4.0)));
+        assert!(!circle().encloses(&Circle::new(7.0, 5.0, 4.0)));
+        assert!(!circle().encloses(&Circle::new(5.0, 5.0, 6.0)));
+    }
+
+    #[test]
+    fn circle_triangle() {
+        assert!(!triangle().intersect(&Circle::new(10.0, 10.0, 5.0)));
+        assert!(Circle::new(10.0, 10.0, 8.0).intersect(&triangle()));
+        assert!(triangle().intersect(&Circle::new(-3.0, -4.0, 5.0)));
+        assert!(triangle().encloses(&Circle::new(3.0, 3.0, 2.0)));
+        assert!(!triangle().encloses(&Circle::new(3.0, 3.0, 3.0)));
+        assert!(circle().encloses(&Triangle::new(5.0, 1.0, 9.0, 5.0, 1.0, 5.0)));
+        assert!(!circle().encloses(&triangle()));
+    }
+
+    #[test]
+    fn rect_triangle() {
+        assert!(!triangle().intersect(&Rect::new(6.0, 6.0, 4.0, 4.0)));
+        assert!(Rect::new(5.0, 5.0, 4.0, 4.0).intersect(&triangle()));
+        assert!(triangle().encloses(&Rect::new(1.0, 1.0, 4.0, 4.0)));
+        assert!(!triangle().encloses(&Rect::new(1.0, 1.0, 5.0, 4.0)));
+        assert!(rect().encloses(&triangle()));
+        assert!(!Rect::new(0.0, 0.0, 5.0, 5.0).encloses(&triangle()));
+    }
+
+    #[test]
+    fn triangle_triangle() {
+        assert!(!triangle().intersect(&Triangle::new(10.0, 10.0, 20.0, 10.0, 10.0, 20.0)));
+        assert!(triangle().intersect(&Triangle::new(5.0, 5.0, 20.0, 10.0, 10.0, 20.0)));
+        assert!(triangle().encloses(&Triangle::new(1.0, 1.0, 9.0, 1.0, 1.0, 9.0)));
+        assert!(!triangle().encloses(&Triangle::new(1.0, 1.0, 10.0, 1.0, 1.0, 9.0)));
+
+        // Crossing like a star, no corner inside the other
+        let up = Triangle::new(0.0, 0.0, 10.0, 0.0, 5.0, 10.0);
+        let down = Triangle::new(0.0, 7.0, 10.0, 7.0, 5.0, -3.0);
+        assert!(up.intersect(&down));
+        assert!(!up.encloses(&down));
+    }
+
+    #[test]
+    fn sub_pixel_scale() {
+        let mut small = circle();
+        small.scale(0.1);
+        assert_eq!(small.radius, 0.5);
+        assert!(small.intersect(&Point::new(0.5, 0.9)));
+
+        let mut rect = rect();
+        rect.scale(0.25);
+        rect.translate(Vec2::new(0.5, 0.5));
+        assert!(rect.encloses(&Point::new(3.0, 3.0)));
+        assert!(!rect.intersect(&Point::new(3.1, 0.0)));
+    }
+}
@@ -1,27 +1,36 @@
-use crate::{math::{Transform, Vec2}, DrawableSurface};
+use crate::math::{Affine, Transform, Vec2};
 
-use super::{Point, Rect, Triangle, Shape, Intersect};
+use super::{ComposedShape, Point, Line, Rect, Triangle, Polygon, Polyline, Path, Shape, Intersect};
 
-#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[derive(Debug, PartialEq, Clone, Copy)]
 pub struct Circle {
-    pub position: Vec2,
-    pub radius: i32,
+    pub position: Vec2<f32>,
+    pub radius: f32,
 }
 
 impl Circle {
-    pub fn new(x: i32, y: i32, radius: i32) -> Self {
+    pub fn new(x: f32, y: f32, radius: f32) -> Self {
         Self {
             position: Vec2::new(x, y),
             radius
         }
     }
 
-    pub fn vec(pos: Vec2, radius: i32) -> Self {
+    pub fn vec(pos: Vec2<f32>, radius: f32) -> Self {
         Self {
             position: pos,
             radius
         }
     }
+
+    /// Point lies inside or on the edge of the circle
+    fn contains(&self, point: Vec2<f32>) -> bool {
+        let dx = point.x as f64 - self.position.x as f64;
+        let dy = point.y as f64 - self.position.y as f64;
+        let r = self.radius as f64;
+
+        dx * dx + dy * dy <= r * r
+    }
 }
 
 impl Intersect for Circle {
@@ -34,20 +43,26 @@ impl Intersect for Circle {
     }
 
     fn intersect_point(&self, other: &Point) -> bool {
-        let dx = self.position.x - other.position.x;
-        let dy = self.position.y - other.position.y;
+        self.contains(other.position)
+    }
 
-        dx * dx + dy * dy <= self.radius * self.radius
+    fn intersect_line(&self, other: &Line) -> bool {
+        other.intersect_circle(self)
     }
 
+    // The point of the rect closest to the center decides
     fn intersect_rect(&self, other: &Rect) -> bool {
-        todo!()
+        let Vec2 { x, y } = self.position;
+        let min = other.position;
+        let max = other.position + other.size;
+
+        self.contains(Vec2::new(x.max(min.x).min(max.x), y.max(min.y).min(max.y)))
     }
 
     fn intersect_circle(&self, other: &Circle) -> bool {
-        let dx = self.position.x - other.position.x;
-        let dy = self.position.x - other.position.x;
-        let r = self.radius + other.radius;
+        let dx = self.position.x as f64 - other.position.x as f64;
+        let dy = self.position.y as f64 - other.position.y as f64;
+        let r = self.radius as f64 + other.radius as f64;
 
         dx * dx + dy * dy <= r * r
     }
@@ -56,8 +71,24 @@ impl Intersect for Circle {
         other.intersect_circle(self)
     }
 
+    fn intersect_polygon(&self, other: &Polygon) -> bool {
+        other.intersect_circle(self)
+    }
+
+    fn intersect_polyline(&self, other: &Polyline) -> bool {
+        other.intersect_circle(self)
+    }
+
+    fn intersect_path(&self, other: &Path) -> bool {
+        other.intersect_circle(self)
+    }
+
+    fn intersect_composed(&self, other: &ComposedShape) -> bool {
+        other.intersect_circle(self)
+    }
+
     fn encloses(&self, other: &dyn Intersect) -> bool {
-        todo!()
+        other.is_enclosed_by(self)
     }
 
     fn is_enclosed_by(&self, other: &dyn Intersect) -> bool {
@@ -65,43 +96,73 @@ impl Intersect for Circle {
     }
 
     fn encloses_point(&self, other: &Point) -> bool {
-        todo!()
+        self.contains(other.position)
+    }
+
+    // A circle is convex, so enclosing all the corners or end points is enough
+    fn encloses_line(&self, other: &Line) -> bool {
+        self.contains(other.start) && self.contains(other.end)
     }
 
     fn encloses_rect(&self, other: &Rect) -> bool {
-        todo!()
+        let Vec2 { x, y } = other.position;
+        let Vec2 { x: w, y: h } = other.size;
+
+        [Vec2::new(x, y), Vec2::new(x + w, y), Vec2::new(x, y + h), Vec2::new(x + w, y + h)]
+            .iter()
+            .all(|point| self.contains(*point))
     }
 
+    // The distance between the centers plus the other radius may not exceed our radius
     fn encloses_circle(&self, other: &Circle) -> bool {
-        todo!()
+        let dx = self.position.x as f64 - other.position.x as f64;
+        let dy = self.position.y as f64 - other.position.y as f64;
+        let room = self.radius as f64 - other.radius as f64;
+
+        room >= 0.0 && dx * dx + dy * dy <= room * room
     }
 
     fn encloses_triangle(&self, other: &Triangle) -> bool {
-        todo!()
+        self.contains(other.p1) && self.contains(other.p2) && self.contains(other.p3)
     }
-}
 
-// impl BitShaping for Circle {
-//     fn bit_dyn_clone(&self) -> Box<dyn Shape> {
-//         Box::new(*self)
-//     }
-// }
+    fn encloses_polygon(&self, other: &Polygon) -> bool {
+        other.points.iter().all(|point| self.contains(*point))
+    }
+
+    fn encloses_polyline(&self, other: &Polyline) -> bool {
+        other.points.iter().all(|point| self.contains(*point))
+    }
+
+    fn encloses_path(&self, other: &Path) -> bool {
+        other.flat_points().iter().all(|point| self.contains(*point))
+    }
+
+    fn encloses_composed(&self, other: &ComposedShape) -> bool {
+        other.is_inside(self)
+    }
+}
 
 impl Transform for Circle {
-    fn translate(&mut self, offset: Vec2) {
+    fn translate(&mut self, offset: Vec2<f32>) {
         self.position += offset
     }
 
     fn scale(&mut self, zoom: f32) {
         self.position *= zoom;
-        self.radius = (self.radius as f32 * zoom) as i32;
+        self.radius *= zoom;
     }
-
 }
 
 impl Shape for Circle {
     fn draw_yourself(&self, target: &crate::tekenen::SurfaceDrawer) {
-        target.circle(self.position.x, self.position.y, self.radius);
+        target.draw_circle(self.position, self.radius);
+    }
+
+    fn transformed(&self, matrix: &Affine) -> Box<dyn Shape> {
+        let Vec2 { x, y } = self.position;
+
+        Path::new().ellipse(x, y, self.radius, self.radius).transformed(matrix)
     }
 
     fn get_bounding_box(&self) -> Rect {
@@ -116,52 +177,8 @@ impl Shape for Circle {
     }
 
     fn iter(&self) -> Box<dyn Iterator<Item = Vec2>> {
-        Box::new(CircleIterator {
-            position: self.position,
-            radius: self.radius,
-            // TODO: can improve
-            curr: self.position + Vec2::new(-self.radius, -self.radius),
-            done: false,
-        })
-    }
-}
-
-pub struct CircleIterator {
-    position: Vec2,
-    radius: i32,
-    curr: Vec2,
-    done: bool,
-}
+        let circle = *self;
 
-impl Iterator for CircleIterator {
-    type Item = Vec2;
-
-    fn next(&mut self) -> Option<Self::Item> {
-        loop {
-            let output = self.curr;
-
-            if self.curr.x == self.position.x + self.radius {
-                if self.curr.y == self.position.y + self.radius {
-                    if self.done {
-                        return None
-                    } else {
-                        self.done = true;
-                    }
-                } else {
-                    self.curr.x = self.position.x - self.radius;
-                    self.curr.y += 1;
-                }
-            } else {
-                self.curr.x += 1;
-            }
-
-            let dx = self.curr.x - self.position.x;
-            let dy = self.curr.y - self.position.y;
-            let r = self.radius;
-
-            if dx * dx + dy * dy < r * r {
-                return Some(output)
-            }
-        }
+        Box::new(self.get_bounding_box().iter().filter(move |point| circle.contains((*point).into())))
     }
-}
\ No newline at end of file
+}
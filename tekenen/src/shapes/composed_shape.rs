@@ -1,135 +1,342 @@
-use crate::math::Vec2;
+use crate::math::{Affine, Transform, Vec2};
 
-use super::{Shape, BitShaping, Intersect, point::Point, rect::Rect, circle::Circle, triangle::Triangle};
+use super::{Shape, Intersect, point::Point, line::Line, rect::Rect, circle::Circle, triangle::Triangle, polygon::Polygon, polyline::Polyline, path::Path};
 
-#[derive(Debug, Clone, Copy)]
+/// How the two shapes of a ComposedShape are combined
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum BitOperand {
+    /// Intersection, inside both shapes
     And,
+
+    /// Union, inside either shape
     Or,
+
+    /// Inside exactly one of the shapes
     Xor,
+
+    /// Difference, inside the first shape but not inside the second
     Not
 }
 
+impl BitOperand {
+    /// Combine the insides of both shapes at a single point
+    pub fn apply(self, a: bool, b: bool) -> bool {
+        match self {
+            BitOperand::And => a & b,
+            BitOperand::Or  => a | b,
+            BitOperand::Xor => a ^ b,
+            BitOperand::Not => a & !b,
+        }
+    }
+
+    /// Combine the anti aliased coverage (0..=255) of both shapes at a single pixel
+    pub fn combine(self, a: u8, b: u8) -> u8 {
+        match self {
+            BitOperand::And => a.min(b),
+            BitOperand::Or  => a.max(b),
+            BitOperand::Xor => a.abs_diff(b),
+            BitOperand::Not => a.min(255 - b),
+        }
+    }
+}
+
+/// Constructive solid geometry, two shapes combined into one.
+/// The pixels are decided by the rasterizers of both shapes, the result is filled with the fill color and has no stroke.
+///
+/// Intersect is exact where it follows from the parts, like a union touching something when either part does.
+/// Otherwise the other shape is sampled at its vertices and at every pixel center (integer point) inside it.
 #[derive(Debug)]
 pub struct ComposedShape {
-    shape_a: Box<dyn Shape>,
-    shape_b: Box<dyn Shape>,
-    operation: BitOperand,
+    pub shape_a: Box<dyn Shape>,
+    pub shape_b: Box<dyn Shape>,
+    pub operation: BitOperand,
 }
 
 impl ComposedShape {
-    pub fn and(a: Box<dyn Shape>, b: Box<dyn Shape>) -> Self {
+    pub fn new(a: Box<dyn Shape>, b: Box<dyn Shape>, operation: BitOperand) -> Self {
         Self {
             shape_a: a,
             shape_b: b,
-            operation: BitOperand::And
+            operation
+        }
+    }
+
+    pub fn union(a: Box<dyn Shape>, b: Box<dyn Shape>) -> Self {
+        Self::new(a, b, BitOperand::Or)
+    }
+
+    pub fn intersection(a: Box<dyn Shape>, b: Box<dyn Shape>) -> Self {
+        Self::new(a, b, BitOperand::And)
+    }
+
+    pub fn difference(a: Box<dyn Shape>, b: Box<dyn Shape>) -> Self {
+        Self::new(a, b, BitOperand::Not)
+    }
+
+    pub fn xor(a: Box<dyn Shape>, b: Box<dyn Shape>) -> Self {
+        Self::new(a, b, BitOperand::Xor)
+    }
+
+    fn contains(&self, point: Vec2<f32>) -> bool {
+        let point = Point::from(point);
+
+        self.operation.apply(self.shape_a.intersect_point(&point), self.shape_b.intersect_point(&point))
+    }
+
+    /// Bounding box, or None when an intersection of disjoint shapes is empty
+    fn region(&self) -> Option<Rect> {
+        let a = self.shape_a.get_bounding_box();
+        let b = self.shape_b.get_bounding_box();
+
+        match self.operation {
+            BitOperand::And => overlap(a, b),
+            BitOperand::Or | BitOperand::Xor => Some(union(a, b)),
+            BitOperand::Not => Some(a),
+        }
+    }
+
+    /// Some sample of other is inside self, other lies within bounds
+    fn any_point(&self, other: &dyn Intersect, vertices: &[Vec2<f32>], bounds: Rect) -> bool {
+        let Some(region) = self.region().and_then(|region| overlap(region, bounds)) else {
+            return false
+        };
+
+        samples(other, vertices, region).any(|point| self.contains(point))
+    }
+
+    /// Every sample of other is inside self, other lies within bounds.
+    /// False when other has no samples, so nothing is enclosed by accident
+    fn all_points(&self, other: &dyn Intersect, vertices: &[Vec2<f32>], bounds: Rect) -> bool {
+        let mut samples = samples(other, vertices, bounds).peekable();
+
+        samples.peek().is_some() && samples.all(|point| self.contains(point))
+    }
+
+    fn intersect_shape(&self, other: &dyn Intersect, vertices: &[Vec2<f32>], bounds: Rect) -> bool {
+        let a = self.shape_a.intersect(other);
+        let b = self.shape_b.intersect(other);
+
+        match self.operation {
+            BitOperand::Or => a || b,
+            BitOperand::And => a && b && self.any_point(other, vertices, bounds),
+            BitOperand::Xor => (a || b) && self.any_point(other, vertices, bounds),
+            BitOperand::Not => a && self.any_point(other, vertices, bounds),
+        }
+    }
+
+    fn encloses_shape(&self, other: &dyn Intersect, vertices: &[Vec2<f32>], bounds: Rect) -> bool {
+        match self.operation {
+            BitOperand::And => self.shape_a.encloses(other) && self.shape_b.encloses(other),
+            BitOperand::Or => self.shape_a.encloses(other) || self.shape_b.encloses(other) || self.all_points(other, vertices, bounds),
+            BitOperand::Xor | BitOperand::Not => self.all_points(other, vertices, bounds),
+        }
+    }
+
+    /// Self lies completely inside outer, the other side of encloses_composed
+    pub(super) fn is_inside(&self, outer: &dyn Intersect) -> bool {
+        let a = outer.encloses(self.shape_a.intersect_upcast());
+        let b = outer.encloses(self.shape_b.intersect_upcast());
+
+        match self.operation {
+            BitOperand::Or => a && b,
+            BitOperand::Xor => (a && b) || self.points_inside(outer),
+            BitOperand::And => a || b || self.points_inside(outer),
+            BitOperand::Not => a || self.points_inside(outer),
+        }
+    }
+
+    fn points_inside(&self, outer: &dyn Intersect) -> bool {
+        self.iter().all(|point| outer.encloses_point(&point.into()))
+    }
+}
+
+impl Clone for ComposedShape {
+    fn clone(&self) -> Self {
+        Self {
+            shape_a: self.shape_a.dyn_clone(),
+            shape_b: self.shape_b.dyn_clone(),
+            operation: self.operation
         }
     }
 }
 
+/// Shared part of two bounding boxes, None if they do not touch
+fn overlap(a: Rect, b: Rect) -> Option<Rect> {
+    let x1 = a.position.x.max(b.position.x);
+    let y1 = a.position.y.max(b.position.y);
+    let x2 = (a.position.x + a.size.x).min(b.position.x + b.size.x);
+    let y2 = (a.position.y + a.size.y).min(b.position.y + b.size.y);
+
+    if x1 > x2 || y1 > y2 {
+        return None
+    }
+
+    Some(Rect::new(x1, y1, x2 - x1, y2 - y1))
+}
+
+fn union(a: Rect, b: Rect) -> Rect {
+    let x1 = a.position.x.min(b.position.x);
+    let y1 = a.position.y.min(b.position.y);
+    let x2 = (a.position.x + a.size.x).max(b.position.x + b.size.x);
+    let y2 = (a.position.y + a.size.y).max(b.position.y + b.size.y);
+
+    Rect::new(x1, y1, x2 - x1, y2 - y1)
+}
+
+/// Vertices of other and the pixel centers inside it, limited to region
+fn samples<'a>(other: &'a dyn Intersect, vertices: &'a [Vec2<f32>], region: Rect) -> impl Iterator<Item = Vec2<f32>> + 'a {
+    let inside = move |point: &Vec2<f32>| region.intersect_point(&(*point).into());
+    let centers = region.iter().map(Vec2::into).filter(|point| other.intersect_point(&Point::from(*point)));
+
+    vertices.iter().copied().filter(inside).chain(centers)
+}
+
+fn rect_vertices(rect: &Rect) -> [Vec2<f32>; 4] {
+    let Vec2 { x, y } = rect.position;
+    let Vec2 { x: w, y: h } = rect.size;
+
+    [Vec2::new(x, y), Vec2::new(x + w, y), Vec2::new(x, y + h), Vec2::new(x + w, y + h)]
+}
+
+/// Center and the four points of the circle on the axes
+fn circle_vertices(circle: &Circle) -> [Vec2<f32>; 5] {
+    let Vec2 { x, y } = circle.position;
+    let r = circle.radius;
+
+    [Vec2::new(x, y), Vec2::new(x - r, y), Vec2::new(x + r, y), Vec2::new(x, y - r), Vec2::new(x, y + r)]
+}
+
 impl Intersect for ComposedShape {
     fn intersect_upcast(&self) -> &dyn Intersect {
         self
     }
 
     fn intersect(&self, other: &dyn Intersect) -> bool {
-        other.intersect(self)
+        other.intersect_composed(self)
     }
 
     fn intersect_point(&self, other: &Point) -> bool {
-        todo!()
+        self.contains(other.position)
+    }
+
+    fn intersect_line(&self, other: &Line) -> bool {
+        self.intersect_shape(other, &[other.start, other.end], other.get_bounding_box())
     }
 
     fn intersect_rect(&self, other: &Rect) -> bool {
-        todo!()
+        self.intersect_shape(other, &rect_vertices(other), *other)
     }
 
     fn intersect_circle(&self, other: &Circle) -> bool {
-        todo!()
+        self.intersect_shape(other, &circle_vertices(other), other.get_bounding_box())
     }
 
     fn intersect_triangle(&self, other: &Triangle) -> bool {
-        todo!()
+        self.intersect_shape(other, &[other.p1, other.p2, other.p3], other.get_bounding_box())
+    }
+
+    fn intersect_polygon(&self, other: &Polygon) -> bool {
+        self.intersect_shape(other, &other.points, other.get_bounding_box())
+    }
+
+    fn intersect_polyline(&self, other: &Polyline) -> bool {
+        self.intersect_shape(other, &other.points, other.get_bounding_box())
+    }
+
+    fn intersect_path(&self, other: &Path) -> bool {
+        self.intersect_shape(other, &other.flat_contours().concat(), other.get_bounding_box())
+    }
+
+    fn intersect_composed(&self, other: &ComposedShape) -> bool {
+        self.intersect_shape(other, &[], other.get_bounding_box())
     }
 
     fn encloses(&self, other: &dyn Intersect) -> bool {
-        todo!()
+        other.is_enclosed_by(self)
     }
 
     fn is_enclosed_by(&self, other: &dyn Intersect) -> bool {
-        todo!()
+        other.encloses_composed(self)
     }
 
     fn encloses_point(&self, other: &Point) -> bool {
-        todo!()
+        self.contains(other.position)
+    }
+
+    fn encloses_line(&self, other: &Line) -> bool {
+        self.encloses_shape(other, &[other.start, other.end], other.get_bounding_box())
     }
 
     fn encloses_rect(&self, other: &Rect) -> bool {
-        todo!()
+        self.encloses_shape(other, &rect_vertices(other), *other)
     }
 
     fn encloses_circle(&self, other: &Circle) -> bool {
-        todo!()
+        self.encloses_shape(other, &circle_vertices(other), other.get_bounding_box())
     }
 
     fn encloses_triangle(&self, other: &Triangle) -> bool {
-        todo!()
+        self.encloses_shape(other, &[other.p1, other.p2, other.p3], other.get_bounding_box())
+    }
+
+    fn encloses_polygon(&self, other: &Polygon) -> bool {
+        self.encloses_shape(other, &other.points, other.get_bounding_box())
+    }
+
+    fn encloses_polyline(&self, other: &Polyline) -> bool {
+        self.encloses_shape(other, &other.points, other.get_bounding_box())
+    }
+
+    fn encloses_path(&self, other: &Path) -> bool {
+        self.encloses_shape(other, &other.flat_contours().concat(), other.get_bounding_box())
+    }
+
+    fn encloses_composed(&self, other: &ComposedShape) -> bool {
+        other.is_inside(self)
     }
 }
 
-use std::cmp::{min, max};
+impl Transform for ComposedShape {
+    fn translate(&mut self, offset: Vec2<f32>) {
+        self.shape_a.translate(offset);
+        self.shape_b.translate(offset);
+    }
+
+    fn scale(&mut self, zoom: f32) {
+        self.shape_a.scale(zoom);
+        self.shape_b.scale(zoom);
+    }
+}
 
 impl Shape for ComposedShape {
     fn draw_yourself(&self, target: &crate::tekenen::SurfaceDrawer) {
-        todo!()
+        target.draw_composed(self);
+    }
+
+    fn transformed(&self, matrix: &Affine) -> Box<dyn Shape> {
+        Box::new(Self::new(self.shape_a.transformed(matrix), self.shape_b.transformed(matrix), self.operation))
     }
 
     fn dyn_clone(&self) -> Box<dyn Shape> {
-        todo!()
+        Box::new(self.clone())
     }
 
     fn get_bounding_box(&self) -> Rect {
-        let a = self.shape_a.get_bounding_box();
-        let b = self.shape_a.get_bounding_box();
-
-        let Rect { position: Vec2 {x: ax, y: ay}, size: Vec2 {x: aw, y: ah} } = a;
-        let Rect { position: Vec2 {x: bx, y: by}, size: Vec2 {x: bw, y: bh} } = b;
-
-        let x1 = min(ax, bx);
-        let y1 = min(ay, by);
-
-        let x2 = max(ax + aw, bx + bw);
-        let y2 = max(ay + ah, by + bh);
-
-        Rect::new(x1, y1, x2 - x1, y2 - y1)
+        self.region().unwrap_or(Rect::new(0.0, 0.0, 0.0, 0.0))
     }
 
     fn iter(&self) -> Box<dyn Iterator<Item = Vec2>> {
+        let Some(region) = self.region() else {
+            return Box::new(std::iter::empty())
+        };
+
         Box::new(ComposedIterator {
             shape_a: self.shape_a.dyn_clone(),
             shape_b: self.shape_b.dyn_clone(),
             operation: self.operation,
-            rect_iter: self.get_bounding_box().iter()
+            rect_iter: region.iter()
         })
     }
-
-    fn scale(&mut self, zoom: f32) {
-        todo!()
-    }
-
-    fn tranlsate(&mut self, offset: Vec2) {
-        todo!()
-    }
-}
-
-impl BitShaping for ComposedShape {
-    fn bit_dyn_clone(&self) -> Box<dyn Shape> {
-        todo!()
-    }
-
-    fn join_and(&self, other: &dyn BitShaping) -> ComposedShape {
-        todo!()
-    }
 }
 
 struct ComposedIterator {
@@ -149,14 +356,113 @@ impl Iterator for ComposedIterator {
             let bit_a = self.shape_a.intersect_point(&candidate.into());
             let bit_b = self.shape_b.intersect_point(&candidate.into());
 
-            if match self.operation {
-                BitOperand::And => bit_a & bit_b,
-                BitOperand::Or  => bit_a | bit_b,
-                BitOperand::Xor => bit_a ^ bit_b,
-                BitOperand::Not =>       ! bit_a,
-            } {
+            if self.operation.apply(bit_a, bit_b) {
                 return Some(candidate)
             }
         }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::shapes::{circle::Circle, line::Line, point::Point, rect::Rect, Intersect, Shape};
+
+    use super::ComposedShape;
+
+    fn parts() -> (Box<Circle>, Box<Rect>) {
+        (Box::new(Circle::new(0.0, 0.0, 10.0)), Box::new(Rect::new(0.0, 0.0, 20.0, 20.0)))
+    }
+
+    #[test]
+    fn bounding_box_uses_both_shapes() {
+        let (circle, rect) = parts();
+
+        assert_eq!(ComposedShape::union(circle.clone(), rect.clone()).get_bounding_box(), Rect::new(-10.0, -10.0, 30.0, 30.0));
+        assert_eq!(ComposedShape::intersection(circle.clone(), rect.clone()).get_bounding_box(), Rect::new(0.0, 0.0, 10.0, 10.0));
+        assert_eq!(ComposedShape::difference(circle, rect).get_bounding_box(), Rect::new(-10.0, -10.0, 20.0, 20.0));
+
+        let apart = ComposedShape::intersection(Box::new(Rect::new(0.0, 0.0, 5.0, 5.0)), Box::new(Rect::new(10.0, 10.0, 5.0, 5.0)));
+        assert_eq!(apart.iter().count(), 0);
+    }
+
+    #[test]
+    fn points() {
+        let (circle, rect) = parts();
+        let union = ComposedShape::union(circle.clone(), rect.clone());
+        let intersection = ComposedShape::intersection(circle.clone(), rect.clone());
+        let difference = ComposedShape::difference(circle.clone(), rect.clone());
+        let xor = ComposedShape::xor(circle, rect);
+
+        for (point, inside) in [((-5.0, -5.0), [true, false, true, true]), ((5.0, 5.0), [true, true, false, false]), ((15.0, 15.0), [true, false, false, true])] {
+            let point = Point::new(point.0, point.1);
+
+            assert_eq!([&union, &intersection, &difference, &xor].map(|shape| shape.intersect(&point)), inside);
+        }
+    }
+
+    #[test]
+    fn against_other_shapes() {
+        let (circle, rect) = parts();
+        let difference = ComposedShape::difference(rect.clone(), circle.clone());
+
+        // The circle bites the corner out of the rect
+        assert!(!difference.intersect(&Circle::new(0.0, 0.0, 5.0)));
+        assert!(difference.intersect(&Line::new(0.0, 0.0, 20.0, 20.0)));
+        assert!(difference.encloses(&Rect::new(12.0, 12.0, 5.0, 5.0)));
+        assert!(!difference.encloses(&Rect::new(2.0, 2.0, 5.0, 5.0)));
+
+        let union = ComposedShape::union(circle, rect);
+        assert!(union.encloses(&Line::new(-5.0, 0.0, 15.0, 0.0)));
+        assert!(Rect::new(-10.0, -10.0, 30.0, 30.0).encloses(&union));
+        assert!(!Rect::new(-10.0, -10.0, 29.0, 30.0).encloses(&union));
+        assert!(union.intersect(&difference));
+    }
+
+    #[test]
+    fn fractional_coordinates() {
+        let (circle, rect) = parts();
+        let difference = ComposedShape::difference(rect, circle);
+
+        // Points and shapes between pixel centers are decided by their own coordinates
+        assert!(!difference.encloses(&Point::new(1.5, 1.5)));
+        assert!(difference.encloses(&Point::new(15.5, 15.5)));
+        assert!(!difference.encloses(&Point::new(50.5, 50.5)));
+        assert!(!difference.encloses(&Rect::new(100.2, 100.2, 0.6, 0.6)));
+        assert!(!difference.intersect(&Rect::new(100.2, 100.2, 0.6, 0.6)));
+        assert!(difference.encloses(&Rect::new(15.2, 15.2, 0.6, 0.6)));
+        assert!(difference.intersect(&Rect::new(15.2, 15.2, 0.6, 0.6)));
+        assert!(!difference.encloses(&Line::new(1.2, 1.2, 1.8, 1.8)));
+        assert!(!difference.encloses(&Rect::new(6.5, 6.5, 2.0, 2.0)));
+    }
+}
+
+#[cfg(all(test, feature = "image"))]
+mod snapshots {
+    use crate::{colors, shapes::{circle::Circle, rect::Rect}, DrawableSurface};
+    use crate::snapshot::{assert_snapshot, reference, render};
+
+    use super::ComposedShape;
+
+    #[test]
+    fn composed() {
+        let surface = render(96, 48, |ctx| {
+            ctx.background(colors::BLACK);
+            ctx.fill_color(colors::WHITE);
+
+            let operations = [ComposedShape::union, ComposedShape::intersection, ComposedShape::difference, ComposedShape::xor];
+
+            // Aliased on top, anti aliased below
+            for (row, anti_alias) in [false, true].into_iter().enumerate() {
+                ctx.anti_alias(anti_alias);
+
+                for (column, operation) in operations.iter().enumerate() {
+                    let (x, y) = (column as f32 * 24.0, row as f32 * 24.0);
+
+                    ctx.shape(operation(Box::new(Circle::new(x + 9.0, y + 9.0, 7.0)), Box::new(Rect::new(x + 10.0, y + 10.0, 12.0, 12.0))));
+                }
+            }
+        });
+
+        assert_snapshot(reference("composed"), &surface, 0);
+    }
+}
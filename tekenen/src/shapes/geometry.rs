@@ -0,0 +1,188 @@
+//! Helpers shared by the Intersect implementations.
+//! Everything is computed in f64, so whole and half pixel coordinates give exact answers.
+
+use std::cmp::Ordering;
+
+use crate::math::Vec2;
+
+use super::polygon::FillRule;
+
+/// Twice the signed area of the triangle a, b, c.
+/// Positive if the points turn clockwise on screen (y pointing down), zero if they are collinear.
+pub fn orientation(a: Vec2<f32>, b: Vec2<f32>, c: Vec2<f32>) -> f64 {
+    let (abx, aby) = (b.x as f64 - a.x as f64, b.y as f64 - a.y as f64);
+    let (acx, acy) = (c.x as f64 - a.x as f64, c.y as f64 - a.y as f64);
+
+    abx * acy - aby * acx
+}
+
+/// Side of the line a-b that c lies on, -1, 0 or 1
+fn side(a: Vec2<f32>, b: Vec2<f32>, c: Vec2<f32>) -> i32 {
+    let orientation = orientation(a, b, c);
+
+    if orientation > 0.0 {
+        1
+    } else if orientation < 0.0 {
+        -1
+    } else {
+        0
+    }
+}
+
+/// Vec2<f32> p lies on the segment from a to b, including the end points
+pub fn on_segment(a: Vec2<f32>, b: Vec2<f32>, p: Vec2<f32>) -> bool {
+    orientation(a, b, p) == 0.0 &&
+    p.x >= a.x.min(b.x) && p.x <= a.x.max(b.x) &&
+    p.y >= a.y.min(b.y) && p.y <= a.y.max(b.y)
+}
+
+/// Segments a1-a2 and b1-b2 share at least one point, touching counts
+pub fn segments_intersect(a1: Vec2<f32>, a2: Vec2<f32>, b1: Vec2<f32>, b2: Vec2<f32>) -> bool {
+    let d1 = side(b1, b2, a1);
+    let d2 = side(b1, b2, a2);
+    let d3 = side(a1, a2, b1);
+    let d4 = side(a1, a2, b2);
+
+    if d1 * d2 < 0 && d3 * d4 < 0 {
+        return true
+    }
+
+    on_segment(b1, b2, a1) || on_segment(b1, b2, a2) || on_segment(a1, a2, b1) || on_segment(a1, a2, b2)
+}
+
+/// Segments a1-a2 and b1-b2 cross each other in a single point that is not an end point of either
+pub fn segments_cross(a1: Vec2<f32>, a2: Vec2<f32>, b1: Vec2<f32>, b2: Vec2<f32>) -> bool {
+    let d1 = side(b1, b2, a1);
+    let d2 = side(b1, b2, a2);
+    let d3 = side(a1, a2, b1);
+    let d4 = side(a1, a2, b2);
+
+    d1 * d2 < 0 && d3 * d4 < 0
+}
+
+/// Compare the distance from p to the segment a-b with radius, without taking square roots
+pub fn compare_segment_distance(a: Vec2<f32>, b: Vec2<f32>, p: Vec2<f32>, radius: f32) -> Ordering {
+    let (abx, aby) = (b.x as f64 - a.x as f64, b.y as f64 - a.y as f64);
+    let (apx, apy) = (p.x as f64 - a.x as f64, p.y as f64 - a.y as f64);
+    let r2 = radius as f64 * radius as f64;
+
+    let length = abx * abx + aby * aby;
+    let t = apx * abx + apy * aby;
+
+    // Closest to one of the end points
+    if length == 0.0 || t <= 0.0 {
+        return (apx * apx + apy * apy).total_cmp(&r2)
+    }
+
+    if t >= length {
+        let (bpx, bpy) = (p.x as f64 - b.x as f64, p.y as f64 - b.y as f64);
+        return (bpx * bpx + bpy * bpy).total_cmp(&r2)
+    }
+
+    // Distance to the line is cross / |ab|, square both sides
+    let cross = abx * apy - aby * apx;
+    (cross * cross).total_cmp(&(r2 * length))
+}
+
+/// Iterate the edges of a closed polygon, the last edge goes back to the first point
+pub fn edges(points: &[Vec2<f32>]) -> impl Iterator<Item = (Vec2<f32>, Vec2<f32>)> + '_ {
+    points.iter().zip(points.iter().cycle().skip(1)).map(|(a, b)| (*a, *b))
+}
+
+/// The inside of one or more closed outlines, shared by the shapes with an area like Polygon and Path
+pub struct Area<'a> {
+    pub contours: Vec<&'a [Vec2<f32>]>,
+    pub fill_rule: FillRule,
+}
+
+impl<'a> Area<'a> {
+    pub fn new(contours: Vec<&'a [Vec2<f32>]>, fill_rule: FillRule) -> Self {
+        Self {
+            contours,
+            fill_rule
+        }
+    }
+
+    /// Edges of all the contours
+    pub fn edges(&self) -> impl Iterator<Item = (Vec2<f32>, Vec2<f32>)> + '_ {
+        self.contours.iter().flat_map(|contour| edges(contour))
+    }
+
+    fn first_point(&self) -> Option<Vec2<f32>> {
+        self.contours.iter().find_map(|contour| contour.first().copied())
+    }
+
+    /// Vec2<f32> is inside or on the outline
+    pub fn contains(&self, p: Vec2<f32>) -> bool {
+        if self.edges().any(|(a, b)| on_segment(a, b, p)) {
+            return true
+        }
+
+        // Winding number, counting edges crossing the horizontal ray to the right of p
+        let mut winding = 0;
+
+        for (a, b) in self.edges() {
+            if a.y <= p.y {
+                if b.y > p.y && orientation(a, b, p) > 0.0 {
+                    winding += 1;
+                }
+            } else if b.y <= p.y && orientation(a, b, p) < 0.0 {
+                winding -= 1;
+            }
+        }
+
+        match self.fill_rule {
+            FillRule::NonZero => winding != 0,
+            FillRule::EvenOdd => winding % 2 != 0,
+        }
+    }
+
+    /// The outline through points touches the area, or lies inside it
+    pub fn intersect_outline(&self, points: &[Vec2<f32>], closed: bool) -> bool {
+        let count = if closed { points.len() } else { points.len().saturating_sub(1) };
+
+        let touching = edges(points).take(count).any(|(a, b)| {
+            self.edges().any(|(c, d)| segments_intersect(a, b, c, d))
+        });
+
+        touching || points.first().is_some_and(|point| self.contains(*point))
+    }
+
+    /// All points are inside, and no edge of the outline leaves the area
+    pub fn encloses_outline(&self, points: &[Vec2<f32>], closed: bool) -> bool {
+        if !points.iter().all(|point| self.contains(*point)) {
+            return false
+        }
+
+        let count = if closed { points.len() } else { points.len().saturating_sub(1) };
+
+        edges(points).take(count).all(|(a, b)| {
+            self.edges().all(|(c, d)| !segments_cross(a, b, c, d))
+        })
+    }
+
+    pub fn intersect_area(&self, other: &Area) -> bool {
+        let touching = other.edges().any(|(a, b)| {
+            self.edges().any(|(c, d)| segments_intersect(a, b, c, d))
+        });
+
+        touching ||
+        other.first_point().is_some_and(|point| self.contains(point)) ||
+        self.first_point().is_some_and(|point| other.contains(point))
+    }
+
+    pub fn encloses_area(&self, other: &Area) -> bool {
+        other.contours.iter().all(|contour| self.encloses_outline(contour, true))
+    }
+
+    pub fn intersect_circle(&self, center: Vec2<f32>, radius: f32) -> bool {
+        self.contains(center) ||
+        self.edges().any(|(a, b)| compare_segment_distance(a, b, center, radius) != Ordering::Greater)
+    }
+
+    /// Touching the outline from the inside is still enclosed
+    pub fn encloses_circle(&self, center: Vec2<f32>, radius: f32) -> bool {
+        self.contains(center) &&
+        self.edges().all(|(a, b)| compare_segment_distance(a, b, center, radius) != Ordering::Less)
+    }
+}
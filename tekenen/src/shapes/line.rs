@@ -1,76 +1,122 @@
-use crate::{math::{Transform, Vec2}, DrawableSurface};
+use std::cmp::Ordering;
 
-use super::{Intersect, Shape};
+use crate::{math::{Affine, Transform, Vec2}, tekenen::raster};
 
-#[derive(Debug)]
+use super::{ComposedShape, geometry, polygon::bounding_box, Circle, Intersect, Path, Point, Polygon, Polyline, Rect, Shape, Triangle};
+
+#[derive(Debug, PartialEq, Clone)]
 pub struct Line {
-    pub start: Vec2,
-    pub end: Vec2,
+    pub start: Vec2<f32>,
+    pub end: Vec2<f32>,
 }
 
 impl Line {
-    pub fn new(x1: i32, y1: i32, x2: i32, y2: i32) -> Self {
+    pub fn new(x1: f32, y1: f32, x2: f32, y2: f32) -> Self {
         Self { start: Vec2::new(x1, y1), end: Vec2::new(x2, y2) }
     }
 
-    pub fn new_vec(p1: Vec2, p2: Vec2) -> Self {
+    pub fn new_vec(p1: Vec2<f32>, p2: Vec2<f32>) -> Self {
         Self { start: p1, end: p2 }
     }
-}
 
-// impl BitShaping for Line {
-//     fn bit_dyn_clone(&self) -> Box<dyn Shape> {
-//         todo!()
-//     }
-// }
+    fn contains(&self, point: Vec2<f32>) -> bool {
+        geometry::on_segment(self.start, self.end, point)
+    }
+
+    /// A line only encloses shapes that collapse onto it
+    fn contains_all(&self, points: &[Vec2<f32>]) -> bool {
+        points.iter().all(|point| self.contains(*point))
+    }
+}
 
 impl Intersect for Line {
     fn intersect_upcast(&self) -> &dyn Intersect {
-        todo!()
+        self
     }
 
     fn intersect(&self, other: &dyn Intersect) -> bool {
-        todo!()
+        other.intersect_line(self)
+    }
+
+    fn intersect_point(&self, other: &Point) -> bool {
+        self.contains(other.position)
+    }
+
+    fn intersect_line(&self, other: &Line) -> bool {
+        geometry::segments_intersect(self.start, self.end, other.start, other.end)
+    }
+
+    fn intersect_rect(&self, other: &Rect) -> bool {
+        Polygon::from(*other).intersect_line(self)
+    }
+
+    fn intersect_circle(&self, other: &Circle) -> bool {
+        geometry::compare_segment_distance(self.start, self.end, other.position, other.radius) != Ordering::Greater
+    }
+
+    fn intersect_triangle(&self, other: &Triangle) -> bool {
+        Polygon::from(other.clone()).intersect_line(self)
     }
 
-    fn intersect_point(&self, other: &super::point::Point) -> bool {
-        todo!()
+    fn intersect_polygon(&self, other: &Polygon) -> bool {
+        other.intersect_line(self)
     }
 
-    fn intersect_rect(&self, other: &super::rect::Rect) -> bool {
-        todo!()
+    fn intersect_polyline(&self, other: &Polyline) -> bool {
+        other.intersect_line(self)
     }
 
-    fn intersect_circle(&self, other: &super::circle::Circle) -> bool {
-        todo!()
+    fn intersect_path(&self, other: &Path) -> bool {
+        other.intersect_line(self)
     }
 
-    fn intersect_triangle(&self, other: &super::triangle::Triangle) -> bool {
-        todo!()
+    fn intersect_composed(&self, other: &ComposedShape) -> bool {
+        other.intersect_line(self)
     }
 
     fn encloses(&self, other: &dyn Intersect) -> bool {
-        todo!()
+        other.is_enclosed_by(self)
     }
 
     fn is_enclosed_by(&self, other: &dyn Intersect) -> bool {
-        todo!()
+        other.encloses_line(self)
     }
 
-    fn encloses_point(&self, other: &super::point::Point) -> bool {
-        todo!()
+    fn encloses_point(&self, other: &Point) -> bool {
+        self.contains(other.position)
     }
 
-    fn encloses_rect(&self, other: &super::rect::Rect) -> bool {
-        todo!()
+    fn encloses_line(&self, other: &Line) -> bool {
+        self.contains(other.start) && self.contains(other.end)
     }
 
-    fn encloses_circle(&self, other: &super::circle::Circle) -> bool {
-        todo!()
+    // Only a rect without width or height lies on a line
+    fn encloses_rect(&self, other: &Rect) -> bool {
+        (other.size.x == 0.0 || other.size.y == 0.0) && self.contains_all(&[other.position, other.position + other.size])
     }
 
-    fn encloses_triangle(&self, other: &super::triangle::Triangle) -> bool {
-        todo!()
+    fn encloses_circle(&self, other: &Circle) -> bool {
+        other.radius == 0.0 && self.contains(other.position)
+    }
+
+    fn encloses_triangle(&self, other: &Triangle) -> bool {
+        self.contains_all(&[other.p1, other.p2, other.p3])
+    }
+
+    fn encloses_polygon(&self, other: &Polygon) -> bool {
+        self.contains_all(&other.points)
+    }
+
+    fn encloses_polyline(&self, other: &Polyline) -> bool {
+        self.contains_all(&other.points)
+    }
+
+    fn encloses_path(&self, other: &Path) -> bool {
+        self.contains_all(&other.flat_points())
+    }
+
+    fn encloses_composed(&self, other: &ComposedShape) -> bool {
+        other.is_inside(self)
     }
 }
 
@@ -80,7 +126,7 @@ impl Transform for Line {
         self.end *= zoom;
     }
 
-    fn translate(&mut self, offset: Vec2) {
+    fn translate(&mut self, offset: Vec2<f32>) {
         self.start += offset;
         self.end += offset;
     }
@@ -88,18 +134,27 @@ impl Transform for Line {
 
 impl Shape for Line {
     fn draw_yourself(&self, target: &crate::tekenen::SurfaceDrawer) {
-        target.line(self.start.x, self.start.y, self.end.x, self.end.y);
+        target.draw_polyline(&[self.start, self.end]);
+    }
+
+    fn transformed(&self, matrix: &Affine) -> Box<dyn Shape> {
+        Box::new(Self::new_vec(*matrix * self.start, *matrix * self.end))
     }
 
     fn dyn_clone(&self) -> Box<dyn Shape> {
-        todo!()
+        Box::new(self.clone())
     }
 
-    fn get_bounding_box(&self) -> super::rect::Rect {
-        todo!()
+    fn get_bounding_box(&self) -> Rect {
+        bounding_box(&[self.start, self.end])
     }
 
     fn iter(&self) -> Box<dyn Iterator<Item = Vec2>> {
-        todo!()
+        let mut pixels = Vec::new();
+        let (start, end) = (self.start.round(), self.end.round());
+
+        raster::line(start.x, start.y, end.x, end.y, |x, y, _| pixels.push(Vec2::new(x, y)));
+
+        Box::new(pixels.into_iter())
     }
-}
\ No newline at end of file
+}
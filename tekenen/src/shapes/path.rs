@@ -0,0 +1,558 @@
+use crate::math::{Affine, Transform, Vec2};
+
+use super::{ComposedShape, geometry::Area, polygon::{bounding_box, FillRule}, Circle, Intersect, Line, Point, Polygon, Polyline, Rect, Shape, Triangle};
+
+/// Distance in pixels a flattened curve may deviate from the real curve when drawn
+pub const FLATTEN_TOLERANCE: f32 = 0.25;
+
+/// Tolerance for the Intersect methods, half a pixel is close enough to decide overlap
+const INTERSECT_TOLERANCE: f32 = 0.5;
+
+/// Curves are split at most this many times, so a single curve becomes at most 2^16 lines
+const MAX_DEPTH: u32 = 16;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PathCommand {
+    /// Start a new contour at the point
+    MoveTo(Vec2<f32>),
+
+    /// Straight line to the point
+    LineTo(Vec2<f32>),
+
+    /// Quadratic curve with one control point, to the end point
+    QuadTo(Vec2<f32>, Vec2<f32>),
+
+    /// Cubic curve with two control points, to the end point
+    CubicTo(Vec2<f32>, Vec2<f32>, Vec2<f32>),
+
+    /// Straight line back to the start of the contour
+    Close,
+}
+
+/// A contour of a path, flattened to straight lines
+#[derive(Debug, Clone, PartialEq)]
+pub struct Contour {
+    pub points: Vec<Vec2<f32>>,
+
+    /// The contour was closed, so the stroke connects the last point to the first
+    pub closed: bool,
+}
+
+/// Outline built from lines and curves, like a path on a html canvas.
+/// When filled every contour is closed, when stroked only contours ending with close are.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Path {
+    pub commands: Vec<PathCommand>,
+    pub fill_rule: FillRule,
+}
+
+/// Distance from p to the line through a and b
+fn distance_to_line(a: Vec2<f32>, b: Vec2<f32>, p: Vec2<f32>) -> f32 {
+    let length = a.distance(b);
+
+    if length == 0.0 {
+        return a.distance(p)
+    }
+
+    (b - a).cross(p - a).abs() / length
+}
+
+/// Split the curve in halves until it is flat enough, the end points of the lines are pushed to points
+fn flatten_cubic(p0: Vec2<f32>, p1: Vec2<f32>, p2: Vec2<f32>, p3: Vec2<f32>, tolerance: f32, depth: u32, points: &mut Vec<Vec2<f32>>) {
+    // The curve never deviates more than 3/4 of the distance of the control points to the chord
+    let flatness = 0.75 * distance_to_line(p0, p3, p1).max(distance_to_line(p0, p3, p2));
+
+    if flatness <= tolerance || depth >= MAX_DEPTH {
+        points.push(p3);
+        return
+    }
+
+    // de Casteljau
+    let a = p0.lerp(p1, 0.5);
+    let b = p1.lerp(p2, 0.5);
+    let c = p2.lerp(p3, 0.5);
+    let ab = a.lerp(b, 0.5);
+    let bc = b.lerp(c, 0.5);
+    let middle = ab.lerp(bc, 0.5);
+
+    flatten_cubic(p0, a, ab, middle, tolerance, depth + 1, points);
+    flatten_cubic(middle, bc, c, p3, tolerance, depth + 1, points);
+}
+
+impl Path {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_fill_rule(mut self, fill_rule: FillRule) -> Self {
+        self.fill_rule = fill_rule;
+        self
+    }
+
+    /// The point the next command starts from
+    fn current(&self) -> Vec2<f32> {
+        match self.commands.last() {
+            None => Vec2::new(0.0, 0.0),
+            Some(PathCommand::MoveTo(point) | PathCommand::LineTo(point)) => *point,
+            Some(PathCommand::QuadTo(_, point) | PathCommand::CubicTo(_, _, point)) => *point,
+            Some(PathCommand::Close) => self.commands.iter().rev().find_map(|command| match command {
+                PathCommand::MoveTo(point) => Some(*point),
+                _ => None
+            }).unwrap_or(Vec2::new(0.0, 0.0)),
+        }
+    }
+
+    /// Like on a canvas, drawing without a current point starts a contour instead
+    fn ensure_start(&mut self, point: Vec2<f32>) {
+        if self.commands.is_empty() {
+            self.commands.push(PathCommand::MoveTo(point));
+        }
+    }
+
+    pub fn move_to(mut self, x: f32, y: f32) -> Self {
+        self.commands.push(PathCommand::MoveTo(Vec2::new(x, y)));
+        self
+    }
+
+    pub fn line_to(mut self, x: f32, y: f32) -> Self {
+        self.ensure_start(Vec2::new(x, y));
+        self.commands.push(PathCommand::LineTo(Vec2::new(x, y)));
+        self
+    }
+
+    pub fn quad_to(mut self, cx: f32, cy: f32, x: f32, y: f32) -> Self {
+        self.ensure_start(Vec2::new(cx, cy));
+        self.commands.push(PathCommand::QuadTo(Vec2::new(cx, cy), Vec2::new(x, y)));
+        self
+    }
+
+    pub fn cubic_to(mut self, c1x: f32, c1y: f32, c2x: f32, c2y: f32, x: f32, y: f32) -> Self {
+        self.ensure_start(Vec2::new(c1x, c1y));
+        self.commands.push(PathCommand::CubicTo(Vec2::new(c1x, c1y), Vec2::new(c2x, c2y), Vec2::new(x, y)));
+        self
+    }
+
+    /// Round the corner at [x1, y1], like arcTo on a canvas.
+    /// A line goes to the start of the arc, the arc ends on the line from [x1, y1] to [x2, y2].
+    /// The arc is stored as cubic curves, so it transforms like everything else.
+    pub fn arc_to(mut self, x1: f32, y1: f32, x2: f32, y2: f32, radius: f32) -> Self {
+        self.ensure_start(Vec2::new(x1, y1));
+
+        let p0 = self.current();
+        let corner = Vec2::new(x1, y1);
+        let p2 = Vec2::new(x2, y2);
+
+        let (l0, l2) = (corner.distance(p0), corner.distance(p2));
+
+        // Straight or degenerate corners are drawn as a line
+        if radius <= 0.0 || l0 == 0.0 || l2 == 0.0 || distance_to_line(p0, p2, corner) == 0.0 {
+            return self.line_to(x1, y1)
+        }
+
+        let d0 = (p0 - corner) / l0;
+        let d2 = (p2 - corner) / l2;
+
+        // Half the angle between both lines
+        let half = d0.dot(d2).clamp(-1.0, 1.0).acos() / 2.0;
+        let tangent = radius / half.tan();
+
+        let start = corner + d0 * tangent;
+        let end = corner + d2 * tangent;
+
+        let bisector = d0 + d2;
+        let center = corner + bisector * (radius / half.sin() / bisector.length());
+
+        let from = (start - center).angle();
+        let to = (end - center).angle();
+
+        // The arc is always shorter than half a circle, so take the short way around
+        let mut sweep = to - from;
+
+        if sweep > std::f32::consts::PI {
+            sweep -= std::f32::consts::TAU;
+        } else if sweep < -std::f32::consts::PI {
+            sweep += std::f32::consts::TAU;
+        }
+
+        self = self.line_to(start.x, start.y);
+
+        // A cubic curve is a close fit for at most a quarter circle
+        let parts = if sweep.abs() > std::f32::consts::FRAC_PI_2 { 2 } else { 1 };
+        let step = sweep / parts as f32;
+        let k = 4.0 / 3.0 * (step / 4.0).tan() * radius;
+
+        for part in 0..parts {
+            let a0 = from + step * part as f32;
+            let a1 = a0 + step;
+
+            let (sin0, cos0) = a0.sin_cos();
+            let (sin1, cos1) = a1.sin_cos();
+
+            let p3 = Vec2::new(center.x + radius * cos1, center.y + radius * sin1);
+            let c1 = Vec2::new(center.x + radius * cos0 - k * sin0, center.y + radius * sin0 + k * cos0);
+            let c2 = Vec2::new(p3.x + k * sin1, p3.y - k * cos1);
+
+            self.commands.push(PathCommand::CubicTo(c1, c2, p3));
+        }
+
+        self
+    }
+
+    pub fn close(mut self) -> Self {
+        self.commands.push(PathCommand::Close);
+        self
+    }
+
+    /// Closed contour around the rect, clockwise on screen
+    pub fn rect(self, x: f32, y: f32, w: f32, h: f32) -> Self {
+        self.move_to(x, y).line_to(x + w, y).line_to(x + w, y + h).line_to(x, y + h).close()
+    }
+
+    /// Closed contour around the ellipse, made of four quarter cubic curves
+    pub fn ellipse(mut self, cx: f32, cy: f32, rx: f32, ry: f32) -> Self {
+        // Control point distance for the closest cubic fit of a quarter circle
+        const KAPPA: f32 = 0.552_284_8;
+        let (kx, ky) = (rx * KAPPA, ry * KAPPA);
+
+        self = self.move_to(cx + rx, cy);
+
+        for (x, y, c1, c2) in [
+            (cx, cy + ry, (cx + rx, cy + ky), (cx + kx, cy + ry)),
+            (cx - rx, cy, (cx - kx, cy + ry), (cx - rx, cy + ky)),
+            (cx, cy - ry, (cx - rx, cy - ky), (cx - kx, cy - ry)),
+            (cx + rx, cy, (cx + kx, cy - ry), (cx + rx, cy - ky)),
+        ] {
+            self = self.cubic_to(c1.0, c1.1, c2.0, c2.1, x, y);
+        }
+
+        self.close()
+    }
+
+    /// Closed contour through whole pixel points, used to turn other shapes into paths
+    pub(super) fn polygon(mut self, points: &[Vec2<f32>]) -> Self {
+        for (index, point) in points.iter().enumerate() {
+            let Vec2 { x, y } = *point;
+            self = if index == 0 { self.move_to(x, y) } else { self.line_to(x, y) };
+        }
+
+        self.close()
+    }
+
+    /// Move every point, including the control points, through the matrix
+    pub fn transform(&mut self, matrix: &Affine) {
+        self.map_points(|point| *matrix * point)
+    }
+
+    /// Replace every curve by straight lines, deviating at most tolerance from the curve
+    pub fn flatten(&self, tolerance: f32) -> Vec<Contour> {
+        let mut contours = Vec::new();
+        let mut contour = Contour { points: Vec::new(), closed: false };
+
+        let finish = |contour: &mut Contour, contours: &mut Vec<Contour>, closed: bool| {
+            let mut finished = std::mem::replace(contour, Contour { points: Vec::new(), closed: false });
+            finished.closed = closed;
+
+            if finished.points.len() > 1 {
+                contours.push(finished);
+            }
+        };
+
+        for command in &self.commands {
+            let last = contour.points.last().copied().unwrap_or(Vec2::new(0.0, 0.0));
+
+            match *command {
+                PathCommand::MoveTo(point) => {
+                    finish(&mut contour, &mut contours, false);
+                    contour.points.push(point);
+                },
+                PathCommand::LineTo(point) => {
+                    contour.points.push(point);
+                },
+                PathCommand::QuadTo(control, point) => {
+                    // Every quadratic curve is also a cubic curve
+                    let c1 = last.lerp(control, 2.0 / 3.0);
+                    let c2 = point.lerp(control, 2.0 / 3.0);
+
+                    flatten_cubic(last, c1, c2, point, tolerance, 0, &mut contour.points);
+                },
+                PathCommand::CubicTo(c1, c2, point) => {
+                    flatten_cubic(last, c1, c2, point, tolerance, 0, &mut contour.points);
+                },
+                PathCommand::Close => {
+                    let start = contour.points.first().copied();
+                    finish(&mut contour, &mut contours, true);
+
+                    // The next contour starts where this one started
+                    contour.points.extend(start);
+                },
+            }
+        }
+
+        finish(&mut contour, &mut contours, false);
+        contours
+    }
+
+    /// Flattened contours, used for the Intersect methods
+    pub(super) fn flat_contours(&self) -> Vec<Vec<Vec2<f32>>> {
+        self.flatten(INTERSECT_TOLERANCE).into_iter().map(|contour| contour.points).collect()
+    }
+
+    /// Run f with the filled area of the path
+    pub(super) fn with_area<R>(&self, f: impl FnOnce(&Area) -> R) -> R {
+        let contours = self.flat_contours();
+        f(&Area::new(contours.iter().map(|contour| contour.as_slice()).collect(), self.fill_rule))
+    }
+
+    /// All the points of the flattened contours
+    pub(super) fn flat_points(&self) -> Vec<Vec2<f32>> {
+        self.flat_contours().concat()
+    }
+
+    /// Every point of the commands, including the control points
+    fn control_points(&self) -> impl Iterator<Item = Vec2<f32>> + '_ {
+        self.commands.iter().flat_map(|command| match *command {
+            PathCommand::MoveTo(point) | PathCommand::LineTo(point) => vec![point],
+            PathCommand::QuadTo(control, point) => vec![control, point],
+            PathCommand::CubicTo(c1, c2, point) => vec![c1, c2, point],
+            PathCommand::Close => vec![],
+        })
+    }
+
+    fn map_points(&mut self, f: impl Fn(Vec2<f32>) -> Vec2<f32>) {
+        for command in &mut self.commands {
+            match command {
+                PathCommand::MoveTo(point) | PathCommand::LineTo(point) => *point = f(*point),
+                PathCommand::QuadTo(control, point) => {
+                    *control = f(*control);
+                    *point = f(*point);
+                },
+                PathCommand::CubicTo(c1, c2, point) => {
+                    *c1 = f(*c1);
+                    *c2 = f(*c2);
+                    *point = f(*point);
+                },
+                PathCommand::Close => {},
+            }
+        }
+    }
+}
+
+impl Intersect for Path {
+    fn intersect_upcast(&self) -> &dyn Intersect {
+        self
+    }
+
+    fn intersect(&self, other: &dyn Intersect) -> bool {
+        other.intersect_path(self)
+    }
+
+    fn intersect_point(&self, other: &Point) -> bool {
+        self.with_area(|area| area.contains(other.position))
+    }
+
+    fn intersect_line(&self, other: &Line) -> bool {
+        self.with_area(|area| area.intersect_outline(&[other.start, other.end], false))
+    }
+
+    fn intersect_rect(&self, other: &Rect) -> bool {
+        self.with_area(|area| area.intersect_area(&Polygon::from(*other).area()))
+    }
+
+    fn intersect_circle(&self, other: &Circle) -> bool {
+        self.with_area(|area| area.intersect_circle(other.position, other.radius))
+    }
+
+    fn intersect_triangle(&self, other: &Triangle) -> bool {
+        self.with_area(|area| area.intersect_area(&Polygon::from(other.clone()).area()))
+    }
+
+    fn intersect_polygon(&self, other: &Polygon) -> bool {
+        self.with_area(|area| area.intersect_area(&other.area()))
+    }
+
+    fn intersect_polyline(&self, other: &Polyline) -> bool {
+        self.with_area(|area| area.intersect_outline(&other.points, false))
+    }
+
+    fn intersect_path(&self, other: &Path) -> bool {
+        self.with_area(|area| other.with_area(|other| area.intersect_area(other)))
+    }
+
+    fn intersect_composed(&self, other: &ComposedShape) -> bool {
+        other.intersect_path(self)
+    }
+
+    fn encloses(&self, other: &dyn Intersect) -> bool {
+        other.is_enclosed_by(self)
+    }
+
+    fn is_enclosed_by(&self, other: &dyn Intersect) -> bool {
+        other.encloses_path(self)
+    }
+
+    fn encloses_point(&self, other: &Point) -> bool {
+        self.intersect_point(other)
+    }
+
+    fn encloses_line(&self, other: &Line) -> bool {
+        self.with_area(|area| area.encloses_outline(&[other.start, other.end], false))
+    }
+
+    fn encloses_rect(&self, other: &Rect) -> bool {
+        self.with_area(|area| area.encloses_area(&Polygon::from(*other).area()))
+    }
+
+    fn encloses_circle(&self, other: &Circle) -> bool {
+        self.with_area(|area| area.encloses_circle(other.position, other.radius))
+    }
+
+    fn encloses_triangle(&self, other: &Triangle) -> bool {
+        self.with_area(|area| area.encloses_area(&Polygon::from(other.clone()).area()))
+    }
+
+    fn encloses_polygon(&self, other: &Polygon) -> bool {
+        self.with_area(|area| area.encloses_area(&other.area()))
+    }
+
+    fn encloses_polyline(&self, other: &Polyline) -> bool {
+        self.with_area(|area| area.encloses_outline(&other.points, false))
+    }
+
+    fn encloses_path(&self, other: &Path) -> bool {
+        self.with_area(|area| other.with_area(|other| area.encloses_area(other)))
+    }
+
+    fn encloses_composed(&self, other: &ComposedShape) -> bool {
+        other.is_inside(self)
+    }
+}
+
+impl Transform for Path {
+    fn translate(&mut self, offset: Vec2<f32>) {
+        self.map_points(|point| point + offset)
+    }
+
+    fn scale(&mut self, zoom: f32) {
+        self.map_points(|point| point * zoom)
+    }
+}
+
+impl Shape for Path {
+    fn draw_yourself(&self, target: &crate::tekenen::SurfaceDrawer) {
+        target.draw_path(self);
+    }
+
+    fn transformed(&self, matrix: &Affine) -> Box<dyn Shape> {
+        let mut path = self.clone();
+        path.transform(matrix);
+        Box::new(path)
+    }
+
+    /// Bounding box of all the points, including the control points
+    fn get_bounding_box(&self) -> Rect {
+        let points: Vec<_> = self.control_points().collect();
+
+        bounding_box(&points)
+    }
+
+    fn dyn_clone(&self) -> Box<dyn Shape> {
+        Box::new(self.clone())
+    }
+
+    fn iter(&self) -> Box<dyn Iterator<Item = Vec2>> {
+        let contours = self.flat_contours();
+        let fill_rule = self.fill_rule;
+
+        Box::new(self.get_bounding_box().iter().filter(move |point| {
+            Area::new(contours.iter().map(|contour| contour.as_slice()).collect(), fill_rule).contains((*point).into())
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::shapes::{point::Point, rect::Rect, Intersect};
+
+    use super::Path;
+
+    #[test]
+    fn flattened_arc_stays_on_the_circle() {
+        // Rounded corner with radius 20 around the center [20, 20]
+        let path = Path::new().move_to(0.0, 40.0).arc_to(0.0, 0.0, 40.0, 0.0, 20.0);
+        let contours = path.flatten(0.1);
+
+        assert_eq!(contours.len(), 1);
+        assert!(contours[0].points.len() > 4);
+
+        for point in contours[0].points.iter().filter(|point| point.y < 20.0 && point.x < 20.0) {
+            let distance = (point.x - 20.0).hypot(point.y - 20.0);
+            assert!((distance - 20.0).abs() < 0.1, "{point:?} is {distance} from the center");
+        }
+
+        let end = contours[0].points.last().unwrap();
+        assert!((end.x - 20.0).abs() < 1e-3 && end.y.abs() < 1e-3);
+    }
+
+    #[test]
+    fn contours_and_holes() {
+        let square = |path: Path, x: f32, size: f32| {
+            path.move_to(x, x).line_to(x + size, x).line_to(x + size, x + size).line_to(x, x + size).close()
+        };
+
+        // Inner square wound the same way, so only even-odd cuts a hole
+        let path = square(square(Path::new(), 0.0, 30.0), 10.0, 10.0);
+        let contours = path.flatten(0.25);
+
+        assert_eq!(contours.len(), 2);
+        assert!(contours.iter().all(|contour| contour.closed));
+
+        assert!(path.intersect_point(&Point::new(15.0, 15.0)));
+        assert!(!path.clone().with_fill_rule(super::FillRule::EvenOdd).intersect_point(&Point::new(15.0, 15.0)));
+        assert!(path.encloses(&Rect::new(2.0, 2.0, 5.0, 5.0)));
+        assert!(!path.intersect(&Rect::new(31.0, 0.0, 5.0, 5.0)));
+    }
+}
+
+#[cfg(all(test, feature = "image"))]
+mod snapshots {
+    use crate::{colors, shapes::polygon::FillRule, DrawableSurface};
+    use crate::snapshot::{assert_snapshot, reference, render};
+
+    use super::Path;
+
+    #[test]
+    fn path() {
+        let surface = render(96, 64, |ctx| {
+            ctx.background(colors::BLACK);
+            ctx.anti_alias(true);
+
+            // Node editor like connection
+            ctx.no_fill();
+            ctx.stroke_color(colors::WHITE);
+            ctx.stroke_width(2.0);
+            ctx.path(&Path::new().move_to(4.0, 8.0).cubic_to(48.0, 8.0, 20.0, 56.0, 60.0, 56.0));
+
+            // Rounded rect with a hole
+            ctx.fill_color(colors::RED);
+            ctx.stroke_color(colors::GREEN);
+            ctx.stroke_width(1.5);
+
+            let path = Path::new()
+                .move_to(66.0, 4.0)
+                .arc_to(92.0, 4.0, 92.0, 30.0, 6.0)
+                .arc_to(92.0, 30.0, 66.0, 30.0, 6.0)
+                .arc_to(60.0, 30.0, 60.0, 4.0, 6.0)
+                .arc_to(60.0, 4.0, 92.0, 4.0, 6.0)
+                .close()
+                .move_to(70.0, 12.0)
+                .quad_to(76.0, 2.0, 82.0, 12.0)
+                .line_to(76.0, 24.0)
+                .close()
+                .with_fill_rule(FillRule::EvenOdd);
+
+            ctx.path(&path);
+        });
+
+        assert_snapshot(reference("path"), &surface, 0);
+    }
+}
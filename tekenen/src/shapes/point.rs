@@ -1,24 +1,32 @@
-use crate::math::Vec2;
+use crate::math::{Vec2, Zero};
 
-use super::{Rect, Circle, Triangle, Intersect};
+use super::{ComposedShape, Line, Rect, Circle, Triangle, Polygon, Polyline, Path, Intersect};
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, PartialEq)]
 pub struct Point {
-    pub position: Vec2
+    pub position: Vec2<f32>
 }
 
 impl Point {
-    pub fn new(x: i32, y: i32) -> Self {
+    pub fn new(x: f32, y: f32) -> Self {
         Self {
             position: Vec2::new(x, y)
         }
     }
 }
 
+impl From<Vec2<f32>> for Point {
+    fn from(position: Vec2<f32>) -> Self {
+        Self {
+            position
+        }
+    }
+}
+
 impl From<Vec2> for Point {
     fn from(position: Vec2) -> Self {
         Self {
-            position
+            position: position.into()
         }
     }
 }
@@ -42,6 +50,10 @@ impl Intersect for Point {
         self.position == other.position
     }
 
+    fn intersect_line(&self, other: &Line) -> bool {
+        other.intersect_point(self)
+    }
+
     fn intersect_rect(&self, other: &Rect) -> bool {
         other.intersect_point(self)
     }
@@ -54,8 +66,24 @@ impl Intersect for Point {
         other.intersect_point(self)
     }
 
+    fn intersect_polygon(&self, other: &Polygon) -> bool {
+        other.intersect_point(self)
+    }
+
+    fn intersect_polyline(&self, other: &Polyline) -> bool {
+        other.intersect_point(self)
+    }
+
+    fn intersect_path(&self, other: &Path) -> bool {
+        other.intersect_point(self)
+    }
+
+    fn intersect_composed(&self, other: &ComposedShape) -> bool {
+        other.intersect_point(self)
+    }
+
     fn encloses(&self, other: &dyn Intersect) -> bool {
-        other.encloses_point(self)
+        other.is_enclosed_by(self)
     }
 
     fn is_enclosed_by(&self, other: &dyn Intersect) -> bool {
@@ -66,17 +94,37 @@ impl Intersect for Point {
         self == other
     }
 
-    // If rect has zero size?
+    // Only shapes that collapse to a single point can be enclosed
+    fn encloses_line(&self, other: &Line) -> bool {
+        other.start == self.position && other.end == self.position
+    }
+
     fn encloses_rect(&self, other: &Rect) -> bool {
-        false
+        other.position == self.position && other.size.is_zero()
     }
 
     fn encloses_circle(&self, other: &Circle) -> bool {
-        false
+        other.position == self.position && other.radius == 0.0
     }
 
     fn encloses_triangle(&self, other: &Triangle) -> bool {
-        false
+        [other.p1, other.p2, other.p3].iter().all(|point| *point == self.position)
+    }
+
+    fn encloses_polygon(&self, other: &Polygon) -> bool {
+        other.points.iter().all(|point| *point == self.position)
+    }
+
+    fn encloses_polyline(&self, other: &Polyline) -> bool {
+        other.points.iter().all(|point| *point == self.position)
+    }
+
+    fn encloses_path(&self, other: &Path) -> bool {
+        other.flat_points().iter().all(|point| *point == self.position)
+    }
+
+    fn encloses_composed(&self, other: &ComposedShape) -> bool {
+        other.is_inside(self)
     }
 }
 
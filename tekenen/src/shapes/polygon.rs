@@ -0,0 +1,288 @@
+use crate::math::{Affine, Transform, Vec2};
+
+use super::{ComposedShape, geometry, Circle, Intersect, Line, Path, Point, Polyline, Rect, Shape, Triangle};
+
+/// Decides which parts of a self intersecting polygon are inside
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FillRule {
+    /// Inside if the outline winds around the point at least once, in either direction
+    #[default]
+    NonZero,
+
+    /// Inside if a ray from the point crosses the outline an odd number of times
+    EvenOdd,
+}
+
+/// Closed shape through any number of points, the last point connects back to the first
+#[derive(Debug, PartialEq, Clone)]
+pub struct Polygon {
+    pub points: Vec<Vec2<f32>>,
+    pub fill_rule: FillRule,
+}
+
+impl Polygon {
+    pub fn new(points: Vec<Vec2<f32>>) -> Self {
+        Self {
+            points,
+            fill_rule: FillRule::default()
+        }
+    }
+
+    pub fn with_fill_rule(mut self, fill_rule: FillRule) -> Self {
+        self.fill_rule = fill_rule;
+        self
+    }
+
+    pub(super) fn area(&self) -> geometry::Area<'_> {
+        geometry::Area::new(vec![&self.points], self.fill_rule)
+    }
+
+    fn contains(&self, point: Vec2<f32>) -> bool {
+        self.area().contains(point)
+    }
+}
+
+impl From<Triangle> for Polygon {
+    fn from(triangle: Triangle) -> Self {
+        Self::new(vec![triangle.p1, triangle.p2, triangle.p3])
+    }
+}
+
+impl From<Rect> for Polygon {
+    fn from(rect: Rect) -> Self {
+        let Vec2 { x, y } = rect.position;
+        let Vec2 { x: w, y: h } = rect.size;
+
+        Self::new(vec![Vec2::new(x, y), Vec2::new(x + w, y), Vec2::new(x + w, y + h), Vec2::new(x, y + h)])
+    }
+}
+
+impl Intersect for Polygon {
+    fn intersect_upcast(&self) -> &dyn Intersect {
+        self
+    }
+
+    fn intersect(&self, other: &dyn Intersect) -> bool {
+        other.intersect_polygon(self)
+    }
+
+    fn intersect_point(&self, other: &Point) -> bool {
+        self.contains(other.position)
+    }
+
+    fn intersect_line(&self, other: &Line) -> bool {
+        self.area().intersect_outline(&[other.start, other.end], false)
+    }
+
+    fn intersect_rect(&self, other: &Rect) -> bool {
+        self.intersect_polygon(&(*other).into())
+    }
+
+    fn intersect_circle(&self, other: &Circle) -> bool {
+        self.area().intersect_circle(other.position, other.radius)
+    }
+
+    fn intersect_triangle(&self, other: &Triangle) -> bool {
+        self.intersect_polygon(&other.clone().into())
+    }
+
+    fn intersect_polygon(&self, other: &Polygon) -> bool {
+        self.area().intersect_area(&other.area())
+    }
+
+    fn intersect_polyline(&self, other: &Polyline) -> bool {
+        self.area().intersect_outline(&other.points, false)
+    }
+
+    fn intersect_path(&self, other: &Path) -> bool {
+        other.intersect_polygon(self)
+    }
+
+    fn intersect_composed(&self, other: &ComposedShape) -> bool {
+        other.intersect_polygon(self)
+    }
+
+    fn encloses(&self, other: &dyn Intersect) -> bool {
+        other.is_enclosed_by(self)
+    }
+
+    fn is_enclosed_by(&self, other: &dyn Intersect) -> bool {
+        other.encloses_polygon(self)
+    }
+
+    fn encloses_point(&self, other: &Point) -> bool {
+        self.contains(other.position)
+    }
+
+    fn encloses_line(&self, other: &Line) -> bool {
+        self.area().encloses_outline(&[other.start, other.end], false)
+    }
+
+    fn encloses_rect(&self, other: &Rect) -> bool {
+        self.encloses_polygon(&(*other).into())
+    }
+
+    fn encloses_circle(&self, other: &Circle) -> bool {
+        self.area().encloses_circle(other.position, other.radius)
+    }
+
+    fn encloses_triangle(&self, other: &Triangle) -> bool {
+        self.encloses_polygon(&other.clone().into())
+    }
+
+    fn encloses_polygon(&self, other: &Polygon) -> bool {
+        self.area().encloses_area(&other.area())
+    }
+
+    fn encloses_polyline(&self, other: &Polyline) -> bool {
+        self.area().encloses_outline(&other.points, false)
+    }
+
+    fn encloses_path(&self, other: &Path) -> bool {
+        other.with_area(|area| self.area().encloses_area(area))
+    }
+
+    fn encloses_composed(&self, other: &ComposedShape) -> bool {
+        other.is_inside(self)
+    }
+}
+
+impl Transform for Polygon {
+    fn translate(&mut self, offset: Vec2<f32>) {
+        for point in &mut self.points {
+            *point += offset
+        }
+    }
+
+    fn scale(&mut self, zoom: f32) {
+        for point in &mut self.points {
+            *point *= zoom
+        }
+    }
+}
+
+impl Shape for Polygon {
+    fn draw_yourself(&self, target: &crate::tekenen::SurfaceDrawer) {
+        target.draw_polygon(&self.points, self.fill_rule);
+    }
+
+    fn transformed(&self, matrix: &Affine) -> Box<dyn Shape> {
+        Path::new().polygon(&self.points).with_fill_rule(self.fill_rule).transformed(matrix)
+    }
+
+    fn get_bounding_box(&self) -> Rect {
+        bounding_box(&self.points)
+    }
+
+    fn dyn_clone(&self) -> Box<dyn Shape> {
+        Box::new(self.clone())
+    }
+
+    fn iter(&self) -> Box<dyn Iterator<Item = Vec2>> {
+        let polygon = self.clone();
+
+        Box::new(self.get_bounding_box().iter().filter(move |point| polygon.contains((*point).into())))
+    }
+}
+
+/// Smallest rect containing all the points
+pub(super) fn bounding_box(points: &[Vec2<f32>]) -> Rect {
+    let Some(first) = points.first() else {
+        return Rect::new(0.0, 0.0, 0.0, 0.0)
+    };
+
+    let (mut min, mut max) = (*first, *first);
+
+    for point in points {
+        min.x = min.x.min(point.x);
+        min.y = min.y.min(point.y);
+        max.x = max.x.max(point.x);
+        max.y = max.y.max(point.y);
+    }
+
+    Rect::new_vec(min, max - min)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{math::Vec2, shapes::{circle::Circle, point::Point, polyline::Polyline, rect::Rect, Intersect}};
+
+    use super::{FillRule, Polygon};
+
+    fn star() -> Polygon {
+        // Pentagram, the pentagon in the middle is wound twice
+        Polygon::new(vec![Vec2::new(0.0, -10.0), Vec2::new(6.0, 8.0), Vec2::new(-10.0, -3.0), Vec2::new(10.0, -3.0), Vec2::new(-6.0, 8.0)])
+    }
+
+    #[test]
+    fn fill_rules() {
+        let center = Point::new(0.0, 0.0);
+
+        assert!(star().intersect_point(&center));
+        assert!(!star().with_fill_rule(FillRule::EvenOdd).intersect_point(&center));
+        assert!(star().with_fill_rule(FillRule::EvenOdd).intersect_point(&Point::new(0.0, -9.0)));
+        assert!(star().intersect_point(&Point::new(0.0, -10.0)));
+        assert!(!star().intersect_point(&Point::new(10.0, 10.0)));
+    }
+
+    #[test]
+    fn against_other_shapes() {
+        let square: Polygon = Rect::new(0.0, 0.0, 10.0, 10.0).into();
+
+        assert!(square.encloses(&Circle::new(5.0, 5.0, 5.0)));
+        assert!(!square.encloses(&Circle::new(5.0, 5.0, 6.0)));
+        assert!(square.intersect(&Circle::new(-3.0, 5.0, 3.0)));
+        assert!(!square.intersect(&Circle::new(-4.0, 5.0, 3.0)));
+
+        assert!(square.encloses(&Rect::new(0.0, 0.0, 10.0, 10.0)));
+        assert!(square.intersect(&Rect::new(10.0, 10.0, 5.0, 5.0)));
+        assert!(!square.intersect(&Rect::new(11.0, 0.0, 5.0, 5.0)));
+
+        let zigzag = Polyline::new(vec![Vec2::new(2.0, 2.0), Vec2::new(8.0, 5.0), Vec2::new(2.0, 8.0)]);
+        assert!(square.encloses(&zigzag));
+        assert!(zigzag.intersect(&square));
+        assert!(!star().encloses(&Polyline::new(vec![Vec2::new(0.0, 0.0), Vec2::new(0.0, 9.0)])));
+    }
+}
+
+#[cfg(all(test, feature = "image"))]
+mod snapshots {
+    use crate::{colors, math::Vec2, DrawableSurface, LineCap, LineJoin};
+    use crate::snapshot::{assert_snapshot, reference, render};
+
+    use super::{FillRule, Polygon};
+
+    #[test]
+    fn polygon() {
+        let star: Vec<_> = (0..5).map(|i| {
+            let angle = i as f32 * std::f32::consts::PI * 4.0 / 5.0 - std::f32::consts::FRAC_PI_2;
+            Vec2::new((angle.cos() * 14.0) as i32, (angle.sin() * 14.0) as i32)
+        }).collect();
+
+        let at = |x: i32, y: i32| star.iter().map(|point| *point + Vec2::new(x, y)).collect::<Vec<_>>();
+
+        let surface = render(96, 64, |ctx| {
+            ctx.background(colors::BLACK);
+
+            ctx.fill_color(colors::RED);
+            ctx.polygon(&at(16, 16));
+            ctx.shape(Polygon::new(at(48, 16).into_iter().map(Vec2::into).collect()).with_fill_rule(FillRule::EvenOdd));
+
+            ctx.fill_color(colors::GREEN);
+            ctx.triangle(68, 4, 92, 10, 74, 28);
+
+            ctx.anti_alias(true);
+            ctx.fill_color(colors::BLUE);
+            ctx.stroke_color(colors::WHITE);
+            ctx.stroke_width(2.0);
+            ctx.line_join(LineJoin::Round);
+            ctx.polygon(&[Vec2::new(6, 36), Vec2::new(40, 40), Vec2::new(20, 60)]);
+
+            ctx.stroke_width(4.0);
+            ctx.line_cap(LineCap::Round);
+            ctx.polyline(&[Vec2::new(48, 58), Vec2::new(60, 38), Vec2::new(72, 54), Vec2::new(90, 40)]);
+        });
+
+        assert_snapshot(reference("polygon"), &surface, 0);
+    }
+}
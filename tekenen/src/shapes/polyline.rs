@@ -0,0 +1,182 @@
+use std::cmp::Ordering;
+
+use crate::{math::{Affine, Transform, Vec2}, tekenen::raster};
+
+use super::{ComposedShape, geometry, polygon::bounding_box, Circle, Intersect, Line, Path, Point, Polygon, Rect, Shape, Triangle};
+
+/// Open line through any number of points, it has no inside
+#[derive(Debug, PartialEq, Clone)]
+pub struct Polyline {
+    pub points: Vec<Vec2<f32>>,
+}
+
+impl Polyline {
+    pub fn new(points: Vec<Vec2<f32>>) -> Self {
+        Self {
+            points
+        }
+    }
+
+    /// Iterate the segments between consecutive points
+    fn segments(&self) -> impl Iterator<Item = (Vec2<f32>, Vec2<f32>)> + '_ {
+        self.points.windows(2).map(|pair| (pair[0], pair[1]))
+    }
+
+    fn contains(&self, point: Vec2<f32>) -> bool {
+        match self.points.as_slice() {
+            [single] => *single == point,
+            _ => self.segments().any(|(a, b)| geometry::on_segment(a, b, point)),
+        }
+    }
+}
+
+impl Intersect for Polyline {
+    fn intersect_upcast(&self) -> &dyn Intersect {
+        self
+    }
+
+    fn intersect(&self, other: &dyn Intersect) -> bool {
+        other.intersect_polyline(self)
+    }
+
+    fn intersect_point(&self, other: &Point) -> bool {
+        self.contains(other.position)
+    }
+
+    fn intersect_line(&self, other: &Line) -> bool {
+        self.intersect_polyline(&Polyline::new(vec![other.start, other.end]))
+    }
+
+    fn intersect_rect(&self, other: &Rect) -> bool {
+        Polygon::from(*other).intersect_polyline(self)
+    }
+
+    fn intersect_circle(&self, other: &Circle) -> bool {
+        match self.points.as_slice() {
+            [single] => geometry::compare_segment_distance(*single, *single, other.position, other.radius) != Ordering::Greater,
+            _ => self.segments().any(|(a, b)| {
+                geometry::compare_segment_distance(a, b, other.position, other.radius) != Ordering::Greater
+            }),
+        }
+    }
+
+    fn intersect_triangle(&self, other: &Triangle) -> bool {
+        Polygon::from(other.clone()).intersect_polyline(self)
+    }
+
+    fn intersect_polygon(&self, other: &Polygon) -> bool {
+        other.intersect_polyline(self)
+    }
+
+    fn intersect_path(&self, other: &Path) -> bool {
+        other.intersect_polyline(self)
+    }
+
+    fn intersect_composed(&self, other: &ComposedShape) -> bool {
+        other.intersect_polyline(self)
+    }
+
+    fn intersect_polyline(&self, other: &Polyline) -> bool {
+        if self.points.len() < 2 || other.points.len() < 2 {
+            return self.points.iter().any(|point| other.contains(*point)) ||
+                other.points.iter().any(|point| self.contains(*point))
+        }
+
+        self.segments().any(|(a, b)| other.segments().any(|(c, d)| geometry::segments_intersect(a, b, c, d)))
+    }
+
+    fn encloses(&self, other: &dyn Intersect) -> bool {
+        other.is_enclosed_by(self)
+    }
+
+    fn is_enclosed_by(&self, other: &dyn Intersect) -> bool {
+        other.encloses_polyline(self)
+    }
+
+    fn encloses_point(&self, other: &Point) -> bool {
+        self.contains(other.position)
+    }
+
+    fn encloses_line(&self, other: &Line) -> bool {
+        self.encloses_polyline(&Polyline::new(vec![other.start, other.end]))
+    }
+
+    // Without an inside, only other lines can be enclosed
+    fn encloses_rect(&self, other: &Rect) -> bool {
+        false
+    }
+
+    fn encloses_circle(&self, other: &Circle) -> bool {
+        false
+    }
+
+    fn encloses_triangle(&self, other: &Triangle) -> bool {
+        false
+    }
+
+    fn encloses_polygon(&self, other: &Polygon) -> bool {
+        false
+    }
+
+    fn encloses_path(&self, other: &Path) -> bool {
+        false
+    }
+
+    fn encloses_composed(&self, other: &ComposedShape) -> bool {
+        other.is_inside(self)
+    }
+
+    /// Every segment of other lies on one of the segments of self
+    fn encloses_polyline(&self, other: &Polyline) -> bool {
+        other.points.iter().all(|point| self.contains(*point)) &&
+        other.segments().all(|(a, b)| {
+            self.segments().any(|(c, d)| geometry::on_segment(c, d, a) && geometry::on_segment(c, d, b))
+        })
+    }
+}
+
+impl Transform for Polyline {
+    fn translate(&mut self, offset: Vec2<f32>) {
+        for point in &mut self.points {
+            *point += offset
+        }
+    }
+
+    fn scale(&mut self, zoom: f32) {
+        for point in &mut self.points {
+            *point *= zoom
+        }
+    }
+}
+
+impl Shape for Polyline {
+    fn draw_yourself(&self, target: &crate::tekenen::SurfaceDrawer) {
+        target.draw_polyline(&self.points);
+    }
+
+    // Stays a polyline, so it keeps falling back to one pixel in the fill color without a stroke
+    fn transformed(&self, matrix: &Affine) -> Box<dyn Shape> {
+        Box::new(Self::new(self.points.iter().map(|point| *matrix * *point).collect()))
+    }
+
+    fn get_bounding_box(&self) -> Rect {
+        bounding_box(&self.points)
+    }
+
+    fn dyn_clone(&self) -> Box<dyn Shape> {
+        Box::new(self.clone())
+    }
+
+    fn iter(&self) -> Box<dyn Iterator<Item = Vec2>> {
+        let mut pixels = Vec::new();
+
+        for (a, b) in self.segments() {
+            let (a, b) = (a.round(), b.round());
+            raster::line(a.x, a.y, b.x, b.y, |x, y, _| pixels.push(Vec2::new(x, y)));
+        }
+
+        pixels.dedup();
+
+        Box::new(pixels.into_iter())
+    }
+}
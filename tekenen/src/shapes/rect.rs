@@ -1,30 +1,32 @@
 use std::{fmt::Display, ops::{Add, AddAssign, Sub}};
 
-use crate::{math::{Transform, Vec2}, DrawableSurface};
+use crate::math::{Affine, Transform, Vec2};
 
-use super::{Circle, Intersect, Point, Shape, Sides, Triangle};
+use super::{ComposedShape, Circle, Intersect, Line, Path, Point, Polygon, Polyline, Shape, Sides, Triangle};
 
+/// Axis aligned rectangle. As a shape it has float geometry,
+/// layout and clipping use `Rect<i32>` for whole pixels.
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
-pub struct Rect {
-    pub position: Vec2,
-    pub size: Vec2,
+pub struct Rect<T = f32> {
+    pub position: Vec2<T>,
+    pub size: Vec2<T>,
 }
 
-impl Display for Rect {
+impl<T: Display> Display for Rect<T> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "Rect: [x: {}, y: {}, w: {}, h: {}]", self.position.x, self.position.y, self.size.x, self.size.y)
     }
 }
 
-impl Rect {
-    pub fn new(x: i32, y: i32, w: i32, h: i32) -> Self {
+impl<T> Rect<T> {
+    pub fn new(x: T, y: T, w: T, h: T) -> Self {
         Self {
             position: Vec2 { x, y },
             size: Vec2 {x: w, y: h}
         }
     }
 
-    pub fn new_vec(pos: Vec2, size: Vec2) -> Self {
+    pub fn new_vec(pos: Vec2<T>, size: Vec2<T>) -> Self {
         Self {
             position: pos,
             size
@@ -32,8 +34,14 @@ impl Rect {
     }
 }
 
+impl From<Rect<i32>> for Rect {
+    fn from(rect: Rect<i32>) -> Self {
+        Self::new_vec(rect.position.into(), rect.size.into())
+    }
+}
+
 impl Transform for Rect {
-    fn translate(&mut self, offset: Vec2) {
+    fn translate(&mut self, offset: Vec2<f32>) {
         self.position += offset
     }
 
@@ -45,7 +53,14 @@ impl Transform for Rect {
 
 impl Shape for Rect {
     fn draw_yourself(&self, target: &crate::tekenen::SurfaceDrawer) {
-        target.rect(self.position.x, self.position.y, self.size.x, self.size.y);
+        target.draw_rect(self);
+    }
+
+    fn transformed(&self, matrix: &Affine) -> Box<dyn Shape> {
+        let Vec2 { x, y } = self.position;
+        let Vec2 { x: w, y: h } = self.size;
+
+        Path::new().rect(x, y, w, h).transformed(matrix)
     }
 
     fn get_bounding_box(&self) -> Rect {
@@ -56,12 +71,14 @@ impl Shape for Rect {
         Box::new(*self)
     }
 
+    /// Every whole pixel on or inside the edges
     fn iter(&self) -> Box<dyn Iterator<Item = Vec2>> {
+        let start = self.position.ceil();
+
         Box::new(RectIter {
-            start: self.position,
-            end: self.position + self.size,
-            curr: self.position,
-            done: false
+            start,
+            end: (self.position + self.size).floor(),
+            curr: start,
         })
     }
 }
@@ -82,6 +99,10 @@ impl Intersect for Rect {
         other.position.y <= self.position.y + self.size.y
     }
 
+    fn intersect_line(&self, other: &Line) -> bool {
+        other.intersect_rect(self)
+    }
+
     fn intersect_rect(&self, other: &Rect) -> bool {
         !(
             self.position.x > other.position.x + other.size.x ||
@@ -99,6 +120,22 @@ impl Intersect for Rect {
         other.intersect_rect(self)
     }
 
+    fn intersect_polygon(&self, other: &Polygon) -> bool {
+        other.intersect_rect(self)
+    }
+
+    fn intersect_polyline(&self, other: &Polyline) -> bool {
+        other.intersect_rect(self)
+    }
+
+    fn intersect_path(&self, other: &Path) -> bool {
+        other.intersect_rect(self)
+    }
+
+    fn intersect_composed(&self, other: &ComposedShape) -> bool {
+        other.intersect_rect(self)
+    }
+
     fn encloses(&self, other: &dyn Intersect) -> bool {
         other.is_enclosed_by(self)
     }
@@ -111,6 +148,11 @@ impl Intersect for Rect {
         self.intersect_point(other)
     }
 
+    fn encloses_line(&self, other: &Line) -> bool {
+        self.encloses_point(&other.start.into()) &&
+        self.encloses_point(&other.end.into())
+    }
+
     fn encloses_rect(&self, other: &Rect) -> bool {
         other.position.x >= self.position.x &&
         other.position.x + other.size.x <= self.position.x + self.size.x &&
@@ -130,38 +172,43 @@ impl Intersect for Rect {
         self.encloses_point(&other.p2.into()) &&
         self.encloses_point(&other.p3.into())
     }
-}
 
-// impl BitShaping for Rect {
-//     fn bit_dyn_clone(&self) -> Box<dyn Shape> {
-//         Box::new(*self)
-//     }
-// }
+    fn encloses_polygon(&self, other: &Polygon) -> bool {
+        other.points.iter().all(|point| self.encloses_point(&(*point).into()))
+    }
+
+    fn encloses_polyline(&self, other: &Polyline) -> bool {
+        other.points.iter().all(|point| self.encloses_point(&(*point).into()))
+    }
+
+    fn encloses_path(&self, other: &Path) -> bool {
+        other.flat_points().iter().all(|point| self.encloses_point(&(*point).into()))
+    }
+
+    fn encloses_composed(&self, other: &ComposedShape) -> bool {
+        other.is_inside(self)
+    }
+}
 
 pub struct RectIter {
     start: Vec2,
     end: Vec2,
     curr: Vec2,
-    done: bool
 }
 
 impl Iterator for RectIter {
     type Item = Vec2;
 
     fn next(&mut self) -> Option<Self::Item> {
+        if self.start.x > self.end.x || self.curr.y > self.end.y {
+            return None
+        }
+
         let output = self.curr;
 
         if self.curr.x == self.end.x {
-            if self.curr.y == self.end.y {
-                if self.done {
-                    return None
-                } else {
-                    self.done = true;
-                }
-            } else {
-                self.curr.x = self.start.x;
-                self.curr.y += 1;
-            }
+            self.curr.x = self.start.x;
+            self.curr.y += 1;
         } else {
             self.curr.x += 1;
         }
@@ -170,7 +217,7 @@ impl Iterator for RectIter {
     }
 }
 
-impl Add<Sides> for Rect {
+impl Add<Sides> for Rect<i32> {
     type Output = Self;
 
     fn add(self, rhs: Sides) -> Self::Output {
@@ -187,7 +234,7 @@ impl Add<Sides> for Rect {
     }
 }
 
-impl AddAssign<Sides> for Rect {
+impl AddAssign<Sides> for Rect<i32> {
     fn add_assign(&mut self, rhs: Sides) {
         self.position.x -= rhs.top;
         self.position.y -= rhs.left;
@@ -196,7 +243,7 @@ impl AddAssign<Sides> for Rect {
     }
 }
 
-impl Sub<Sides> for Rect {
+impl Sub<Sides> for Rect<i32> {
     type Output = Self;
 
     fn sub(self, rhs: Sides) -> Self::Output {
@@ -1,41 +1,80 @@
-use crate::math::Vec2;
+use crate::math::{Affine, Transform, Vec2};
 
-use super::{Point, Rect, Circle, Intersect};
+use super::{ComposedShape, polygon::{bounding_box, FillRule}, Point, Line, Rect, Circle, Polygon, Polyline, Path, Intersect, Shape};
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Clone)]
 pub struct Triangle {
-    pub p1: Vec2,
-    pub p2: Vec2,
-    pub p3: Vec2,
+    pub p1: Vec2<f32>,
+    pub p2: Vec2<f32>,
+    pub p3: Vec2<f32>,
 }
 
+impl Triangle {
+    pub fn new(x1: f32, y1: f32, x2: f32, y2: f32, x3: f32, y3: f32) -> Self {
+        Self {
+            p1: Vec2::new(x1, y1),
+            p2: Vec2::new(x2, y2),
+            p3: Vec2::new(x3, y3),
+        }
+    }
+
+    pub fn new_vec(p1: Vec2<f32>, p2: Vec2<f32>, p3: Vec2<f32>) -> Self {
+        Self { p1, p2, p3 }
+    }
+
+    fn polygon(&self) -> Polygon {
+        Polygon::from(self.clone())
+    }
+}
+
+// A triangle is the simplest polygon, all the exact work happens there
 impl Intersect for Triangle {
     fn intersect_upcast(&self) -> &dyn Intersect {
         self
     }
 
     fn intersect(&self, other: &dyn Intersect) -> bool {
-        other.intersect(self)
+        other.intersect_triangle(self)
     }
 
     fn intersect_point(&self, other: &Point) -> bool {
-        todo!()
+        self.polygon().intersect_point(other)
+    }
+
+    fn intersect_line(&self, other: &Line) -> bool {
+        self.polygon().intersect_line(other)
     }
 
     fn intersect_rect(&self, other: &Rect) -> bool {
-        todo!()
+        self.polygon().intersect_rect(other)
     }
 
     fn intersect_circle(&self, other: &Circle) -> bool {
-        todo!()
+        self.polygon().intersect_circle(other)
     }
 
     fn intersect_triangle(&self, other: &Triangle) -> bool {
-        todo!()
+        self.polygon().intersect_triangle(other)
+    }
+
+    fn intersect_polygon(&self, other: &Polygon) -> bool {
+        other.intersect_triangle(self)
+    }
+
+    fn intersect_polyline(&self, other: &Polyline) -> bool {
+        other.intersect_triangle(self)
+    }
+
+    fn intersect_path(&self, other: &Path) -> bool {
+        other.intersect_triangle(self)
+    }
+
+    fn intersect_composed(&self, other: &ComposedShape) -> bool {
+        other.intersect_triangle(self)
     }
 
     fn encloses(&self, other: &dyn Intersect) -> bool {
-        todo!()
+        other.is_enclosed_by(self)
     }
 
     fn is_enclosed_by(&self, other: &dyn Intersect) -> bool {
@@ -43,18 +82,74 @@ impl Intersect for Triangle {
     }
 
     fn encloses_point(&self, other: &Point) -> bool {
-        todo!()
+        self.polygon().encloses_point(other)
+    }
+
+    fn encloses_line(&self, other: &Line) -> bool {
+        self.polygon().encloses_line(other)
     }
 
     fn encloses_rect(&self, other: &Rect) -> bool {
-        todo!()
+        self.polygon().encloses_rect(other)
     }
 
     fn encloses_circle(&self, other: &Circle) -> bool {
-        todo!()
+        self.polygon().encloses_circle(other)
     }
 
     fn encloses_triangle(&self, other: &Triangle) -> bool {
-        todo!()
+        self.polygon().encloses_triangle(other)
+    }
+
+    fn encloses_polygon(&self, other: &Polygon) -> bool {
+        self.polygon().encloses_polygon(other)
+    }
+
+    fn encloses_polyline(&self, other: &Polyline) -> bool {
+        self.polygon().encloses_polyline(other)
+    }
+
+    fn encloses_path(&self, other: &Path) -> bool {
+        self.polygon().encloses_path(other)
+    }
+
+    fn encloses_composed(&self, other: &ComposedShape) -> bool {
+        other.is_inside(self)
+    }
+}
+
+impl Transform for Triangle {
+    fn translate(&mut self, offset: Vec2<f32>) {
+        self.p1 += offset;
+        self.p2 += offset;
+        self.p3 += offset;
+    }
+
+    fn scale(&mut self, zoom: f32) {
+        self.p1 *= zoom;
+        self.p2 *= zoom;
+        self.p3 *= zoom;
+    }
+}
+
+impl Shape for Triangle {
+    fn draw_yourself(&self, target: &crate::tekenen::SurfaceDrawer) {
+        target.draw_polygon(&[self.p1, self.p2, self.p3], FillRule::NonZero);
+    }
+
+    fn transformed(&self, matrix: &Affine) -> Box<dyn Shape> {
+        self.polygon().transformed(matrix)
+    }
+
+    fn get_bounding_box(&self) -> Rect {
+        bounding_box(&[self.p1, self.p2, self.p3])
+    }
+
+    fn dyn_clone(&self) -> Box<dyn Shape> {
+        Box::new(self.clone())
+    }
+
+    fn iter(&self) -> Box<dyn Iterator<Item = Vec2>> {
+        self.polygon().iter()
     }
 }
@@ -0,0 +1,222 @@
+use std::{fmt, path::{Path, PathBuf}};
+
+use crate::{platform::{ImageLoadingError, Platform, PlatformTrait}, ImageSavingError, Pixel, Surface, SurfaceView};
+
+/// Set this environment variable to rewrite the references instead of comparing against them.
+pub const BLESS_ENV: &str = "TEKENEN_BLESS";
+
+/// Render into a fresh transparent surface
+pub fn render(width: i32, height: i32, draw: impl FnOnce(&SurfaceView)) -> Surface {
+    let view = SurfaceView::new(width, height, Surface::new(width as usize, height as usize).into());
+    draw(&view);
+
+    let surface = view.get_surface().clone();
+    surface
+}
+
+/// Result of a pixel by pixel comparison
+#[derive(Debug)]
+pub struct SnapshotDiff {
+    /// Number of pixels with at least one channel differing by more than the tolerance
+    pub mismatched: usize,
+
+    /// Biggest difference found in any channel
+    pub max_difference: u8,
+
+    /// Position, expected and actual color of the first mismatched pixel
+    pub first_mismatch: Option<(i32, i32, Pixel, Pixel)>,
+
+    /// Actual image dimmed to gray, with the mismatched pixels painted red
+    pub image: Surface,
+}
+
+impl SnapshotDiff {
+    pub fn is_match(&self) -> bool {
+        self.mismatched == 0
+    }
+}
+
+/// Compare two surfaces of the same size, channels may differ at most by `tolerance`
+pub fn compare(expected: &Surface, actual: &Surface, tolerance: u8) -> SnapshotDiff {
+    assert!(
+        expected.width() == actual.width() && expected.height() == actual.height(),
+        "Cannot compare surfaces of different size, {}x{} and {}x{}",
+        expected.width(), expected.height(),
+        actual.width(), actual.height()
+    );
+
+    let mut image = Surface::new(actual.width() as usize, actual.height() as usize);
+    let mut mismatched = 0;
+    let mut max_difference = 0;
+    let mut first_mismatch = None;
+
+    for y in 0..actual.height() {
+        for x in 0..actual.width() {
+            let index = (y * actual.width() + x) as usize;
+            let a = expected.pixels[index];
+            let b = actual.pixels[index];
+
+            let difference = (0..4).map(|i| a[i].abs_diff(b[i])).max().unwrap();
+            max_difference = max_difference.max(difference);
+
+            if difference > tolerance {
+                mismatched += 1;
+                first_mismatch.get_or_insert((x, y, a, b));
+                image.set_pixel(x, y, [0xff, 0x00, 0x00, 0xff]);
+            } else {
+                let gray = ((b[0] as u32 + b[1] as u32 + b[2] as u32) / 9) as u8;
+                image.set_pixel(x, y, [gray, gray, gray, 0xff]);
+            }
+        }
+    }
+
+    SnapshotDiff {
+        mismatched,
+        max_difference,
+        first_mismatch,
+        image,
+    }
+}
+
+#[derive(Debug)]
+pub enum SnapshotError {
+    /// There is no reference, run with TEKENEN_BLESS=1 to create it
+    MissingReference(PathBuf),
+    SizeMismatch {
+        expected: (i32, i32),
+        actual: (i32, i32),
+    },
+    Mismatch {
+        diff: SnapshotDiff,
+        diff_path: PathBuf,
+    },
+    IOError(std::io::Error),
+    ImageError(ImageLoadingError),
+    SavingError(ImageSavingError),
+}
+
+impl fmt::Display for SnapshotError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::MissingReference(path) => write!(f, "Missing reference {}, run with {BLESS_ENV}=1 to create it", path.display()),
+            Self::SizeMismatch { expected, actual } => write!(f, "Expected size {}x{}, but got {}x{}", expected.0, expected.1, actual.0, actual.1),
+            Self::Mismatch { diff, diff_path } => {
+                write!(f, "{} pixels differ (max channel difference {})", diff.mismatched, diff.max_difference)?;
+
+                if let Some((x, y, expected, actual)) = diff.first_mismatch {
+                    write!(f, ", first at [{x}, {y}]: expected {expected:?}, got {actual:?}")?;
+                }
+
+                write!(f, ", diff written to {}", diff_path.display())
+            },
+            Self::IOError(error) => write!(f, "IO error: {error}"),
+            Self::ImageError(error) => write!(f, "Image error: {error:?}"),
+            Self::SavingError(error) => write!(f, "Could not save: {error}"),
+        }
+    }
+}
+
+impl std::error::Error for SnapshotError {}
+
+/// Encode a surface in the format given by the extension of path, creating missing directories
+pub fn write_image(path: &Path, surface: &Surface) -> Result<(), SnapshotError> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(SnapshotError::IOError)?;
+    }
+
+    surface.save(path).map_err(SnapshotError::SavingError)
+}
+
+fn sibling(path: &Path, suffix: &str) -> PathBuf {
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    path.with_file_name(format!("{stem}.{suffix}.png"))
+}
+
+/// Compare a surface against the reference stored at path (png or fpia).
+/// On mismatch a `<name>.diff.png` and a `<name>.actual.png` are written next to the reference.
+/// If TEKENEN_BLESS is set the reference is rewritten instead.
+pub fn check_snapshot(path: impl AsRef<Path>, actual: &Surface, tolerance: u8) -> Result<(), SnapshotError> {
+    let path = path.as_ref();
+
+    if std::env::var_os(BLESS_ENV).is_some() {
+        return write_image(path, actual)
+    }
+
+    if !path.exists() {
+        return Err(SnapshotError::MissingReference(path.to_owned()))
+    }
+
+    let data = std::fs::read(path).map_err(SnapshotError::IOError)?;
+    let expected = Platform::parse_image(&data).map_err(SnapshotError::ImageError)?;
+
+    if expected.width() != actual.width() || expected.height() != actual.height() {
+        return Err(SnapshotError::SizeMismatch {
+            expected: (expected.width(), expected.height()),
+            actual: (actual.width(), actual.height()),
+        })
+    }
+
+    let diff = compare(&expected, actual, tolerance);
+
+    if diff.is_match() {
+        return Ok(())
+    }
+
+    let diff_path = sibling(path, "diff");
+    write_image(&diff_path, &diff.image)?;
+    write_image(&sibling(path, "actual"), actual)?;
+
+    Err(SnapshotError::Mismatch { diff, diff_path })
+}
+
+/// Like check_snapshot, but panics with a report on failure
+#[track_caller]
+pub fn assert_snapshot(path: impl AsRef<Path>, actual: &Surface, tolerance: u8) {
+    if let Err(error) = check_snapshot(path.as_ref(), actual, tolerance) {
+        panic!("Snapshot {} failed: {error}", path.as_ref().display())
+    }
+}
+
+/// Reference image with the given name in the snapshots directory of this crate
+#[cfg(test)]
+pub(crate) fn reference(name: &str) -> String {
+    format!("{}/snapshots/{name}.png", env!("CARGO_MANIFEST_DIR"))
+}
+
+/// Small red and blue image with a transparent corner, drawn by the image snapshots
+#[cfg(test)]
+pub(crate) fn checkerboard() -> Surface {
+    let mut surface = Surface::new(4, 4);
+
+    for y in 0..4 {
+        for x in 0..4 {
+            let color = if (x + y) % 2 == 0 { crate::colors::RED } else { crate::colors::BLUE };
+            surface.set_pixel(x, y, color);
+        }
+    }
+
+    surface.set_pixel(3, 3, [0, 0, 0, 0]);
+    surface
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::colors;
+
+    use super::{checkerboard, compare};
+
+    #[test]
+    fn compare_reports_differences() {
+        let a = checkerboard();
+        let mut b = checkerboard();
+        b.set_pixel(1, 1, [0xff, 0x00, 0x08, 0xff]);
+
+        let diff = compare(&a, &b, 8);
+        assert!(diff.is_match());
+        assert_eq!(diff.max_difference, 8);
+
+        let diff = compare(&a, &b, 4);
+        assert_eq!(diff.mismatched, 1);
+        assert_eq!(diff.first_mismatch, Some((1, 1, colors::RED, [0xff, 0x00, 0x08, 0xff])));
+    }
+}
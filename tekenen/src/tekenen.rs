@@ -1,6 +1,6 @@
 pub type Pixel = [u8; 4];
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Surface {
     pub pixels: Vec<Pixel>,
     width: usize,
@@ -46,31 +46,73 @@ impl Surface {
         let index = (y * self.width as i32 + x) as usize;
         self.pixels[index] = color;
     }
-}
 
-#[cfg(feature = "c64")]
-mod font {
-    mod font_c64;
-    pub use font_c64::*;
-}
+    /// Copy of the pixels inside rect, the parts outside of this surface are transparent
+    pub fn crop(&self, rect: Rect<i32>) -> Surface {
+        let mut surface = Surface::new(rect.size.x.max(0) as usize, rect.size.y.max(0) as usize);
+
+        for y in 0..surface.height() {
+            for x in 0..surface.width() {
+                let (xs, ys) = (rect.position.x + x, rect.position.y + y);
+
+                if xs >= 0 && ys >= 0 && xs < self.width() && ys < self.height() {
+                    surface.set_pixel(x, y, self.pixels[(ys * self.width() + xs) as usize]);
+                }
+            }
+        }
+
+        surface
+    }
+
+    /// Combine color with the pixel already present, coverage (0..=255) scales the alpha of color
+    pub fn blend_pixel(&mut self, x: i32, y: i32, color: Pixel, mode: CompositeMode, coverage: u8) {
+        if x < 0 || y < 0 || x >= self.width as i32 || y >= self.height as i32 {
+            return
+        }
 
-#[cfg(not(feature = "c64"))]
-mod font {
-    mod font_default;
-    pub use font_default::*;
+        let index = (y * self.width as i32 + x) as usize;
+        self.pixels[index] = mode.blend(color, self.pixels[index], coverage);
+    }
 }
 
 use std::{cell::{Cell, Ref, RefCell}, rc::Rc};
 
 use enum_dispatch::enum_dispatch;
-use font::*;
 
-use crate::{math::{Transform, Vec2, Zero}, platform::{Event, MouseButton}, shapes::{circle::Circle, line::Line, point::Point, rect::Rect, Intersect, Shape}};
+use crate::{atlas::Atlas, math::{Affine, Transform, Vec2}, platform::{Event, MouseButton}, shapes::{circle::Circle, composed_shape::ComposedShape, line::Line, point::Point, path::{Path, FLATTEN_TOLERANCE}, polygon::{FillRule, Polygon}, polyline::Polyline, rect::Rect, triangle::Triangle, Intersect, Shape}};
 
 
 pub mod colors;
 
-#[derive(Debug, Clone)]
+mod color;
+pub use color::{Color, ColorParseError, ColorSpace};
+
+mod composite;
+pub use composite::CompositeMode;
+
+pub(crate) mod raster;
+
+mod sampling;
+pub use sampling::Sampling;
+
+mod encode;
+pub use encode::{ImageFormat, ImageSavingError};
+
+mod font;
+pub use font::{Font, FontLoadingError};
+
+mod text;
+pub use text::{RichText, TextAlign, TextBaseline, TextLayout, TextMetrics, TextStyle};
+use text::{Decorations, ITALIC_SLANT};
+
+mod style;
+pub use style::{LineCap, LineJoin, Style};
+
+mod paint;
+pub use paint::{ColorStop, Gradient, GradientKind, Paint, Pattern, SpreadMode};
+use paint::Shader;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum OverflowBehavior {
     /// Draw everything
     Overflow,
@@ -81,7 +123,7 @@ pub enum OverflowBehavior {
     /// Draw only shapes fully inside the clip zone
     Skip,
 
-    /// Draw onyl shapes intersecting the clip zone
+    /// Draw only shapes intersecting the clip zone
     MaybeFasterIDK
 }
 
@@ -104,7 +146,69 @@ pub trait DrawableSurface {
     //     self.shape(Point::new(x, y))
     // }
 
-    fn fill_color(&self, color: Pixel);
+    /// Current fill, stroke and line settings
+    fn style(&self) -> Style;
+
+    fn set_style(&self, style: Style);
+
+    /// Color of the inside of shapes, text and one pixel lines
+    fn fill_color(&self, color: Pixel) {
+        self.fill_paint(color)
+    }
+
+    /// Gradient, pattern or color of the inside of shapes, text and one pixel lines.
+    /// Its coordinates are those of the shapes, views move it along with their transformation
+    fn fill_paint(&self, paint: impl Into<Paint>) {
+        self.set_style(Style { fill: Some(paint.into()), ..self.style() })
+    }
+
+    /// Do not fill shapes, only their outline is drawn
+    fn no_fill(&self) {
+        self.set_style(Style { fill: None, ..self.style() })
+    }
+
+    /// Color of the outline of shapes, lines use the stroke when it is set
+    fn stroke_color(&self, color: Pixel) {
+        self.set_style(Style { stroke: Some(color), ..self.style() })
+    }
+
+    /// Do not draw outlines, lines fall back to one pixel in the fill color
+    fn no_stroke(&self) {
+        self.set_style(Style { stroke: None, ..self.style() })
+    }
+
+    /// Width of outlines and lines in pixels, defaults to 1
+    fn stroke_width(&self, width: f32) {
+        self.set_style(Style { stroke_width: width, ..self.style() })
+    }
+
+    /// Ends of open strokes, defaults to Butt
+    fn line_cap(&self, cap: LineCap) {
+        self.set_style(Style { line_cap: cap, ..self.style() })
+    }
+
+    /// Corners of strokes, defaults to Miter
+    fn line_join(&self, join: LineJoin) {
+        self.set_style(Style { line_join: join, ..self.style() })
+    }
+
+    /// How drawn colors are combined with the surface, defaults to SourceOver
+    fn composite_mode(&self, mode: CompositeMode);
+
+    /// Smooth the edges of shapes and strokes, defaults to false
+    fn anti_alias(&self, enabled: bool);
+
+    /// Edges of shapes and strokes are smoothed, see anti_alias
+    fn is_anti_aliased(&self) -> bool;
+
+    /// How images are sampled when drawn scaled or transformed, defaults to Nearest
+    fn image_sampling(&self, sampling: Sampling);
+
+    /// Font used by text
+    fn font(&self) -> Font;
+
+    /// Font used by text, defaults to the builtin bitmap font
+    fn set_font(&self, font: Font);
 
     /// Line
     fn line(&self, x1: i32, y1: i32, x2: i32, y2: i32);
@@ -115,9 +219,100 @@ pub trait DrawableSurface {
     /// Circle
     fn circle(&self, x: i32, y: i32, radius: i32);
 
+    /// Ellipse, centered on x, y
+    fn ellipse(&self, x: i32, y: i32, radius_x: i32, radius_y: i32);
+
+    /// Triangle
+    fn triangle(&self, x1: i32, y1: i32, x2: i32, y2: i32, x3: i32, y3: i32) {
+        self.shape(Triangle::new(x1 as f32, y1 as f32, x2 as f32, y2 as f32, x3 as f32, y3 as f32))
+    }
+
+    /// Closed polygon, filled with the non-zero rule, use the Polygon shape for even-odd
+    fn polygon(&self, points: &[Vec2]) {
+        self.shape(Polygon::new(points.iter().map(|point| (*point).into()).collect()))
+    }
+
+    /// Open line through all the points, drawn like line
+    fn polyline(&self, points: &[Vec2]) {
+        self.shape(Polyline::new(points.iter().map(|point| (*point).into()).collect()))
+    }
+
+    /// Lines and curves, filled and stroked with the current style
+    fn path(&self, path: &Path) {
+        self.shape(path.clone())
+    }
+
     fn background(&self, color: Pixel);
 
-    fn text(&self, text: &str, x: i32, y: i32, height: i32) -> Vec2;
+    /// Text in the current font with its top left corner at x, y, lines only break at newlines.
+    /// Returns the size of the text
+    fn text(&self, text: &str, x: i32, y: i32, height: i32) -> Vec2 {
+        self.rich_text(&RichText::from(text), x, y, height)
+    }
+
+    /// Like text, every run is drawn in its own color and decorations. Runs without a color use the fill color
+    fn rich_text(&self, text: &RichText, x: i32, y: i32, height: i32) -> Vec2;
+
+    /// Text in font instead of the current font
+    fn text_with_font(&self, font: &Font, text: &str, x: i32, y: i32, height: i32) -> Vec2 {
+        let previous = self.font();
+
+        self.set_font(font.clone());
+        let size = self.text(text, x, y, height);
+        self.set_font(previous);
+
+        size
+    }
+
+    /// Advance width, ascent and descent of text in the current font
+    fn measure_text(&self, text: &str, height: i32) -> TextMetrics {
+        self.font().measure(text, height as f32)
+    }
+
+    /// Advance width, ascent and descent of rich text in the current font, styles do not change the size
+    fn measure_rich_text(&self, text: &RichText, height: i32) -> TextMetrics {
+        self.measure_text(text.as_str(), height)
+    }
+
+    /// Text aligned on x, y, wrapped and truncated as described by layout. Returns the size of the block of lines
+    fn text_layout(&self, text: &str, x: i32, y: i32, height: i32, layout: &TextLayout) -> Vec2 {
+        self.rich_text_layout(&RichText::from(text), x, y, height, layout)
+    }
+
+    /// Rich text aligned on x, y, wrapped and truncated as described by layout. Returns the size of the block of lines
+    fn rich_text_layout(&self, text: &RichText, x: i32, y: i32, height: i32, layout: &TextLayout) -> Vec2 {
+        let font = self.font();
+        let size = height as f32;
+
+        let lines = layout.rich_lines(&font, text, size);
+        let line_height = font.line_height(size);
+        let spacing = line_height * layout.line_spacing;
+        let block_height = spacing * (lines.len().max(1) - 1) as f32 + line_height;
+
+        let top = y as f32 - match layout.baseline {
+            TextBaseline::Top => 0.0,
+            TextBaseline::Middle => block_height / 2.0,
+            TextBaseline::Alphabetic => font.ascent(size),
+            TextBaseline::Bottom => block_height,
+        };
+
+        let mut width: f32 = 0.0;
+
+        for (i, line) in lines.iter().enumerate() {
+            let line_width = font.measure(line.as_str(), size).width;
+            width = width.max(line_width);
+
+            let left = x as f32 - match layout.align {
+                TextAlign::Left => 0.0,
+                TextAlign::Center => line_width / 2.0,
+                TextAlign::Right => line_width,
+            };
+
+            self.rich_text(line, left.round() as i32, (top + spacing * i as f32).round() as i32, height);
+        }
+
+        Vec2::new(width, block_height).ceil()
+    }
 
     fn draw_image(&self, x: i32, y: i32, image: &Surface) {
         self.draw_image_at(x, y, image.width(), image.height(), image)
@@ -130,7 +325,30 @@ pub trait DrawableSurface {
         self.draw_image_at(x, y, w, h, image)
     }
 
-    fn draw_image_at(&self, x: i32, y: i32, w: i32, h: i32, image: &Surface);
+    /// Stretch the image over w by h pixels, negative sizes mirror it
+    fn draw_image_at(&self, x: i32, y: i32, w: i32, h: i32, image: &Surface) {
+        self.draw_image_region(Rect::new(0, 0, image.width(), image.height()), x, y, w, h, image)
+    }
+
+    /// Draw only the source rect of the image, stretched over w by h pixels
+    fn draw_image_region(&self, source: Rect<i32>, x: i32, y: i32, w: i32, h: i32, image: &Surface);
+
+    /// Draw the image through matrix, which maps image pixels onto this surface
+    fn draw_image_transformed(&self, matrix: &Affine, image: &Surface);
+
+    /// Draw one frame of the atlas, unknown frames are skipped
+    fn draw_frame(&self, atlas: &Atlas, frame: usize, x: i32, y: i32) {
+        if let Some(source) = atlas.frame(frame) {
+            self.draw_image_region(source, x, y, source.size.x, source.size.y, atlas.surface())
+        }
+    }
+
+    /// Draw one frame of the atlas stretched over w by h pixels
+    fn draw_frame_at(&self, atlas: &Atlas, frame: usize, x: i32, y: i32, w: i32, h: i32) {
+        if let Some(source) = atlas.frame(frame) {
+            self.draw_image_region(source, x, y, w, h, atlas.surface())
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -138,7 +356,18 @@ pub struct SurfaceDrawer {
     /// The memory buffer holding the pixels
     pixels: Rc<RefCell<Surface>>,
 
-    fill_color: Cell<Pixel>,
+    style: RefCell<Style>,
+
+    composite_mode: Cell<CompositeMode>,
+
+    anti_alias: Cell<bool>,
+
+    sampling: Cell<Sampling>,
+
+    font: RefCell<Font>,
+
+    /// Pixels outside this rect are left untouched, set by views that hide their overflow
+    clip: Cell<Option<Rect<i32>>>,
 }
 
 #[derive(Debug)]
@@ -147,14 +376,19 @@ pub struct SurfaceView {
     surface: Rc<SurfaceDestination>,
 
     /// Screen coordinates
-    screen: Cell<Rect>,
+    screen: Cell<Rect<i32>>,
 
     /// World coordinates
-    translation: Cell<Vec2>,
-    scale: Cell<f32>,
+    transform: Cell<Affine>,
+
+    /// Saved transformations, see push and pop
+    transform_stack: RefCell<Vec<Affine>>,
 
     /// clip object outside the clip area
-    overflow_behavior: OverflowBehavior,
+    overflow_behavior: Cell<OverflowBehavior>,
+
+    /// Every view can choose independently to anti alias
+    anti_alias: Cell<bool>,
 
     /// used for panning and scaleing
     moving: Cell<bool>,
@@ -182,14 +416,262 @@ impl SurfaceDrawer {
     pub fn new(width: usize, height: usize) -> Self {
         Self {
             pixels: Rc::new(RefCell::new(Surface::new(width, height))),
-            fill_color: Cell::new(colors::WHITE),
+            style: RefCell::new(Style::default()),
+            composite_mode: Cell::new(CompositeMode::SourceOver),
+            anti_alias: Cell::new(false),
+            sampling: Cell::new(Sampling::Nearest),
+            font: RefCell::new(Font::builtin()),
+            clip: Cell::new(None),
         }
     }
 
     pub fn from_surface(surface: Surface) -> Self {
         Self {
             pixels: Rc::new(RefCell::new(surface)),
-            fill_color: Cell::new(colors::WHITE),
+            style: RefCell::new(Style::default()),
+            composite_mode: Cell::new(CompositeMode::SourceOver),
+            anti_alias: Cell::new(false),
+            sampling: Cell::new(Sampling::Nearest),
+            font: RefCell::new(Font::builtin()),
+            clip: Cell::new(None),
+        }
+    }
+
+    /// The pixels of the surface inside the clip, rasterizers never visit pixels outside
+    fn drawable(&self) -> Rect<i32> {
+        let surface = Rect::new(0, 0, self.width(), self.height());
+        self.clip.get().map_or(surface, |clip| shared_pixels(surface, clip))
+    }
+
+    /// Blend any color into the surface, pixels outside the clip are skipped
+    fn blender(&self) -> impl FnMut(i32, i32, Pixel, u8) + '_ {
+        let mut pixels = self.pixels.borrow_mut();
+        let mode = self.composite_mode.get();
+        let clip = self.clip.get();
+
+        move |x, y, color, coverage| {
+            if pixel_in_clip(clip, x, y) {
+                pixels.blend_pixel(x, y, color, mode, coverage)
+            }
+        }
+    }
+
+    /// Blend paint into the surface, with the coverage given by the rasterizer
+    fn plotter(&self, paint: &Paint) -> impl FnMut(i32, i32, u8) + '_ {
+        let shader = paint.shader(self.sampling.get());
+        let mut blend = self.blender();
+
+        move |x, y, coverage| blend(x, y, shader.color_at(x, y), coverage)
+    }
+
+    /// Stroke through the points with the caps and joins of the current style.
+    /// The pieces of the stroke are collected in a mask first, so overlaps are blended only once.
+    fn draw_stroke(&self, contours: &[(&[(f32, f32)], bool)], paint: &Paint, width: f32) {
+        let style = self.style();
+        let points = || contours.iter().flat_map(|(points, _)| points.iter());
+
+        // Miters stick out the furthest, at most half the width times the limit
+        let margin = width / 2.0 * raster::MITER_LIMIT + 2.0;
+        let x_min = points().map(|p| p.0).fold(f32::INFINITY, f32::min) - margin;
+        let y_min = points().map(|p| p.1).fold(f32::INFINITY, f32::min) - margin;
+        let x_max = points().map(|p| p.0).fold(f32::NEG_INFINITY, f32::max) + margin;
+        let y_max = points().map(|p| p.1).fold(f32::NEG_INFINITY, f32::max) + margin;
+
+        let drawable = self.drawable();
+        let mut mask = raster::Mask::new(
+            (x_min as i32).max(drawable.position.x),
+            (y_min as i32).max(drawable.position.y),
+            (x_max as i32).min(drawable.position.x + drawable.size.x),
+            (y_max as i32).min(drawable.position.y + drawable.size.y)
+        );
+
+        for (points, closed) in contours {
+            raster::stroke(
+                points,
+                *closed,
+                width,
+                style.line_cap,
+                style.line_join,
+                self.anti_alias.get(),
+                &mut mask
+            );
+        }
+
+        mask.for_each(self.plotter(paint));
+    }
+
+    /// Lines use the stroke when it is set, otherwise they are one pixel wide in the fill color
+    fn draw_line_stroke(&self, points: &[(f32, f32)]) {
+        let style = self.style();
+
+        match (style.stroke, &style.fill) {
+            (Some(stroke_color), _) => self.draw_stroke(&[(points, false)], &Paint::Solid(stroke_color), style.stroke_width),
+            (None, Some(fill)) => self.draw_stroke(&[(points, false)], fill, 1.0),
+            (None, None) => {},
+        }
+    }
+
+    /// Fill and stroke a closed polygon with the current style
+    pub fn draw_polygon(&self, points: &[Vec2<f32>], fill_rule: FillRule) {
+        let style = self.style();
+        let points: Vec<_> = points.iter().map(|point| point.tuple()).collect();
+
+        if let Some(fill) = &style.fill {
+            raster::polygon(std::slice::from_ref(&points), fill_rule, self.anti_alias.get(), self.drawable(), self.plotter(fill));
+        }
+
+        if let Some(stroke_color) = style.stroke {
+            self.draw_stroke(&[(&points, true)], &Paint::Solid(stroke_color), style.stroke_width);
+        }
+    }
+
+    /// Fill every contour of the path and stroke them, the curves are flattened in screen space
+    pub fn draw_path(&self, path: &Path) {
+        let style = self.style();
+
+        let contours: Vec<_> = path.flatten(FLATTEN_TOLERANCE).into_iter().map(|contour| {
+            let points: Vec<_> = contour.points.iter().map(|point| (point.x, point.y)).collect();
+            (points, contour.closed)
+        }).collect();
+
+        if let Some(fill) = &style.fill {
+            let points: Vec<_> = contours.iter().map(|(points, _)| points.clone()).collect();
+            raster::polygon(&points, path.fill_rule, self.anti_alias.get(), self.drawable(), self.plotter(fill));
+        }
+
+        if let Some(stroke_color) = style.stroke {
+            let contours: Vec<_> = contours.iter().map(|(points, closed)| (points.as_slice(), *closed)).collect();
+            self.draw_stroke(&contours, &Paint::Solid(stroke_color), style.stroke_width);
+        }
+    }
+
+    /// Open line through all the points, without a stroke it is one pixel wide in the fill color
+    pub fn draw_polyline(&self, points: &[Vec2<f32>]) {
+        let points: Vec<_> = points.iter().map(|point| point.tuple()).collect();
+        self.draw_line_stroke(&points);
+    }
+
+    /// Fill and stroke a rect with the current style. Whole coordinates fill exactly the pixels x..x + w,
+    /// fractions are covered proportionally when anti aliased.
+    pub fn draw_rect(&self, rect: &Rect) {
+        let style = self.style();
+        let Vec2 { x, y } = rect.position;
+        let Vec2 { x: w, y: h } = rect.size;
+
+        if let Some(fill) = &style.fill {
+            // Only the pixels inside the clip are visited, however large the rect
+            let clip = self.drawable();
+            let mut plot = self.plotter(fill);
+            let columns = |from: f32, to: f32| raster::clamp_range(from as i32, to as i32, clip.position.x, clip.size.x);
+            let rows = |from: f32, to: f32| raster::clamp_range(from as i32, to as i32, clip.position.y, clip.size.y);
+
+            // Pixel p spans p - 0.5 to p + 0.5, the rect spans x - 0.5 to x + w - 0.5
+            if self.anti_alias.get() {
+                let cover = |p: i32, from: f32, length: f32| {
+                    let p = p as f32;
+                    ((p + 0.5).min(from + length - 0.5) - (p - 0.5).max(from - 0.5)).clamp(0.0, 1.0)
+                };
+
+                for py in rows(y.floor(), (y + h).ceil()) {
+                    for px in columns(x.floor(), (x + w).ceil()) {
+                        let coverage = cover(px, x, w) * cover(py, y, h);
+
+                        if coverage > 0.0 {
+                            plot(px, py, (coverage * 255.0).round() as u8);
+                        }
+                    }
+                }
+            } else {
+                for px in columns((x - 0.5).ceil(), (x + w - 0.5).ceil() - 1.0) {
+                    for py in rows((y - 0.5).ceil(), (y + h - 0.5).ceil() - 1.0) {
+                        plot(px, py, 255);
+                    }
+                }
+            }
+        }
+
+        // The outline runs through the centers of the outer pixels
+        if let Some(stroke_color) = style.stroke.filter(|_| w > 0.0 && h > 0.0) {
+            let (left, top) = (x, y);
+            let (right, bottom) = ((x + w - 1.0).max(left), (y + h - 1.0).max(top));
+
+            let points = [(left, top), (right, top), (right, bottom), (left, bottom)];
+            self.draw_stroke(&[(&points, true)], &Paint::Solid(stroke_color), style.stroke_width);
+        }
+    }
+
+    /// Fill and stroke a circle with the current style
+    pub fn draw_circle(&self, center: Vec2<f32>, r: f32) {
+        let style = self.style();
+        let Vec2 { x: xc, y: yc } = center;
+
+        if let Some(fill) = &style.fill {
+            if self.anti_alias.get() {
+                raster::ellipse_anti_aliased(xc, yc, r, r, self.drawable(), self.plotter(fill));
+            } else {
+                let clip = self.drawable();
+                let mut plot = self.plotter(fill);
+
+                for x in raster::clamp_range((xc - r).ceil() as i32, ((xc + r).ceil() - 1.0) as i32, clip.position.x, clip.size.x) {
+                    for y in raster::clamp_range((yc - r).ceil() as i32, ((yc + r).ceil() - 1.0) as i32, clip.position.y, clip.size.y) {
+                        if (xc - x as f32).powi(2) + (yc - y as f32).powi(2) <= r * r {
+                            plot(x, y, 255);
+                        }
+                    }
+                }
+            }
+        }
+
+        if let Some(stroke_color) = style.stroke {
+            raster::ellipse_stroke((xc, yc), (r, r), style.stroke_width, self.anti_alias.get(), self.drawable(), self.plotter(&Paint::Solid(stroke_color)));
+        }
+    }
+
+    /// Coverage of shape inside region, found by filling it on a transparent scratch surface
+    fn coverage(&self, shape: &dyn Shape, region: Rect<i32>) -> Vec<u8> {
+        let scratch = SurfaceDrawer::new(region.size.x as usize, region.size.y as usize);
+        scratch.set_style(Style { fill: Some(Paint::Solid(colors::WHITE)), stroke: None, ..Style::default() });
+        scratch.anti_alias(self.anti_alias.get());
+
+        let mut shape = shape.dyn_clone();
+        shape.translate((-region.position).into());
+        scratch.dyn_shape(shape.as_mut());
+
+        let pixels = scratch.pixels.borrow();
+        pixels.as_slice().iter().map(|pixel| pixel[3]).collect()
+    }
+
+    /// Fill the combination of both parts, the coverage of each part is combined pixel by pixel
+    pub fn draw_composed(&self, shape: &ComposedShape) {
+        let Some(fill) = self.style().fill else {
+            return
+        };
+
+        // One pixel of margin for anti aliased edges, the bounding box includes its far edge
+        let bounds = shape.get_bounding_box();
+        let (start, end) = (bounds.position.floor(), (bounds.position + bounds.size).ceil());
+        let x_min = (start.x - 1).max(0);
+        let y_min = (start.y - 1).max(0);
+        let x_max = (end.x + 2).min(self.width());
+        let y_max = (end.y + 2).min(self.height());
+
+        if x_min >= x_max || y_min >= y_max {
+            return
+        }
+
+        let region = Rect::new(x_min, y_min, x_max - x_min, y_max - y_min);
+        let a = self.coverage(shape.shape_a.as_ref(), region);
+        let b = self.coverage(shape.shape_b.as_ref(), region);
+
+        let mut plot = self.plotter(&fill);
+
+        for (index, (a, b)) in a.into_iter().zip(b).enumerate() {
+            let coverage = shape.operation.combine(a, b);
+
+            if coverage > 0 {
+                let index = index as i32;
+                plot(x_min + index % region.size.x, y_min + index / region.size.x, coverage);
+            }
         }
     }
 }
@@ -212,8 +694,36 @@ impl SourcableSurface for SurfaceDrawer {
 }
 
 impl DrawableSurface for SurfaceDrawer {
-    fn fill_color(&self, color:Pixel) {
-        self.fill_color.set(color)
+    fn style(&self) -> Style {
+        self.style.borrow().clone()
+    }
+
+    fn set_style(&self, style: Style) {
+        *self.style.borrow_mut() = style
+    }
+
+    fn composite_mode(&self, mode: CompositeMode) {
+        self.composite_mode.set(mode)
+    }
+
+    fn anti_alias(&self, enabled: bool) {
+        self.anti_alias.set(enabled)
+    }
+
+    fn is_anti_aliased(&self) -> bool {
+        self.anti_alias.get()
+    }
+
+    fn image_sampling(&self, sampling: Sampling) {
+        self.sampling.set(sampling)
+    }
+
+    fn font(&self) -> Font {
+        self.font.borrow().clone()
+    }
+
+    fn set_font(&self, font: Font) {
+        *self.font.borrow_mut() = font
     }
 
     fn width(&self) -> i32 {
@@ -232,187 +742,246 @@ impl DrawableSurface for SurfaceDrawer {
         shape.draw_yourself(self)
     }
 
+    /// Replaces the pixels instead of blending, so a transparent color clears the surface
     fn background(&self, color: Pixel) {
         let mut pixels = self.pixels.borrow_mut();
-        
+        let clip = self.clip.get();
+
         for x in 0..pixels.width() {
             for y in 0..pixels.height() {
-                pixels.set_pixel(x, y, color);
+                if pixel_in_clip(clip, x, y) {
+                    pixels.set_pixel(x, y, color);
+                }
             }
         }
     }
 
-    fn text(&self, text: &str, x: i32, y: i32, height: i32) -> Vec2 {
-        let mut pixels = self.pixels.borrow_mut();
+    fn rich_text(&self, text: &RichText, x: i32, y: i32, height: i32) -> Vec2 {
+        let font = self.font();
+        let size = height as f32;
+        let fill = self.style().fill;
+        let sampling = self.sampling.get();
+        let line_height = font.line_height(size);
+        let ascent = font.ascent(size).round() as i32;
+        let decorations = Decorations::new(&font, size);
+        let bold = TextStyle::bold_offset(size) as i32;
+        let lines = text.lines();
+        let mut blend = self.blender();
+        let mut width = 0;
+
+        for (i, line) in lines.iter().enumerate() {
+            let top = y + (line_height * i as f32).round() as i32;
+            let runs = line.run_extents(&font, size);
+
+            // Runs without a color of their own use the fill
+            let shaders: Vec<_> = runs.iter()
+                .map(|(_, _, style)| style.color.map(Paint::Solid).or_else(|| fill.clone()).map(|paint| paint.shader(sampling)))
+                .collect();
+
+            for (left, right, style) in &runs {
+                if let Some(background) = style.background {
+                    fill_rect(&mut blend, x + *left as i32, top, x + *right as i32, top + line_height.round() as i32, &Shader::Solid(background));
+                }
+            }
 
-        let pos = Vec2::new(x, y);
-        let x = pos.x;
-        let y = pos.y;
+            let line_size = font.layout_indexed(line.as_str(), size, |index, pen_x, baseline, placed| {
+                let Some(run) = line.run_at(index) else {
+                    return
+                };
+
+                let (style, Some(shader)) = (runs[run].2, &shaders[run]) else {
+                    return
+                };
+
+                let Some(glyph) = font.glyph(placed, size) else {
+                    return
+                };
+
+                let left = x + pen_x + glyph.left;
+                let baseline = top + baseline;
+                let copies = if style.bold { bold } else { 0 };
+
+                // Bold copies are merged by taking the highest coverage, so they blend only once
+                for row in 0..glyph.height {
+                    let pixel_y = baseline + glyph.top + row;
+                    let slant = if style.italic { ((baseline - pixel_y) as f32 * ITALIC_SLANT).round() as i32 } else { 0 };
+
+                    for column in 0..glyph.width + copies {
+                        let coverage = (column - copies..=column)
+                            .filter(|source| (0..glyph.width).contains(source))
+                            .map(|source| glyph.coverage[(row * glyph.width + source) as usize])
+                            .max()
+                            .unwrap_or(0);
+
+                        if coverage > 0 {
+                            let pixel_x = left + column + slant;
+                            blend(pixel_x, pixel_y, shader.color_at(pixel_x, pixel_y), coverage);
+                        }
+                    }
+                }
+            });
 
-        const FONT_SCALE: i32 = 2;
-        const FONT_SIZE: i32 = 8 * FONT_SCALE;
+            width = width.max(line_size.x);
 
-        let mut curr_x = 0;
-        let mut curr_y = 0;
+            let baseline = top + ascent;
+            let thickness = decorations.thickness as i32;
 
-        for char in text.chars() {
-            if curr_x >= 800 || char == '\n' {
-                curr_x = 0;
-                curr_y += FONT_SIZE;
+            for ((left, right, style), shader) in runs.iter().zip(&shaders) {
+                let Some(shader) = shader else {
+                    continue
+                };
 
-                if char == '\n' {
-                    continue;
+                let (left, right) = (x + *left as i32, x + *right as i32);
+
+                if style.underline {
+                    let top = baseline + decorations.underline as i32;
+                    fill_rect(&mut blend, left, top, right, top + thickness, shader);
                 }
-            }
 
-            // skip whitespace
-            if char == ' ' {
-                curr_x += FONT_SIZE;
-                continue;
+                if style.strikethrough {
+                    let top = baseline + decorations.strikethrough as i32;
+                    fill_rect(&mut blend, left, top, right, top + thickness, shader);
+                }
             }
+        }
 
-            // get data by finding offset in charset
-            let data = FONT.get(char as usize - FIRST_CHAR as usize);
+        Vec2::new(width, (line_height * lines.len() as f32).round() as i32)
+    }
 
-            let data = if let Some(data) = data {
-                data
-            } else {
-                println!("Invalid char! {}", char);
-                &FONT['?' as usize]
-            };
+    fn line(&self, x1: i32, y1: i32, x2: i32, y2: i32) {
+        self.draw_line_stroke(&[(x1 as f32, y1 as f32), (x2 as f32, y2 as f32)])
+    }
 
-            for (yd, line) in data.iter().enumerate() {
-                let y = y + yd as i32 * FONT_SCALE + curr_y;
+    fn rect(&self, x: i32, y: i32, w: i32, h: i32) {
+        self.draw_rect(&Rect::new(x as f32, y as f32, w as f32, h as f32))
+    }
 
-                for (xd, symbol) in line.iter().enumerate() {
-                    let x = x + xd as i32 * FONT_SCALE + curr_x;
+    fn circle(&self, xc: i32, yc: i32, r: i32) {
+        self.draw_circle(Vec2::new(xc as f32, yc as f32), r as f32)
+    }
 
-                    if *symbol == ' ' {
-                        continue;
-                    }
+    fn ellipse(&self, xc: i32, yc: i32, rx: i32, ry: i32) {
+        let style = self.style();
 
-                    for xf in 0..FONT_SCALE {
-                        for yf in 0..FONT_SCALE {
-                            pixels.set_pixel(x + xf, y + yf, self.fill_color.get());
-                        }
-                    }
-                }
+        if let Some(fill) = &style.fill {
+            if self.anti_alias.get() {
+                raster::ellipse_anti_aliased(xc as f32, yc as f32, rx as f32, ry as f32, self.drawable(), self.plotter(fill))
+            } else {
+                raster::ellipse(xc, yc, rx, ry, self.drawable(), self.plotter(fill))
             }
-
-            // increment for next character
-            curr_x += FONT_SIZE;
         }
 
-        Vec2::new(curr_x, curr_y + FONT_SIZE)
+        if let Some(stroke_color) = style.stroke {
+            let (xc, yc, rx, ry) = (xc as f32, yc as f32, rx as f32, ry as f32);
+            raster::ellipse_stroke((xc, yc), (rx, ry), style.stroke_width, self.anti_alias.get(), self.drawable(), self.plotter(&Paint::Solid(stroke_color)));
+        }
     }
 
-    fn line(&self, mut x1: i32, mut y1: i32, mut x2: i32, mut y2: i32) {
-        let mut pixels = self.pixels.borrow_mut();
-        let fill_color = self.fill_color.get();
-
-        if x1 > x2 {
-            (x1, x2) = (x2, x1);
-            (y1, y2) = (y2, y1);
+    fn draw_image_region(&self, source: Rect<i32>, x: i32, y: i32, w: i32, h: i32, image: &Surface) {
+        if w == 0 || h == 0 || source.size.x <= 0 || source.size.y <= 0 {
+            return
         }
 
-        let dx = x2 - x1;
-        let dy = y2 - y1;
-
-        let ratio = dy as f32 / dx as f32;
+        let visible = shared_pixels(source, Rect::new(0, 0, image.width(), image.height()));
 
-        let mut y = y1;
-        let mut acc = 0.0;
-        for x in x1..=x2 {
-            pixels.set_pixel(x, y, fill_color);
-            acc += ratio;
+        // Pixel for pixel, nothing to sample
+        if w == source.size.x && h == source.size.y {
+            let mut blend = self.blender();
+            let (dx, dy) = (x - source.position.x, y - source.position.y);
 
-            while acc > 0.5 {
-                y += 1;
-                acc -= 1.0;
-                pixels.set_pixel(x, y, fill_color);
-
-                if y >= y2 {
-                    break
+            for ys in visible.position.y..visible.position.y + visible.size.y {
+                for xs in visible.position.x..visible.position.x + visible.size.x {
+                    blend(xs + dx, ys + dy, image.pixels[(ys * image.width() + xs) as usize], 255);
                 }
             }
 
-            while acc < 0.5 {
-                y -= 1;
-                acc += 1.0;
-                pixels.set_pixel(x, y, fill_color);
-
-                if y <= y2 {
-                    break
-                }
-            }
+            return
         }
-    }
 
-    fn rect(&self, x: i32, y: i32, w: i32, h: i32) {
-        let mut pixels = self.pixels.borrow_mut();
-        let fill_color = self.fill_color.get();
+        let scale = Affine::scale(w as f32 / source.size.x as f32, h as f32 / source.size.y as f32);
+        let origin = Affine::translation(-source.position.x as f32, -source.position.y as f32);
 
-        for x in x..(x + w) {
-            for y in y..(y + h) {
-                pixels.set_pixel(x, y, fill_color);
-            }
-        }
+        self.draw_image_mapped(&(Affine::translation(x as f32, y as f32) * scale * origin), visible, image)
     }
 
-    fn circle(&self, xc: i32, yc: i32, r: i32) {
-        let mut pixels = self.pixels.borrow_mut();
-        let fill_color = self.fill_color.get();
+    fn draw_image_transformed(&self, matrix: &Affine, image: &Surface) {
+        self.draw_image_mapped(matrix, Rect::new(0, 0, image.width(), image.height()), image)
+    }
+}
 
-        for x in (xc - r)..(xc + r) {
-            for y in (yc - r)..(yc + r) {
-                if (xc - x)*(xc - x) + (yc - y)*(yc - y) <= r * r {
-                    pixels.set_pixel(x, y, fill_color);
-                }
-            }
+impl SurfaceDrawer {
+    /// Draw the source rect of image through matrix, every covered pixel is sampled at its center
+    fn draw_image_mapped(&self, matrix: &Affine, source: Rect<i32>, image: &Surface) {
+        let Some(inverse) = matrix.inverse() else {
+            return
+        };
+
+        if source.size.x <= 0 || source.size.y <= 0 {
+            return
         }
-    }
 
-    fn draw_image(&self,x:i32,y:i32,image: &Surface) {
-        let mut destination = self.pixels.borrow_mut();
-        let source = image.as_slice();
+        let (sx, sy) = (source.position.x as f32, source.position.y as f32);
+        let (sw, sh) = (source.size.x as f32, source.size.y as f32);
+        let corners = [(sx, sy), (sx + sw, sy), (sx, sy + sh), (sx + sw, sy + sh)].map(|(x, y)| *matrix * Vec2::new(x, y));
+
+        let x_min = corners.iter().map(|c| c.x).fold(f32::INFINITY, f32::min).floor().max(0.0) as i32;
+        let y_min = corners.iter().map(|c| c.y).fold(f32::INFINITY, f32::min).floor().max(0.0) as i32;
+        let x_max = corners.iter().map(|c| c.x).fold(f32::NEG_INFINITY, f32::max).ceil().min(self.width() as f32) as i32;
+        let y_max = corners.iter().map(|c| c.y).fold(f32::NEG_INFINITY, f32::max).ceil().min(self.height() as f32) as i32;
+
+        // Extent of one destination pixel in the image, box sampling averages over it
+        let (across, down) = (inverse.transform_vector(Vec2::new(1.0, 0.0)), inverse.transform_vector(Vec2::new(0.0, 1.0)));
+        let footprint = Vec2::new(across.x.abs() + down.x.abs(), across.y.abs() + down.y.abs());
 
-        for xd in 0..image.width() {
-            for yd in 0..image.height() {
-                let from = source[(yd * image.width() + xd) as usize];
+        let sampling = self.sampling.get();
+        let mut blend = self.blender();
 
-                // TODO: Proper color mixing
-                if from[3] > 0 {
-                    destination.set_pixel(x + xd, y + yd, from)
+        for yd in y_min..y_max {
+            for xd in x_min..x_max {
+                let from = inverse * Vec2::new(xd as f32 + 0.5, yd as f32 + 0.5);
+
+                if from.x >= sx && from.y >= sy && from.x < sx + sw && from.y < sy + sh {
+                    blend(xd, yd, sampling.sample(image, source, from, footprint), 255);
                 }
             }
         }
     }
+}
 
-    fn draw_image_at(&self, x: i32, y: i32, w: i32, h: i32, image: &Surface) {
-        if w == image.width() && h == image.height() {
-            self.draw_image(x, y, image);
-            return
-        }
-
-        let mut destination = self.pixels.borrow_mut();
-        let source = image.as_slice();
+/// Pixel x, y lies within the rect, the far edges are excluded
+fn pixel_in_rect(rect: Rect<i32>, x: i32, y: i32) -> bool {
+    x >= rect.position.x && x < rect.position.x + rect.size.x &&
+    y >= rect.position.y && y < rect.position.y + rect.size.y
+}
 
-        for xd in x..(x + w) {
-            for yd in y..(y + h) {
-                let xs = (xd - x) * image.width() / w;
-                let ys = (yd - y) * image.height() / h;
+/// Without a clip every pixel is inside
+fn pixel_in_clip(clip: Option<Rect<i32>>, x: i32, y: i32) -> bool {
+    let Some(clip) = clip else {
+        return true
+    };
 
-                let from = source[(ys * image.width() + xs) as usize];
+    pixel_in_rect(clip, x, y)
+}
 
-                // TODO: Proper color mixing
-                if from[3] > 0 {
-                    destination.set_pixel(xd, yd, from)
-                }
-            }
+/// Blend the shader over the pixels from left, top up to right, bottom
+fn fill_rect(blend: &mut impl FnMut(i32, i32, Pixel, u8), left: i32, top: i32, right: i32, bottom: i32, shader: &Shader) {
+    for y in top..bottom {
+        for x in left..right {
+            blend(x, y, shader.color_at(x, y), 255)
         }
     }
 }
 
+/// Pixels covered by both rects, empty rects have zero size
+fn shared_pixels(a: Rect<i32>, b: Rect<i32>) -> Rect<i32> {
+    let x1 = a.position.x.max(b.position.x);
+    let y1 = a.position.y.max(b.position.y);
+    let x2 = (a.position.x + a.size.x).min(b.position.x + b.size.x);
+    let y2 = (a.position.y + a.size.y).min(b.position.y + b.size.y);
+
+    Rect::new(x1, y1, (x2 - x1).max(0), (y2 - y1).max(0))
+}
+
 impl SurfaceView {
     // TODO: better inits
     pub fn new(width: i32, height: i32, surface: SurfaceSource) -> Self {
@@ -424,9 +993,10 @@ impl SurfaceView {
         Self {
             surface: Rc::new(surface),
             screen: Cell::new(Rect::new(0, 0, width, height)),
-            translation: Cell::new(Vec2::zero()),
-            scale: Cell::new(1.0),
-            overflow_behavior: OverflowBehavior::Overflow,
+            transform: Cell::new(Affine::identity()),
+            transform_stack: RefCell::new(Vec::new()),
+            overflow_behavior: Cell::new(OverflowBehavior::Overflow),
+            anti_alias: Cell::new(false),
             moving: Cell::new(false),
         }
     }
@@ -435,9 +1005,10 @@ impl SurfaceView {
         Self {
             surface: self.surface.clone(),
             screen: Cell::new(Rect::new(0, 0, 800, 600)),
-            translation: Cell::new(Vec2::zero()),
-            scale: Cell::new(1.0),
-            overflow_behavior: OverflowBehavior::Overflow,
+            transform: Cell::new(Affine::identity()),
+            transform_stack: RefCell::new(Vec::new()),
+            overflow_behavior: Cell::new(OverflowBehavior::Overflow),
+            anti_alias: Cell::new(false),
             moving: Cell::new(false),
         }
     }
@@ -456,6 +1027,29 @@ impl SurfaceView {
 }
 
 impl SurfaceDestination {
+    /// Draw with only the pixels inside clip, given in the coordinates of this destination.
+    /// Nested clips only ever shrink the visible area.
+    fn with_clip(&self, mut clip: Rect<i32>, draw: impl FnOnce(&SurfaceDestination)) {
+        // Follow nested views down to the drawer, moving the clip into the coordinates of each
+        let mut destination = self;
+
+        let drawer = loop {
+            match destination {
+                SurfaceDestination::SurfaceDrawer(drawer) => break drawer,
+                SurfaceDestination::SurfaceView(view) => {
+                    clip = Rect::new_vec(view.world_point_to_screen(clip.position).round(), view.world_length_to_screen(clip.size).round());
+                    destination = &view.surface;
+                }
+            }
+        };
+
+        let previous = drawer.clip.get();
+
+        drawer.clip.set(Some(previous.map_or(clip, |previous| shared_pixels(previous, clip))));
+        draw(self);
+        drawer.clip.set(previous);
+    }
+
     fn get_surface(&self) -> Ref<Surface> {
         match self {
             SurfaceDestination::SurfaceDrawer(drawer) => drawer.pixels.borrow(),
@@ -468,7 +1062,7 @@ impl SurfaceView {
     pub fn handle_pan_and_zoom(&self, event: Event) -> bool {
         match event {
             Event::MouseDown{ x, y, key: MouseButton::Right } => {
-                if self.screen.get().encloses_point(&Point::new(x, y)) {
+                if Rect::from(self.screen.get()).encloses_point(&Point::from(Vec2::new(x, y))) {
                     self.moving.set(true);
                     true
                 } else {
@@ -499,83 +1093,302 @@ impl SurfaceView {
 
 impl SurfaceView {
     // Set clip
-    pub fn clip(&self, clip: Rect) {
+    pub fn clip(&self, clip: Rect<i32>) {
         self.screen.set(clip)
     }
 
+    /// What happens to drawing outside the clip, defaults to Overflow
+    pub fn set_overflow_behavior(&self, behavior: OverflowBehavior) {
+        self.overflow_behavior.set(behavior)
+    }
+
     pub fn reset_clip(&self) {
         self.screen.set(Rect::new(0, 0, self.width(), self.height()))
     }
 
-    // Set transformation
-    pub fn set_translation(&self, translation: Vec2) {
-        self.translation.set(translation);
+    // Set transformation, world coordinates can be fractions of a pixel
+    pub fn set_translation(&self, translation: impl Into<Vec2<f32>>) {
+        let translation = translation.into();
+        let mut transform = self.transform.get();
+        transform.matrix[0][2] = translation.x;
+        transform.matrix[1][2] = translation.y;
+        self.transform.set(transform);
     }
 
     pub fn set_scale(&self, scale: f32) {
-        self.scale.set(scale);
+        let Vec2 { x, y } = self.transform.get().get_translation();
+        self.transform.set(Affine::new(scale, 0.0, 0.0, scale, x, y));
     }
 
-    pub fn set_transformation(&self, translation: Vec2, scale: f32) {
-        self.translation.set(translation);
-        self.scale.set(scale);
+    pub fn set_transformation(&self, translation: impl Into<Vec2<f32>>, scale: f32) {
+        let Vec2 { x, y } = translation.into();
+        self.transform.set(Affine::new(scale, 0.0, 0.0, scale, x, y));
     }
 
     pub fn reset_transformation(&self) {
-        self.translation.set(Vec2::zero());
-        self.scale.set(1.0);
+        self.transform.set(Affine::identity());
+    }
+
+    /// Replace the whole transformation from world to view coordinates
+    pub fn set_matrix(&self, matrix: Affine) {
+        self.transform.set(matrix);
+    }
+
+    pub fn get_matrix(&self) -> Affine {
+        self.transform.get()
+    }
+
+    /// Save the transformation, until the matching pop
+    pub fn push(&self) {
+        self.transform_stack.borrow_mut().push(self.transform.get());
+    }
+
+    /// Restore the transformation saved by the last push, does nothing if there is none
+    pub fn pop(&self) {
+        if let Some(transform) = self.transform_stack.borrow_mut().pop() {
+            self.transform.set(transform);
+        }
     }
 
     // Change transformation
-    pub fn translate_screen(&self, translation: Vec2) {
-        self.translation.set(self.translation.get() + translation);
+    pub fn translate_screen(&self, translation: impl Into<Vec2<f32>>) {
+        let Vec2 { x, y } = translation.into();
+        self.transform.set(Affine::translation(x, y) * self.transform.get());
     }
 
-    pub fn translate(&self, translation: Vec2) {
-        self.translation.set(self.translation.get() + translation * self.scale.get());
+    pub fn translate(&self, translation: impl Into<Vec2<f32>>) {
+        let Vec2 { x, y } = translation.into();
+        self.apply_matrix(Affine::translation(x, y));
     }
 
-    pub fn scale_screen(&self, scale: f32, mut from: Vec2) {
-        from -= self.screen.get().position;
-        self.translation.set(from + (self.translation.get() - from) * scale);
-        self.scale.set(self.scale.get() * scale);
+    pub fn scale_screen(&self, scale: f32, from: impl Into<Vec2<f32>>) {
+        let Vec2 { x, y } = from.into() - self.screen.get().position.into();
+
+        let zoom = Affine::translation(x, y) * Affine::scale(scale, scale) * Affine::translation(-x, -y);
+        self.transform.set(zoom * self.transform.get());
     }
 
     pub fn scale(&self, scale: f32) {
-        self.scale.set(self.scale.get() * scale);
+        self.apply_matrix(Affine::scale(scale, scale));
+    }
+
+    pub fn scale_xy(&self, x: f32, y: f32) {
+        self.apply_matrix(Affine::scale(x, y));
+    }
+
+    /// Rotate the world clockwise around its origin, angle in radians
+    pub fn rotate(&self, angle: f32) {
+        self.apply_matrix(Affine::rotation(angle));
+    }
+
+    /// Shear the world along x and y, angles in radians
+    pub fn skew(&self, x: f32, y: f32) {
+        self.apply_matrix(Affine::skew(x, y));
     }
 
-    // Apply transformation
+    /// Transform the world by matrix, before the current transformation
+    pub fn apply_matrix(&self, matrix: Affine) {
+        self.transform.set(self.transform.get() * matrix);
+    }
+
+    /// From world to screen, including the position of the view
+    fn screen_matrix(&self) -> Affine {
+        let Vec2 { x, y } = self.screen.get().position;
+        Affine::translation(x as f32, y as f32) * self.transform.get()
+    }
+
+    /// Shapes can be moved and scaled with Transform only while the scale is the same in both directions
+    fn is_uniform(&self) -> bool {
+        self.transform.get().get_axis_scale().is_some_and(|scale| scale.x == scale.y)
+    }
+
+    // Apply transformation, only translation and scale can be applied to a Transform
     pub fn world_to_screen(&self, target: &mut dyn Transform) {
-        target.scale(self.scale.get());
-        target.translate(self.translation.get() + self.screen.get().position);
+        target.scale(self.transform.get().get_scale_factor());
+        target.translate(self.transform.get().get_translation() + self.screen.get().position.into());
     }
 
     pub fn screen_to_world(&self, target: &mut dyn Transform) {
-        target.translate(-(self.translation.get() + self.screen.get().position));
-        target.scale(1.0 / self.scale.get());
+        target.translate(-(self.transform.get().get_translation() + self.screen.get().position.into()));
+        target.scale(1.0 / self.transform.get().get_scale_factor());
     }
 
-    pub fn world_point_to_screen(&self, point: Vec2) -> Vec2 {
-        point * self.scale.get() + self.translation.get() + self.screen.get().position
+    pub fn world_point_to_screen(&self, point: impl Into<Vec2<f32>>) -> Vec2<f32> {
+        self.screen_matrix() * point.into()
     }
 
-    pub fn screen_point_to_world(&self, point: Vec2) -> Vec2 {
-        (point - self.translation.get() - self.screen.get().position) / self.scale.get()
+    pub fn screen_point_to_world(&self, point: impl Into<Vec2<f32>>) -> Vec2<f32> {
+        let point = point.into();
+
+        match self.screen_matrix().inverse() {
+            Some(inverse) => inverse * point,
+            None => point,
+        }
     }
 
-    pub fn world_length_to_screen(&self, length: Vec2) -> Vec2 {
-        length * self.scale.get()
+    /// Length along each axis, how long the axes become after rotation or skew
+    pub fn world_length_to_screen(&self, length: impl Into<Vec2<f32>>) -> Vec2<f32> {
+        let [[a, c, _], [b, d, _]] = self.transform.get().matrix;
+        length.into() * Vec2::new(a.hypot(b), c.hypot(d))
     }
 
     pub fn screen_length_to_world(&self, length: f32) -> f32 {
-        length / self.scale.get()
+        length / self.transform.get().get_scale_factor()
+    }
+
+    /// Draw on the underlying surface with the settings of this view,
+    /// the stroke width, fill paint and anti aliasing only apply for the duration of draw
+    fn on_surface(&self, draw: impl FnOnce(&SurfaceDestination)) {
+        let style = self.surface.style();
+        let anti_alias = self.surface.is_anti_aliased();
+
+        self.surface.set_style(Style {
+            stroke_width: style.stroke_width * self.transform.get().get_scale_factor(),
+            fill: style.fill.as_ref().map(|paint| paint.transformed(&self.screen_matrix())),
+            ..style.clone()
+        });
+        self.surface.anti_alias(self.anti_alias.get());
+
+        draw(&self.surface);
+
+        self.surface.set_style(style);
+        self.surface.anti_alias(anti_alias);
+    }
+}
+
+impl SurfaceView {
+    /// Skip and MaybeFasterIDK decide on the whole drawing, bounds are in screen coordinates
+    fn should_draw(&self, bounds: &dyn Intersect) -> bool {
+        match self.overflow_behavior.get() {
+            OverflowBehavior::Overflow | OverflowBehavior::Hidden => true,
+            OverflowBehavior::Skip => Rect::from(self.screen.get()).encloses(bounds),
+            OverflowBehavior::MaybeFasterIDK => Rect::from(self.screen.get()).intersect(bounds),
+        }
+    }
+
+    /// Like on_surface, Hidden scissors away every pixel outside the clip
+    fn on_clipped_surface(&self, draw: impl FnOnce(&SurfaceDestination)) {
+        match self.overflow_behavior.get() {
+            OverflowBehavior::Hidden => self.on_surface(|surface| surface.with_clip(self.screen.get(), draw)),
+            _ => self.on_surface(draw),
+        }
+    }
+
+    /// Shape is already in screen coordinates
+    fn draw_screen_shape(&self, shape: &mut dyn Shape) {
+        if self.should_draw(shape.intersect_upcast()) {
+            self.on_clipped_surface(|surface| surface.dyn_shape(shape));
+        }
+    }
+
+    /// Glyph outlines become paths in world coordinates, which the transform then maps to the screen
+    fn rich_text_transformed(&self, text: &RichText, x: i32, y: i32, height: i32) -> Vec2 {
+        let font = self.font();
+        let size = height as f32;
+        let line_height = font.line_height(size);
+        let ascent = font.ascent(size).round();
+        let decorations = Decorations::new(&font, size);
+        let bold = TextStyle::bold_offset(size);
+        let lines = text.lines();
+        let style = self.style();
+        let mut width = 0;
+
+        // Text is only filled, like on a SurfaceDrawer
+        let fill = |paint| self.set_style(Style { fill: Some(paint), stroke: None, ..style.clone() });
+
+        for (i, line) in lines.iter().enumerate() {
+            let top = y as f32 + (line_height * i as f32).round();
+            let baseline = top + ascent;
+            let runs = line.run_extents(&font, size);
+            let mut outlines = vec![Path::new(); runs.len()];
+
+            let line_size = font.layout_indexed(line.as_str(), size, |index, pen_x, _, placed| {
+                let Some(run) = line.run_at(index) else {
+                    return
+                };
+
+                let style = line.run_ranges()[run].1;
+                let pen_x = (x + pen_x) as f32;
+
+                let mut outline = font.outline(placed, size, pen_x, baseline, Path::new());
+
+                if style.bold {
+                    outline = font.outline(placed, size, pen_x + bold, baseline, outline);
+                }
+
+                if style.italic {
+                    outline.transform(&Affine::new(1.0, 0.0, -ITALIC_SLANT, 1.0, ITALIC_SLANT * baseline, 0.0));
+                }
+
+                outlines[run].commands.extend(outline.commands);
+            });
+
+            width = width.max(line_size.x);
+
+            for (left, right, run_style) in &runs {
+                if let Some(background) = run_style.background {
+                    fill(Paint::Solid(background));
+                    self.shape(Rect::new(x as f32 + left, top, right - left, line_height.round()));
+                }
+            }
+
+            for ((left, right, run_style), outlines) in runs.iter().zip(outlines) {
+                let Some(paint) = run_style.color.map(Paint::Solid).or_else(|| style.fill.clone()) else {
+                    continue
+                };
+
+                fill(paint);
+                self.shape(outlines);
+
+                let left = x as f32 + left;
+
+                if run_style.underline {
+                    self.shape(Rect::new(left, baseline + decorations.underline, right - left, decorations.thickness));
+                }
+
+                if run_style.strikethrough {
+                    self.shape(Rect::new(left, baseline + decorations.strikethrough, right - left, decorations.thickness));
+                }
+            }
+        }
+
+        self.set_style(style);
+
+        Vec2::new(width, (line_height * lines.len() as f32).round() as i32)
     }
 }
 
 impl DrawableSurface for SurfaceView {
-    fn fill_color(&self, color: Pixel) {
-        self.surface.fill_color(color)
+    fn style(&self) -> Style {
+        self.surface.style()
+    }
+
+    fn set_style(&self, style: Style) {
+        self.surface.set_style(style)
+    }
+
+    fn composite_mode(&self, mode: CompositeMode) {
+        self.surface.composite_mode(mode)
+    }
+
+    fn anti_alias(&self, enabled: bool) {
+        self.anti_alias.set(enabled)
+    }
+
+    fn is_anti_aliased(&self) -> bool {
+        self.anti_alias.get()
+    }
+
+    fn image_sampling(&self, sampling: Sampling) {
+        self.surface.image_sampling(sampling)
+    }
+
+    fn font(&self) -> Font {
+        self.surface.font()
+    }
+
+    fn set_font(&self, font: Font) {
+        self.surface.set_font(font)
     }
 
     fn width(&self) -> i32 {
@@ -587,68 +1400,182 @@ impl DrawableSurface for SurfaceView {
     }
 
     fn background(&self, color: Pixel) {
-        self.fill_color(color);
-        self.surface.shape(self.screen.get());
+        let style = self.style();
+
+        self.set_style(Style { fill: Some(Paint::Solid(color)), stroke: None, ..style.clone() });
+        self.on_clipped_surface(|surface| surface.shape(Rect::from(self.screen.get())));
+        self.set_style(style);
     }
 
     fn line(&self, x1: i32, y1: i32, x2: i32, y2: i32) {
-        self.shape(Line::new(x1, y1, x2, y2))
+        self.shape(Line::new(x1 as f32, y1 as f32, x2 as f32, y2 as f32))
     }
 
     fn rect(&self, x: i32, y: i32, w: i32, h: i32) {
-        self.shape(Rect::new(x, y, w, h))
+        self.shape(Rect::new(x as f32, y as f32, w as f32, h as f32))
     }
 
     fn circle(&self, x: i32, y: i32, radius: i32) {
-        self.shape(Circle::new(x, y, radius))
+        self.shape(Circle::new(x as f32, y as f32, radius as f32))
     }
 
-    fn text(&self, text: &str, x: i32, y: i32, height: i32) -> Vec2 {
-        let pos = self.world_point_to_screen(Vec2::new(x, y));
+    fn ellipse(&self, x: i32, y: i32, radius_x: i32, radius_y: i32) {
+        if !self.is_uniform() {
+            return self.shape(Path::new().ellipse(x as f32, y as f32, radius_x as f32, radius_y as f32))
+        }
 
-        // TODO: could probably be done better, very low priority
-        let height = self.world_length_to_screen(Vec2::new(0, height));
+        let pos = self.world_point_to_screen(Vec2::new(x, y)).round();
+        let radius = self.world_length_to_screen(Vec2::new(radius_x, radius_y)).round();
 
-        self.surface.text(text, pos.x, pos.y, height.y)
+        let bounds = Rect::new_vec(pos - radius, radius * 2.0);
+
+        if self.should_draw(&Rect::from(bounds)) {
+            self.on_clipped_surface(|surface| surface.ellipse(pos.x, pos.y, radius.x, radius.y))
+        }
     }
 
-    fn draw_image_at(&self, x: i32, y: i32, w: i32, h: i32, image: &Surface) {
-        let pos = self.world_point_to_screen(Vec2::new(x, y));
-        let size = self.world_length_to_screen(Vec2::new(w, h));
+    fn rich_text(&self, text: &RichText, x: i32, y: i32, height: i32) -> Vec2 {
+        if !self.is_uniform() {
+            return self.rich_text_transformed(text, x, y, height)
+        }
+
+        let pos = self.world_point_to_screen(Vec2::new(x, y)).round();
+        let screen_height = self.world_length_to_screen(Vec2::new(0, height)).round().y;
+
+        let font = self.font();
+        let screen_size = font.layout(text.as_str(), screen_height as f32, |_, _, _| {});
 
-        self.surface.draw_image_at(pos.x, pos.y, size.x, size.y, image)
+        if self.should_draw(&Rect::from(Rect::new_vec(pos, screen_size))) {
+            self.on_clipped_surface(|surface| {
+                surface.rich_text(text, pos.x, pos.y, screen_height);
+            });
+        }
+
+        font.layout(text.as_str(), height as f32, |_, _, _| {})
+    }
+
+    fn draw_image_region(&self, source: Rect<i32>, x: i32, y: i32, w: i32, h: i32, image: &Surface) {
+        if !self.is_uniform() {
+            let scale = Affine::scale(w as f32 / source.size.x as f32, h as f32 / source.size.y as f32);
+            return self.draw_image_transformed(&(Affine::translation(x as f32, y as f32) * scale), &image.crop(source))
+        }
+
+        let pos = self.world_point_to_screen(Vec2::new(x, y)).round();
+        let size = self.world_length_to_screen(Vec2::new(w, h)).round();
+
+        // Mirrored images extend to the left or top of pos
+        let corner = Vec2::new(pos.x.min(pos.x + size.x), pos.y.min(pos.y + size.y));
+        let bounds = Rect::new_vec(corner, Vec2::new(size.x.abs(), size.y.abs()));
+
+        if self.should_draw(&Rect::from(bounds)) {
+            self.on_clipped_surface(|surface| surface.draw_image_region(source, pos.x, pos.y, size.x, size.y, image))
+        }
+    }
+
+    fn draw_image_transformed(&self, matrix: &Affine, image: &Surface) {
+        let matrix = self.screen_matrix() * *matrix;
+        let bounds = Path::new().rect(0.0, 0.0, image.width() as f32, image.height() as f32).transformed(&matrix);
+
+        if self.should_draw(bounds.intersect_upcast()) {
+            self.on_clipped_surface(|surface| surface.draw_image_transformed(&matrix, image))
+        }
     }
 
     fn dyn_shape(&self, shape: &mut dyn Shape) {
-        self.world_to_screen( shape);
+        if !self.is_uniform() {
+            return self.draw_screen_shape(shape.transformed(&self.screen_matrix()).as_mut())
+        }
 
-        self.surface.dyn_shape(shape);
+        self.world_to_screen(shape);
+        self.draw_screen_shape(shape);
     }
 
     fn shape(&self, mut shape: impl Shape) {
-        self.world_to_screen(&mut shape);
-
-        self.surface.shape(shape);
-        // match self.overflow_behavior {
-        //     OverflowBehavior::Overflow => {
-        //         self.shape_impl(shape)
-        //     },
-        //     OverflowBehavior::Skip => {
-        //         if self.clip.encloses(shape.intersect_upcast()) {
-        //             self.shape_impl(shape)
-        //         }
-        //     },
-        //     OverflowBehavior::Hidden => {
-        //         let shape = shape.join_and(&self.clip);
-
-        //         self.shape_impl(shape)
-
-        //     },
-        //     OverflowBehavior::MaybeFasterIDK => {
-        //         if self.clip.intersect(shape.intersect_upcast()) {
-        //             self.shape_impl(shape)
-        //         }
-        //     }
-        // }
-    }
-}
\ No newline at end of file
+        self.dyn_shape(&mut shape)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{colors, DrawableSurface, Surface, SurfaceView};
+
+    #[test]
+    fn views_keep_their_settings_to_themselves() {
+        let view = SurfaceView::new(8, 8, Surface::new(8, 8).into());
+        view.anti_alias(true);
+        view.fill_color(colors::RED);
+        view.circle(4, 4, 2);
+
+        // The drawer is shared with other views, they should not inherit the flag
+        assert!(view.is_anti_aliased());
+        assert!(!view.surface.is_anti_aliased());
+        assert!(!view.tee().is_anti_aliased());
+    }
+}
+
+#[cfg(all(test, feature = "image"))]
+mod snapshots {
+    use crate::{colors, math::Vec2, shapes::rect::Rect, DrawableSurface, OverflowBehavior};
+    use crate::snapshot::{assert_snapshot, checkerboard, reference, render};
+
+    #[test]
+    fn overflow() {
+        let behaviors = [OverflowBehavior::Overflow, OverflowBehavior::Hidden, OverflowBehavior::Skip, OverflowBehavior::MaybeFasterIDK];
+
+        let surface = render(128, 40, |ctx| {
+            ctx.background(colors::BLACK);
+
+            for (column, behavior) in behaviors.into_iter().enumerate() {
+                let view = ctx.tee();
+                view.clip(Rect::new(column as i32 * 32 + 6, 6, 20, 28));
+                view.set_overflow_behavior(behavior);
+                view.background(colors::GRAY);
+
+                // Crossing the edge, fully inside and fully outside the clip
+                view.fill_color(colors::WHITE);
+                view.stroke_color(colors::RED);
+                view.circle(2, 4, 6);
+                view.rect(12, 14, 4, 4);
+                view.rect(24, 24, 4, 4);
+                view.text("A", 8, 20, 16);
+                view.draw_image(-2, 22, &checkerboard());
+            }
+        });
+
+        assert_snapshot(reference("overflow"), &surface, 0);
+    }
+
+    #[test]
+    fn transform() {
+        let surface = render(128, 48, |ctx| {
+            ctx.background(colors::BLACK);
+            ctx.fill_color(colors::WHITE);
+            ctx.stroke_color(colors::RED);
+
+            // Rotated around the center of each drawing
+            ctx.push();
+            ctx.translate(Vec2::new(24, 24));
+            ctx.rotate(std::f32::consts::FRAC_PI_6);
+            ctx.rect(-12, -8, 24, 16);
+            ctx.pop();
+
+            ctx.push();
+            ctx.translate(Vec2::new(64, 24));
+            ctx.scale_xy(2.0, 1.0);
+            ctx.rotate(std::f32::consts::FRAC_PI_4);
+            ctx.draw_image_at(-8, -8, 16, 16, &checkerboard());
+            ctx.pop();
+
+            ctx.push();
+            ctx.translate(Vec2::new(92, 16));
+            ctx.skew(-0.3, 0.0);
+            ctx.text("Hi", 0, 0, 16);
+            ctx.pop();
+
+            // Back to the identity after the last pop
+            ctx.ellipse(100, 40, 4, 4);
+        });
+
+        assert_snapshot(reference("transform"), &surface, 0);
+    }
+}
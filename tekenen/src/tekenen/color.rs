@@ -0,0 +1,485 @@
+use std::{fmt, str::FromStr};
+
+use super::{colors, Pixel};
+
+/// Spaces colors can be converted to and mixed in, channels are in the order of the name
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ColorSpace {
+    /// Red, green and blue as stored in a Pixel, from 0 to 1
+    #[default]
+    Srgb,
+
+    /// sRGB without the gamma curve, proportional to the amount of light
+    LinearSrgb,
+
+    /// Hue in degrees, saturation and lightness from 0 to 1
+    Hsl,
+
+    /// Hue in degrees, saturation and value from 0 to 1
+    Hsv,
+
+    /// Perceptual lightness from 0 to 1, a and b around 0 for green-red and blue-yellow
+    Oklab,
+}
+
+impl ColorSpace {
+    /// The first channel is an angle, which is mixed along the shortest way around the circle
+    fn has_hue(self) -> bool {
+        matches!(self, Self::Hsl | Self::Hsv)
+    }
+}
+
+/// Color in sRGB with straight alpha, every channel from 0 to 1.
+/// Conversions from other spaces can give channels outside of that range, they are clamped in `to_pixel`
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Color {
+    pub r: f32,
+    pub g: f32,
+    pub b: f32,
+    pub a: f32,
+}
+
+impl Color {
+    pub const fn new(r: f32, g: f32, b: f32, a: f32) -> Self {
+        Self { r, g, b, a }
+    }
+
+    /// Opaque color from 8 bit channels
+    pub fn rgb(r: u8, g: u8, b: u8) -> Self {
+        Self::rgba(r, g, b, 255)
+    }
+
+    pub fn rgba(r: u8, g: u8, b: u8, a: u8) -> Self {
+        Self::from_pixel([r, g, b, a])
+    }
+
+    pub fn from_pixel(pixel: Pixel) -> Self {
+        let [r, g, b, a] = pixel.map(|channel| channel as f32 / 255.0);
+        Self { r, g, b, a }
+    }
+
+    /// Rounded to 8 bits, channels outside of 0 to 1 are clamped
+    pub fn to_pixel(self) -> Pixel {
+        [self.r, self.g, self.b, self.a].map(|channel| (channel.clamp(0.0, 1.0) * 255.0).round() as u8)
+    }
+
+    pub fn with_alpha(self, a: f32) -> Self {
+        Self { a, ..self }
+    }
+
+    /// Color from the channels of space, followed by alpha
+    pub fn from_space(space: ColorSpace, [x, y, z, a]: [f32; 4]) -> Self {
+        let [r, g, b] = match space {
+            ColorSpace::Srgb => [x, y, z],
+            ColorSpace::LinearSrgb => [x, y, z].map(linear_to_srgb),
+            ColorSpace::Hsl => hsl_to_srgb(x, y, z),
+            ColorSpace::Hsv => hsv_to_srgb(x, y, z),
+            ColorSpace::Oklab => oklab_to_linear(x, y, z).map(linear_to_srgb),
+        };
+
+        Self { r, g, b, a }
+    }
+
+    /// Channels in space, followed by alpha. Gray has a hue of 0
+    pub fn to_space(self, space: ColorSpace) -> [f32; 4] {
+        let Self { r, g, b, a } = self;
+
+        let [x, y, z] = match space {
+            ColorSpace::Srgb => [r, g, b],
+            ColorSpace::LinearSrgb => [r, g, b].map(srgb_to_linear),
+            ColorSpace::Hsl => srgb_to_hsl(r, g, b),
+            ColorSpace::Hsv => srgb_to_hsv(r, g, b),
+            ColorSpace::Oklab => linear_to_oklab([r, g, b].map(srgb_to_linear)),
+        };
+
+        [x, y, z, a]
+    }
+
+    pub fn from_hsl(hue: f32, saturation: f32, lightness: f32, a: f32) -> Self {
+        Self::from_space(ColorSpace::Hsl, [hue, saturation, lightness, a])
+    }
+
+    pub fn from_hsv(hue: f32, saturation: f32, value: f32, a: f32) -> Self {
+        Self::from_space(ColorSpace::Hsv, [hue, saturation, value, a])
+    }
+
+    pub fn from_oklab(l: f32, a: f32, b: f32, alpha: f32) -> Self {
+        Self::from_space(ColorSpace::Oklab, [l, a, b, alpha])
+    }
+
+    /// Color channels multiplied by alpha, followed by alpha
+    pub fn premultiplied(self) -> [f32; 4] {
+        [self.r * self.a, self.g * self.a, self.b * self.a, self.a]
+    }
+
+    /// Undo `premultiplied`, fully transparent colors become transparent black
+    pub fn from_premultiplied([r, g, b, a]: [f32; 4]) -> Self {
+        if a == 0.0 {
+            return Self::default()
+        }
+
+        Self { r: r / a, g: g / a, b: b / a, a }
+    }
+
+    /// Color t of the way from self to other, mixed in space with premultiplied alpha like CSS color-mix
+    pub fn mix(self, other: Color, t: f32, space: ColorSpace) -> Color {
+        let from = self.to_space(space);
+        let to = other.to_space(space);
+        let a = from[3] + (to[3] - from[3]) * t;
+
+        let mut mixed = [0.0; 4];
+
+        for channel in 0..3 {
+            if channel == 0 && space.has_hue() {
+                // Shortest way around the circle
+                let delta = (to[0] - from[0] + 540.0).rem_euclid(360.0) - 180.0;
+                mixed[0] = (from[0] + delta * t).rem_euclid(360.0);
+                continue;
+            }
+
+            let (from, to) = (from[channel] * from[3], to[channel] * to[3]);
+            mixed[channel] = if a == 0.0 { 0.0 } else { (from + (to - from) * t) / a };
+        }
+
+        mixed[3] = a;
+        Color::from_space(space, mixed)
+    }
+
+    /// Raise the OKLab lightness by amount, from 0 to 1
+    pub fn lighten(self, amount: f32) -> Color {
+        let [l, a, b, alpha] = self.to_space(ColorSpace::Oklab);
+        Color::from_oklab((l + amount).clamp(0.0, 1.0), a, b, alpha)
+    }
+
+    /// Lower the OKLab lightness by amount, from 0 to 1
+    pub fn darken(self, amount: f32) -> Color {
+        self.lighten(-amount)
+    }
+}
+
+impl From<Pixel> for Color {
+    fn from(pixel: Pixel) -> Self {
+        Self::from_pixel(pixel)
+    }
+}
+
+impl From<Color> for Pixel {
+    fn from(color: Color) -> Self {
+        color.to_pixel()
+    }
+}
+
+fn srgb_to_linear(channel: f32) -> f32 {
+    let magnitude = channel.abs();
+
+    let linear = if magnitude <= 0.04045 {
+        magnitude / 12.92
+    } else {
+        ((magnitude + 0.055) / 1.055).powf(2.4)
+    };
+
+    linear.copysign(channel)
+}
+
+fn linear_to_srgb(channel: f32) -> f32 {
+    let magnitude = channel.abs();
+
+    let srgb = if magnitude <= 0.0031308 {
+        magnitude * 12.92
+    } else {
+        1.055 * magnitude.powf(1.0 / 2.4) - 0.055
+    };
+
+    srgb.copysign(channel)
+}
+
+// https://bottosson.github.io/posts/oklab/
+
+fn linear_to_oklab([r, g, b]: [f32; 3]) -> [f32; 3] {
+    let l = (0.41222147 * r + 0.53633254 * g + 0.051445993 * b).cbrt();
+    let m = (0.2119035 * r + 0.6806995 * g + 0.10739696 * b).cbrt();
+    let s = (0.08830246 * r + 0.28171884 * g + 0.6299787 * b).cbrt();
+
+    [
+        0.21045426 * l + 0.7936178 * m - 0.004072047 * s,
+        1.9779985 * l - 2.4285922 * m + 0.4505937 * s,
+        0.025904037 * l + 0.78277177 * m - 0.80867577 * s,
+    ]
+}
+
+fn oklab_to_linear(l: f32, a: f32, b: f32) -> [f32; 3] {
+    let l_ = (l + 0.39633778 * a + 0.21580376 * b).powi(3);
+    let m_ = (l - 0.105561346 * a - 0.06385417 * b).powi(3);
+    let s_ = (l - 0.08948418 * a - 1.2914855 * b).powi(3);
+
+    [
+        4.0767417 * l_ - 3.3077116 * m_ + 0.23096994 * s_,
+        -1.268438 * l_ + 2.6097574 * m_ - 0.34131938 * s_,
+        -0.0041960863 * l_ - 0.7034186 * m_ + 1.7076147 * s_,
+    ]
+}
+
+/// Hue in degrees, the largest and the smallest channel
+fn hue(r: f32, g: f32, b: f32) -> (f32, f32, f32) {
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let delta = max - min;
+
+    let hue = if delta == 0.0 {
+        0.0
+    } else if max == r {
+        60.0 * ((g - b) / delta).rem_euclid(6.0)
+    } else if max == g {
+        60.0 * ((b - r) / delta + 2.0)
+    } else {
+        60.0 * ((r - g) / delta + 4.0)
+    };
+
+    (hue, max, min)
+}
+
+fn srgb_to_hsl(r: f32, g: f32, b: f32) -> [f32; 3] {
+    let (hue, max, min) = hue(r, g, b);
+    let lightness = (max + min) / 2.0;
+
+    let saturation = if max == min {
+        0.0
+    } else {
+        (max - min) / (1.0 - (2.0 * lightness - 1.0).abs())
+    };
+
+    [hue, saturation, lightness]
+}
+
+fn srgb_to_hsv(r: f32, g: f32, b: f32) -> [f32; 3] {
+    let (hue, max, min) = hue(r, g, b);
+    let saturation = if max == 0.0 { 0.0 } else { (max - min) / max };
+
+    [hue, saturation, max]
+}
+
+/// Channels for a hue with the given chroma, before adding the lightest gray
+fn hue_to_rgb(hue: f32, chroma: f32) -> [f32; 3] {
+    let hue = hue.rem_euclid(360.0) / 60.0;
+    let x = chroma * (1.0 - (hue.rem_euclid(2.0) - 1.0).abs());
+
+    match hue as u32 {
+        0 => [chroma, x, 0.0],
+        1 => [x, chroma, 0.0],
+        2 => [0.0, chroma, x],
+        3 => [0.0, x, chroma],
+        4 => [x, 0.0, chroma],
+        _ => [chroma, 0.0, x],
+    }
+}
+
+fn hsl_to_srgb(hue: f32, saturation: f32, lightness: f32) -> [f32; 3] {
+    let chroma = (1.0 - (2.0 * lightness - 1.0).abs()) * saturation;
+    let gray = lightness - chroma / 2.0;
+
+    hue_to_rgb(hue, chroma).map(|channel| channel + gray)
+}
+
+fn hsv_to_srgb(hue: f32, saturation: f32, value: f32) -> [f32; 3] {
+    let chroma = value * saturation;
+    let gray = value - chroma;
+
+    hue_to_rgb(hue, chroma).map(|channel| channel + gray)
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ColorParseError {
+    /// A # not followed by 3, 4, 6 or 8 hexadecimal digits
+    InvalidHex,
+
+    /// Not a named color or a color function
+    UnknownColor(String),
+
+    /// Wrong number of arguments to a color function, or arguments that are not numbers
+    InvalidArguments,
+}
+
+impl fmt::Display for ColorParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::InvalidHex => write!(f, "Invalid hex color"),
+            Self::UnknownColor(name) => write!(f, "Unknown color {name}"),
+            Self::InvalidArguments => write!(f, "Invalid arguments to color function"),
+        }
+    }
+}
+
+impl std::error::Error for ColorParseError {}
+
+/// CSS colors: `#rgb`, `#rgba`, `#rrggbb`, `#rrggbbaa`, `rgb()`, `rgba()`, `hsl()`, `hsla()` and the named colors.
+/// Functions take the legacy comma separated and the modern space separated arguments
+impl FromStr for Color {
+    type Err = ColorParseError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let text = text.trim().to_ascii_lowercase();
+
+        if let Some(hex) = text.strip_prefix('#') {
+            return parse_hex(hex)
+        }
+
+        let Some((name, arguments)) = text.strip_suffix(')').and_then(|text| text.split_once('(')) else {
+            return colors::css::from_name(&text).map(Color::from).ok_or(ColorParseError::UnknownColor(text))
+        };
+
+        let arguments = split_arguments(arguments).ok_or(ColorParseError::InvalidArguments)?;
+
+        let [first, second, third, alpha] = match arguments.as_slice() {
+            [first, second, third] => [*first, *second, *third, "1"],
+            [first, second, third, alpha] => [*first, *second, *third, *alpha],
+            _ => return Err(ColorParseError::InvalidArguments),
+        };
+
+        let alpha = parse_number(alpha, 1.0).ok_or(ColorParseError::InvalidArguments)?.clamp(0.0, 1.0);
+
+        let [x, y, z] = match name.trim() {
+            "rgb" | "rgba" => [first, second, third].map(|channel| parse_number(channel, 255.0).map(|channel| channel / 255.0)),
+            "hsl" | "hsla" => [parse_angle(first), parse_number(second, 1.0), parse_number(third, 1.0)],
+            _ => return Err(ColorParseError::UnknownColor(name.trim().to_string())),
+        };
+
+        let (Some(x), Some(y), Some(z)) = (x, y, z) else {
+            return Err(ColorParseError::InvalidArguments)
+        };
+
+        let color = match name.trim() {
+            "hsl" | "hsla" => Color::from_hsl(x, y.clamp(0.0, 1.0), z.clamp(0.0, 1.0), alpha),
+            _ => Color::new(x.clamp(0.0, 1.0), y.clamp(0.0, 1.0), z.clamp(0.0, 1.0), alpha),
+        };
+
+        Ok(color)
+    }
+}
+
+fn parse_hex(hex: &str) -> Result<Color, ColorParseError> {
+    if !hex.chars().all(|char| char.is_ascii_hexdigit()) {
+        return Err(ColorParseError::InvalidHex)
+    }
+
+    let digit = |index: usize| u8::from_str_radix(&hex[index..index + 1], 16).unwrap();
+    let byte = |index: usize| u8::from_str_radix(&hex[index..index + 2], 16).unwrap();
+
+    let pixel = match hex.len() {
+        3 => [digit(0) * 17, digit(1) * 17, digit(2) * 17, 255],
+        4 => [digit(0) * 17, digit(1) * 17, digit(2) * 17, digit(3) * 17],
+        6 => [byte(0), byte(2), byte(4), 255],
+        8 => [byte(0), byte(2), byte(4), byte(6)],
+        _ => return Err(ColorParseError::InvalidHex),
+    };
+
+    Ok(Color::from_pixel(pixel))
+}
+
+/// Arguments separated by commas, or by spaces with a slash before alpha
+fn split_arguments(arguments: &str) -> Option<Vec<&str>> {
+    if arguments.contains(',') {
+        return Some(arguments.split(',').map(str::trim).collect())
+    }
+
+    let (channels, alpha) = match arguments.split_once('/') {
+        Some((channels, alpha)) => (channels, Some(alpha.trim())),
+        None => (arguments, None),
+    };
+
+    let mut arguments: Vec<&str> = channels.split_whitespace().collect();
+
+    if let Some(alpha) = alpha {
+        if arguments.len() != 3 {
+            return None
+        }
+
+        arguments.push(alpha);
+    }
+
+    Some(arguments)
+}
+
+/// A number, or a percentage of full
+fn parse_number(text: &str, full: f32) -> Option<f32> {
+    match text.strip_suffix('%') {
+        Some(percentage) => percentage.parse::<f32>().ok().map(|percentage| percentage / 100.0 * full),
+        None => text.parse().ok(),
+    }
+}
+
+/// Degrees, optionally with a deg, grad, rad or turn unit
+fn parse_angle(text: &str) -> Option<f32> {
+    let units = [("deg", 1.0), ("grad", 0.9), ("rad", 180.0 / std::f32::consts::PI), ("turn", 360.0)];
+
+    for (unit, degrees) in units {
+        if let Some(value) = text.strip_suffix(unit) {
+            return value.parse::<f32>().ok().map(|value| value * degrees)
+        }
+    }
+
+    text.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::colors;
+
+    use super::{Color, ColorParseError, ColorSpace};
+
+    #[test]
+    fn conversions() {
+        let orange = Color::from_pixel(colors::ORANGE);
+
+        for space in [ColorSpace::Srgb, ColorSpace::LinearSrgb, ColorSpace::Hsl, ColorSpace::Hsv, ColorSpace::Oklab] {
+            assert_eq!(Color::from_space(space, orange.to_space(space)).to_pixel(), colors::ORANGE, "{space:?}");
+        }
+
+        assert_eq!(Color::from_hsl(120.0, 1.0, 0.25, 1.0).to_pixel(), colors::GREEN);
+        assert_eq!(Color::from_hsv(240.0, 1.0, 0.5, 1.0).to_pixel(), colors::NAVY);
+
+        let [l, a, b, _] = Color::from_pixel(colors::WHITE).to_space(ColorSpace::Oklab);
+        assert!((l - 1.0).abs() < 1e-3 && a.abs() < 1e-3 && b.abs() < 1e-3);
+
+        let translucent = Color::new(1.0, 0.5, 0.0, 0.5);
+        assert_eq!(translucent.premultiplied(), [0.5, 0.25, 0.0, 0.5]);
+        assert_eq!(Color::from_premultiplied(translucent.premultiplied()), translucent);
+    }
+
+    #[test]
+    fn mix() {
+        let red = Color::from_pixel(colors::RED);
+        let blue = Color::from_pixel(colors::BLUE);
+
+        assert_eq!(red.mix(blue, 0.5, ColorSpace::Srgb).to_pixel(), [128, 0, 128, 255]);
+        assert_eq!(red.mix(blue, 0.5, ColorSpace::LinearSrgb).to_pixel(), [188, 0, 188, 255]);
+
+        // Red at 0 degrees and blue at 240 meet at magenta, the short way around
+        assert_eq!(red.mix(blue, 0.5, ColorSpace::Hsl).to_pixel(), colors::MAGENTA);
+
+        // Transparent colors do not pull the mix towards black
+        let clear = Color::from_pixel(colors::css::TRANSPARENT);
+        assert_eq!(red.mix(clear, 0.5, ColorSpace::Srgb).to_pixel(), [255, 0, 0, 128]);
+
+        let gray = Color::from_pixel(colors::GRAY);
+        assert!(gray.lighten(0.1).r > gray.r && gray.darken(0.1).r < gray.r);
+        assert_eq!(gray.lighten(0.0).to_pixel(), colors::GRAY);
+    }
+
+    #[test]
+    fn parse() {
+        let parse = |text: &str| text.parse::<Color>().map(Color::to_pixel);
+
+        assert_eq!(parse("#f80"), Ok([0xff, 0x88, 0x00, 0xff]));
+        assert_eq!(parse("#ff880080"), Ok([0xff, 0x88, 0x00, 0x80]));
+        assert_eq!(parse(" RebeccaPurple "), Ok(colors::css::REBECCAPURPLE));
+        assert_eq!(parse("rgb(255, 165, 0)"), Ok(colors::ORANGE));
+        assert_eq!(parse("rgba(100%, 0%, 0%, 0.5)"), Ok([255, 0, 0, 128]));
+        assert_eq!(parse("rgb(0 128 0 / 50%)"), Ok([0, 128, 0, 128]));
+        assert_eq!(parse("hsl(0.5turn 100% 50%)"), Ok(colors::CYAN));
+        assert_eq!(parse("hsla(240, 100%, 25%, 1)"), Ok(colors::NAVY));
+
+        assert_eq!(parse("#12345"), Err(ColorParseError::InvalidHex));
+        assert_eq!(parse("rgb(1, 2)"), Err(ColorParseError::InvalidArguments));
+        assert_eq!(parse("blurple"), Err(ColorParseError::UnknownColor("blurple".to_string())));
+    }
+}
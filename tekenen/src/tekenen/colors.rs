@@ -178,4 +178,162 @@ pub mod css {
     pub const WHITESMOKE          : Pixel = [0xf5, 0xf5, 0xf5, 0xff]; // #f5f5f5
     pub const YELLOW              : Pixel = [0xff, 0xff, 0x00, 0xff]; // #ffff00
     pub const YELLOWGREEN         : Pixel = [0x9a, 0xcd, 0x32, 0xff]; // #9acd32
+
+    /// Color with a CSS name, names are lowercase
+    pub fn from_name(name: &str) -> Option<Pixel> {
+        let color = match name {
+            "aliceblue"            => ALICEBLUE,
+            "antiquewhite"         => ANTIQUEWHITE,
+            "aqua"                 => AQUA,
+            "aquamarine"           => AQUAMARINE,
+            "azure"                => AZURE,
+            "beige"                => BEIGE,
+            "bisque"               => BISQUE,
+            "black"                => BLACK,
+            "blanchedalmond"       => BLANCHEDALMOND,
+            "blue"                 => BLUE,
+            "blueviolet"           => BLUEVIOLET,
+            "brown"                => BROWN,
+            "burlywood"            => BURLYWOOD,
+            "cadetblue"            => CADETBLUE,
+            "chartreuse"           => CHARTREUSE,
+            "chocolate"            => CHOCOLATE,
+            "coral"                => CORAL,
+            "cornflowerblue"       => CORNFLOWERBLUE,
+            "cornsilk"             => CORNSILK,
+            "crimson"              => CRIMSON,
+            "cyan"                 => CYAN,
+            "darkblue"             => DARKBLUE,
+            "darkcyan"             => DARKCYAN,
+            "darkgoldenrod"        => DARKGOLDENROD,
+            "darkgray"             => DARKGRAY,
+            "darkgreen"            => DARKGREEN,
+            "darkgrey"             => DARKGREY,
+            "darkkhaki"            => DARKKHAKI,
+            "darkmagenta"          => DARKMAGENTA,
+            "darkolivegreen"       => DARKOLIVEGREEN,
+            "darkorange"           => DARKORANGE,
+            "darkorchid"           => DARKORCHID,
+            "darkred"              => DARKRED,
+            "darksalmon"           => DARKSALMON,
+            "darkseagreen"         => DARKSEAGREEN,
+            "darkslateblue"        => DARKSLATEBLUE,
+            "darkslategray"        => DARKSLATEGRAY,
+            "darkslategrey"        => DARKSLATEGREY,
+            "darkturquoise"        => DARKTURQUOISE,
+            "darkviolet"           => DARKVIOLET,
+            "deeppink"             => DEEPPINK,
+            "deepskyblue"          => DEEPSKYBLUE,
+            "dimgray"              => DIMGRAY,
+            "dimgrey"              => DIMGREY,
+            "dodgerblue"           => DODGERBLUE,
+            "firebrick"            => FIREBRICK,
+            "floralwhite"          => FLORALWHITE,
+            "forestgreen"          => FORESTGREEN,
+            "fuchsia"              => FUCHSIA,
+            "gainsboro"            => GAINSBORO,
+            "ghostwhite"           => GHOSTWHITE,
+            "gold"                 => GOLD,
+            "goldenrod"            => GOLDENROD,
+            "gray"                 => GRAY,
+            "green"                => GREEN,
+            "greenyellow"          => GREENYELLOW,
+            "grey"                 => GREY,
+            "honeydew"             => HONEYDEW,
+            "hotpink"              => HOTPINK,
+            "indianred"            => INDIANRED,
+            "indigo"               => INDIGO,
+            "ivory"                => IVORY,
+            "khaki"                => KHAKI,
+            "lavender"             => LAVENDER,
+            "lavenderblush"        => LAVENDERBLUSH,
+            "lawngreen"            => LAWNGREEN,
+            "lemonchiffon"         => LEMONCHIFFON,
+            "lightblue"            => LIGHTBLUE,
+            "lightcoral"           => LIGHTCORAL,
+            "lightcyan"            => LIGHTCYAN,
+            "lightgoldenrodyellow" => LIGHTGOLDENRODYELLOW,
+            "lightgray"            => LIGHTGRAY,
+            "lightgreen"           => LIGHTGREEN,
+            "lightgrey"            => LIGHTGREY,
+            "lightpink"            => LIGHTPINK,
+            "lightsalmon"          => LIGHTSALMON,
+            "lightseagreen"        => LIGHTSEAGREEN,
+            "lightskyblue"         => LIGHTSKYBLUE,
+            "lightslategray"       => LIGHTSLATEGRAY,
+            "lightslategrey"       => LIGHTSLATEGREY,
+            "lightsteelblue"       => LIGHTSTEELBLUE,
+            "lightyellow"          => LIGHTYELLOW,
+            "lime"                 => LIME,
+            "limegreen"            => LIMEGREEN,
+            "linen"                => LINEN,
+            "magenta"              => MAGENTA,
+            "maroon"               => MAROON,
+            "mediumaquamarine"     => MEDIUMAQUAMARINE,
+            "mediumblue"           => MEDIUMBLUE,
+            "mediumorchid"         => MEDIUMORCHID,
+            "mediumpurple"         => MEDIUMPURPLE,
+            "mediumseagreen"       => MEDIUMSEAGREEN,
+            "mediumslateblue"      => MEDIUMSLATEBLUE,
+            "mediumspringgreen"    => MEDIUMSPRINGGREEN,
+            "mediumturquoise"      => MEDIUMTURQUOISE,
+            "mediumvioletred"      => MEDIUMVIOLETRED,
+            "midnightblue"         => MIDNIGHTBLUE,
+            "mintcream"            => MINTCREAM,
+            "mistyrose"            => MISTYROSE,
+            "moccasin"             => MOCCASIN,
+            "navajowhite"          => NAVAJOWHITE,
+            "navy"                 => NAVY,
+            "oldlace"              => OLDLACE,
+            "olive"                => OLIVE,
+            "olivedrab"            => OLIVEDRAB,
+            "orange"               => ORANGE,
+            "orangered"            => ORANGERED,
+            "orchid"               => ORCHID,
+            "palegoldenrod"        => PALEGOLDENROD,
+            "palegreen"            => PALEGREEN,
+            "paleturquoise"        => PALETURQUOISE,
+            "palevioletred"        => PALEVIOLETRED,
+            "papayawhip"           => PAPAYAWHIP,
+            "peachpuff"            => PEACHPUFF,
+            "peru"                 => PERU,
+            "pink"                 => PINK,
+            "plum"                 => PLUM,
+            "powderblue"           => POWDERBLUE,
+            "purple"               => PURPLE,
+            "rebeccapurple"        => REBECCAPURPLE,
+            "red"                  => RED,
+            "rosybrown"            => ROSYBROWN,
+            "royalblue"            => ROYALBLUE,
+            "saddlebrown"          => SADDLEBROWN,
+            "salmon"               => SALMON,
+            "sandybrown"           => SANDYBROWN,
+            "seagreen"             => SEAGREEN,
+            "seashell"             => SEASHELL,
+            "sienna"               => SIENNA,
+            "silver"               => SILVER,
+            "skyblue"              => SKYBLUE,
+            "slateblue"            => SLATEBLUE,
+            "slategray"            => SLATEGRAY,
+            "slategrey"            => SLATEGREY,
+            "snow"                 => SNOW,
+            "springgreen"          => SPRINGGREEN,
+            "steelblue"            => STEELBLUE,
+            "tan"                  => TAN,
+            "teal"                 => TEAL,
+            "thistle"              => THISTLE,
+            "tomato"               => TOMATO,
+            "transparent"          => TRANSPARENT,
+            "turquoise"            => TURQUOISE,
+            "violet"               => VIOLET,
+            "wheat"                => WHEAT,
+            "white"                => WHITE,
+            "whitesmoke"           => WHITESMOKE,
+            "yellow"               => YELLOW,
+            "yellowgreen"          => YELLOWGREEN,
+            _ => return None,
+        };
+
+        Some(color)
+    }
 }
\ No newline at end of file
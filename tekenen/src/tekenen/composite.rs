@@ -0,0 +1,146 @@
+use super::Pixel;
+
+/// How a drawn color is combined with the color already on the surface.
+/// Colors are not premultiplied, alpha 255 is fully opaque.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CompositeMode {
+    /// Draw over the destination, respecting the alpha of the source
+    #[default]
+    SourceOver,
+
+    /// Draw only where the destination is not transparent, the destination is replaced
+    SourceIn,
+
+    /// Erase the destination where the source is opaque, the color of the source is ignored
+    DestinationOut,
+
+    /// Add the colors together, also known as lighter
+    Additive,
+
+    /// Multiply the colors, always results in a darker color
+    Multiply,
+
+    /// Inverse of multiply, always results in a lighter color
+    Screen,
+}
+
+fn to_float(color: Pixel) -> [f32; 4] {
+    color.map(|channel| channel as f32 / 255.0)
+}
+
+fn to_pixel(color: [f32; 4]) -> Pixel {
+    color.map(|channel| (channel.clamp(0.0, 1.0) * 255.0).round() as u8)
+}
+
+impl CompositeMode {
+    /// Combine source and destination, partial coverage (0..=255) mixes the result with the destination
+    pub fn blend(&self, source: Pixel, destination: Pixel, coverage: u8) -> Pixel {
+        // Outside the shape nothing changes, whatever the mode
+        if coverage == 0 {
+            return destination
+        }
+
+        // Fast paths for the most common case
+        if *self == Self::SourceOver {
+            if source[3] == 255 && coverage == 255 {
+                return source
+            }
+
+            if source[3] == 0 {
+                return destination
+            }
+        }
+
+        let [sr, sg, sb, sa] = to_float(source);
+        let [dr, dg, db, da] = to_float(destination);
+
+        let mix = |blend: fn(f32, f32) -> f32| {
+            // Separable blending, see https://www.w3.org/TR/compositing-1/#blending
+            let ra = sa + da * (1.0 - sa);
+
+            if ra == 0.0 {
+                return [0.0; 4]
+            }
+
+            let channel = |s: f32, d: f32| {
+                (sa * (1.0 - da) * s + sa * da * blend(s, d) + (1.0 - sa) * da * d) / ra
+            };
+
+            [channel(sr, dr), channel(sg, dg), channel(sb, db), ra]
+        };
+
+        let result = match self {
+            Self::SourceOver => mix(|s, _| s),
+            Self::Multiply => mix(|s, d| s * d),
+            Self::Screen => mix(|s, d| s + d - s * d),
+            Self::SourceIn => [sr, sg, sb, sa * da],
+            Self::DestinationOut => [dr, dg, db, da * (1.0 - sa)],
+            Self::Additive => {
+                let ra = (sa + da).min(1.0);
+
+                if ra == 0.0 {
+                    [0.0; 4]
+                } else {
+                    let channel = |s: f32, d: f32| (s * sa + d * da) / ra;
+                    [channel(sr, dr), channel(sg, dg), channel(sb, db), ra]
+                }
+            },
+        };
+
+        if coverage == 255 {
+            return to_pixel(result)
+        }
+
+        // Mix premultiplied, so the color of a transparent side does not bleed in
+        let t = coverage as f32 / 255.0;
+        let [rr, rg, rb, ra] = result;
+        let alpha = da + (ra - da) * t;
+
+        if alpha == 0.0 {
+            return [0; 4]
+        }
+
+        let channel = |r: f32, d: f32| (d * da + (r * ra - d * da) * t) / alpha;
+        to_pixel([channel(rr, dr), channel(rg, dg), channel(rb, db), alpha])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CompositeMode;
+
+    const RED_HALF: [u8; 4] = [0xff, 0x00, 0x00, 0x80];
+    const BLUE: [u8; 4] = [0x00, 0x00, 0xff, 0xff];
+    const GRAY: [u8; 4] = [0x80, 0x80, 0x80, 0xff];
+
+    #[test]
+    fn source_over() {
+        assert_eq!(CompositeMode::SourceOver.blend(RED_HALF, BLUE, 255), [0x80, 0x00, 0x7f, 0xff]);
+        assert_eq!(CompositeMode::SourceOver.blend(RED_HALF, [0, 0, 0, 0], 255), RED_HALF);
+        assert_eq!(CompositeMode::SourceOver.blend(BLUE, GRAY, 0), GRAY);
+        assert_eq!(CompositeMode::SourceOver.blend(BLUE, GRAY, 255), BLUE);
+    }
+
+    #[test]
+    fn porter_duff() {
+        assert_eq!(CompositeMode::SourceIn.blend(RED_HALF, BLUE, 255), RED_HALF);
+        assert_eq!(CompositeMode::SourceIn.blend(RED_HALF, [0, 0, 0, 0], 255), [0xff, 0x00, 0x00, 0x00]);
+        assert_eq!(CompositeMode::DestinationOut.blend(RED_HALF, BLUE, 255), [0x00, 0x00, 0xff, 0x7f]);
+    }
+
+    #[test]
+    fn coverage() {
+        // Uncovered pixels are left alone, partially covered ones mix with the destination
+        assert_eq!(CompositeMode::SourceIn.blend(RED_HALF, BLUE, 0), BLUE);
+        assert_eq!(CompositeMode::SourceIn.blend(BLUE, [0xff, 0x00, 0x00, 0xff], 0x80), [0x7f, 0x00, 0x80, 0xff]);
+        assert_eq!(CompositeMode::DestinationOut.blend(BLUE, GRAY, 0x80), [0x80, 0x80, 0x80, 0x7f]);
+        assert_eq!(CompositeMode::SourceOver.blend(RED_HALF, BLUE, 0x80), CompositeMode::SourceOver.blend([0xff, 0x00, 0x00, 0x40], BLUE, 255));
+    }
+
+    #[test]
+    fn separable() {
+        assert_eq!(CompositeMode::Multiply.blend([0xff, 0x80, 0x00, 0xff], GRAY, 255), [0x80, 0x40, 0x00, 0xff]);
+        assert_eq!(CompositeMode::Screen.blend([0xff, 0x00, 0x00, 0xff], GRAY, 255), [0xff, 0x80, 0x80, 0xff]);
+        assert_eq!(CompositeMode::Additive.blend([0x40, 0x00, 0x00, 0xff], GRAY, 255), [0xc0, 0x80, 0x80, 0xff]);
+    }
+}
@@ -0,0 +1,236 @@
+use std::{fmt, path::Path};
+
+use crate::platform::fpia::encode_fpia;
+
+use super::Surface;
+
+/// File formats a surface can be written as
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageFormat {
+    /// Compressed and readable everywhere, needs the image feature
+    #[cfg(feature = "image")]
+    Png,
+
+    /// Fritz Preloaded Image Asset, read back by `PlatformTrait::parse_image`
+    Fpia,
+
+    /// Binary portable pixmap (P6), the alpha channel is dropped
+    Ppm,
+
+    /// Portable arbitrary map (P7) with RGB_ALPHA tuples
+    Pam,
+
+    /// Uncompressed 32 bit bitmap with alpha
+    Bmp,
+}
+
+impl ImageFormat {
+    /// Format belonging to a file extension, case insensitive
+    pub fn from_extension(extension: &str) -> Option<Self> {
+        match extension.to_ascii_lowercase().as_str() {
+            #[cfg(feature = "image")]
+            "png" => Some(Self::Png),
+            "fpia" => Some(Self::Fpia),
+            "ppm" => Some(Self::Ppm),
+            "pam" => Some(Self::Pam),
+            "bmp" => Some(Self::Bmp),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum ImageSavingError {
+    IOError(std::io::Error),
+    #[cfg(feature = "image")]
+    ImageError(image::ImageError),
+
+    /// The extension of the path does not name a supported format
+    UnknownFormat,
+
+    /// An indexed FPIA palette holds at most 256 colors
+    TooManyColors,
+
+    /// Deflate compression needs the deflate feature
+    UnsupportedCompression,
+}
+
+impl fmt::Display for ImageSavingError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::IOError(error) => write!(f, "IO error: {error}"),
+            #[cfg(feature = "image")]
+            Self::ImageError(error) => write!(f, "Image error: {error}"),
+            Self::UnknownFormat => write!(f, "Unknown image format"),
+            Self::TooManyColors => write!(f, "More than 256 colors for an indexed image"),
+            Self::UnsupportedCompression => write!(f, "Deflate is not enabled"),
+        }
+    }
+}
+
+impl std::error::Error for ImageSavingError {}
+
+/// Header of a netpbm file, followed by the raw samples
+fn netpbm(surface: &Surface, header: String, channels: usize) -> Vec<u8> {
+    let mut data = Vec::with_capacity(header.len() + surface.pixels.len() * channels);
+    data.extend_from_slice(header.as_bytes());
+
+    for pixel in surface.as_slice() {
+        data.extend_from_slice(&pixel[..channels]);
+    }
+
+    data
+}
+
+/// BITMAPV4HEADER with bitfields, plain BITMAPINFOHEADER readers ignore the alpha
+fn bmp(surface: &Surface) -> Vec<u8> {
+    const FILE_HEADER: u32 = 14;
+    const INFO_HEADER: u32 = 108;
+
+    let (width, height) = (surface.width() as u32, surface.height() as u32);
+    let size = width * height * 4;
+
+    let mut data = Vec::with_capacity((FILE_HEADER + INFO_HEADER + size) as usize);
+    data.extend_from_slice(b"BM");
+
+    let mut push = |values: &[u32]| values.iter().for_each(|value| data.extend_from_slice(&value.to_le_bytes()));
+    push(&[FILE_HEADER + INFO_HEADER + size, 0, FILE_HEADER + INFO_HEADER]);
+
+    // Size, width, height, one plane and 32 bits per pixel, BI_BITFIELDS, image size and 72 dpi
+    push(&[INFO_HEADER, width, height, 1 | 32 << 16, 3, size, 2835, 2835, 0, 0]);
+
+    // Red, green, blue and alpha masks, then the sRGB color space and unused gamma fields
+    push(&[0x00ff0000, 0x0000ff00, 0x000000ff, 0xff000000, u32::from_be_bytes(*b"sRGB")]);
+    push(&[0; 12]);
+
+    // An empty surface is only the header, there are no rows to split the pixels into
+    if width == 0 {
+        return data
+    }
+
+    // Rows are stored bottom to top, every pixel as BGRA
+    for row in surface.as_slice().chunks(width as usize).rev() {
+        for [r, g, b, a] in row {
+            data.extend_from_slice(&[*b, *g, *r, *a]);
+        }
+    }
+
+    data
+}
+
+impl Surface {
+    /// Serialize the surface in the given format
+    pub fn encode(&self, format: ImageFormat) -> Result<Vec<u8>, ImageSavingError> {
+        let (width, height) = (self.width(), self.height());
+
+        Ok(match format {
+            #[cfg(feature = "image")]
+            ImageFormat::Png => {
+                use image::ImageEncoder;
+
+                let mut data = Vec::new();
+                image::codecs::png::PngEncoder::new(&mut data)
+                    .write_image(self.as_slice().as_flattened(), width as u32, height as u32, image::ColorType::Rgba8)
+                    .map_err(ImageSavingError::ImageError)?;
+
+                data
+            },
+            ImageFormat::Fpia => encode_fpia(self),
+            ImageFormat::Ppm => netpbm(self, format!("P6\n{width} {height}\n255\n"), 3),
+            ImageFormat::Pam => netpbm(self, format!("P7\nWIDTH {width}\nHEIGHT {height}\nDEPTH 4\nMAXVAL 255\nTUPLTYPE RGB_ALPHA\nENDHDR\n"), 4),
+            ImageFormat::Bmp => bmp(self),
+        })
+    }
+
+    /// Write the surface to path, the format is chosen by the extension
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), ImageSavingError> {
+        let path = path.as_ref();
+
+        let format = path.extension()
+            .and_then(|extension| extension.to_str())
+            .and_then(ImageFormat::from_extension)
+            .ok_or(ImageSavingError::UnknownFormat)?;
+
+        self.save_as(path, format)
+    }
+
+    /// Write the surface to path in the given format, whatever the extension
+    pub fn save_as(&self, path: impl AsRef<Path>, format: ImageFormat) -> Result<(), ImageSavingError> {
+        std::fs::write(path, self.encode(format)?).map_err(ImageSavingError::IOError)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{platform::fpia::decode_fpia, Surface};
+
+    use super::ImageFormat;
+
+    fn image() -> Surface {
+        Surface::from_pixels(3, 2, vec![
+            [255, 0, 0, 255], [0, 255, 0, 128], [0, 0, 255, 0],
+            [10, 20, 30, 255], [40, 50, 60, 255], [70, 80, 90, 1],
+        ])
+    }
+
+    #[test]
+    fn netpbm_headers() {
+        let ppm = image().encode(ImageFormat::Ppm).unwrap();
+        assert!(ppm.starts_with(b"P6\n3 2\n255\n"));
+        assert_eq!(ppm.len(), 11 + 3 * 2 * 3);
+        assert_eq!(ppm[11..14], [255, 0, 0]);
+
+        let pam = image().encode(ImageFormat::Pam).unwrap();
+        assert!(pam.ends_with(&[40, 50, 60, 255, 70, 80, 90, 1]));
+
+        assert_eq!(ImageFormat::from_extension("BMP"), Some(ImageFormat::Bmp));
+        assert_eq!(ImageFormat::from_extension("txt"), None);
+    }
+
+    #[test]
+    #[cfg(feature = "image")]
+    fn read_back() {
+        use crate::platform::{Platform, PlatformTrait};
+
+        let surface = image();
+
+        for format in [ImageFormat::Png, ImageFormat::Fpia, ImageFormat::Bmp] {
+            let decoded = Platform::parse_image(&surface.encode(format).unwrap()).unwrap();
+            assert_eq!(decoded.as_slice(), surface.as_slice(), "{format:?}");
+        }
+
+        let opaque: Vec<_> = surface.as_slice().iter().map(|[r, g, b, _]| [*r, *g, *b, 255]).collect();
+        let decoded = Platform::parse_image(&surface.encode(ImageFormat::Ppm).unwrap()).unwrap();
+        assert_eq!(decoded.as_slice(), opaque);
+    }
+
+    #[test]
+    fn empty_surfaces() {
+        for surface in [Surface::new(0, 3), Surface::new(4, 0)] {
+            let size = (surface.width(), surface.height());
+            let (width, height) = size;
+
+            let decoded = decode_fpia(&surface.encode(ImageFormat::Fpia).unwrap()).unwrap();
+            assert_eq!((decoded.width(), decoded.height()), size);
+
+            // Only the headers are left, image can not read these back
+            let pam = surface.encode(ImageFormat::Pam).unwrap();
+            assert_eq!(pam, format!("P7\nWIDTH {width}\nHEIGHT {height}\nDEPTH 4\nMAXVAL 255\nTUPLTYPE RGB_ALPHA\nENDHDR\n").as_bytes());
+
+            let bmp = surface.encode(ImageFormat::Bmp).unwrap();
+            assert_eq!(bmp.len(), 122);
+            assert_eq!(bmp[18..26], [(width as u32).to_le_bytes(), (height as u32).to_le_bytes()].concat());
+
+            #[cfg(feature = "image")]
+            {
+                use crate::platform::{Platform, PlatformTrait};
+
+                let decoded = Platform::parse_image(&surface.encode(ImageFormat::Ppm).unwrap()).unwrap();
+                assert_eq!((decoded.width(), decoded.height()), size);
+
+                // PNG does not allow empty images
+                assert!(surface.encode(ImageFormat::Png).is_err());
+            }
+        }
+    }
+}
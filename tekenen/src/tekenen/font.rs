@@ -0,0 +1,643 @@
+use std::{cell::RefCell, collections::HashMap, fmt, rc::Rc};
+
+use ab_glyph::{Font as _, FontVec, GlyphId, OutlineCurve, PxScale, ScaleFont};
+use unicode_normalization::UnicodeNormalization;
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::{math::Vec2, shapes::path::Path};
+
+use super::text::TextMetrics;
+
+mod bitmap;
+use bitmap::BitmapFont;
+
+/// Tab stops are this many spaces apart
+const TAB_WIDTH: f32 = 4.0;
+
+#[derive(Debug)]
+pub enum FontLoadingError {
+    /// The data is not a font in a supported format
+    InvalidFont,
+
+    /// The data ends in the middle of the font
+    Truncated,
+
+    UnknownVersion(u8),
+
+    /// Sizes or characters that make no sense, or a font without glyphs
+    InvalidData,
+
+    /// A line of a BDF file could not be read, counting from 1
+    InvalidBdf { line: usize },
+}
+
+impl fmt::Display for FontLoadingError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::InvalidFont => write!(f, "Invalid or unsupported font"),
+            Self::Truncated => write!(f, "Font data is truncated"),
+            Self::UnknownVersion(version) => write!(f, "Unknown font version {version}"),
+            Self::InvalidData => write!(f, "Invalid font data"),
+            Self::InvalidBdf { line } => write!(f, "Invalid BDF on line {line}"),
+        }
+    }
+}
+
+impl std::error::Error for FontLoadingError {}
+
+enum FontKind {
+    /// Pixels of a BDF, PSF or FPFN file, scaled by whole pixels when drawn at a multiple of their height
+    Bitmap(BitmapFont),
+
+    /// Outlines of a TTF or OTF file, rasterized on demand
+    TrueType(FontVec),
+}
+
+/// Coverage of a rasterized glyph, placed relative to the pen on the baseline
+pub(crate) struct Glyph {
+    pub left: i32,
+    pub top: i32,
+    pub width: i32,
+    pub height: i32,
+
+    /// Row major, 0..=255
+    pub coverage: Vec<u8>,
+}
+
+/// A glyph of one of the faces of a font
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct GlyphRef {
+    face: usize,
+    id: u32,
+}
+
+/// Rasterized glyphs by glyph id and pixel height
+type GlyphCache = HashMap<(u32, u32), Option<Rc<Glyph>>>;
+
+struct Face {
+    kind: FontKind,
+    cache: RefCell<GlyphCache>,
+}
+
+impl Face {
+    fn scale(height: f32) -> PxScale {
+        PxScale::from(height)
+    }
+
+    /// Id of the glyph for char, None if the face does not have it
+    fn lookup(&self, char: char) -> Option<u32> {
+        match &self.kind {
+            FontKind::Bitmap(font) => font.lookup(char),
+            FontKind::TrueType(font) => Some(font.glyph_id(char).0 as u32).filter(|id| *id != 0),
+        }
+    }
+
+    /// Glyph drawn for characters no face has
+    fn notdef(&self) -> u32 {
+        match &self.kind {
+            FontKind::Bitmap(font) => font.notdef(),
+            FontKind::TrueType(_) => 0,
+        }
+    }
+
+    /// Pixels of a bitmap font per font pixel
+    fn bitmap_scale(font: &BitmapFont, height: f32) -> f32 {
+        height / (font.ascent + font.descent).max(1) as f32
+    }
+
+    fn ascent(&self, height: f32) -> f32 {
+        match &self.kind {
+            FontKind::Bitmap(font) => font.ascent as f32 * Self::bitmap_scale(font, height),
+            FontKind::TrueType(font) => font.as_scaled(Self::scale(height)).ascent(),
+        }
+    }
+
+    fn descent(&self, height: f32) -> f32 {
+        match &self.kind {
+            FontKind::Bitmap(font) => font.descent as f32 * Self::bitmap_scale(font, height),
+            FontKind::TrueType(font) => -font.as_scaled(Self::scale(height)).descent(),
+        }
+    }
+
+    fn line_height(&self, height: f32) -> f32 {
+        match &self.kind {
+            FontKind::Bitmap(_) => height,
+            FontKind::TrueType(font) => {
+                let font = font.as_scaled(Self::scale(height));
+                font.height() + font.line_gap()
+            },
+        }
+    }
+
+    fn advance(&self, id: u32, height: f32) -> f32 {
+        match &self.kind {
+            FontKind::Bitmap(font) => font.glyphs[id as usize].advance as f32 * Self::bitmap_scale(font, height),
+            FontKind::TrueType(font) => font.as_scaled(Self::scale(height)).h_advance(GlyphId(id as u16)),
+        }
+    }
+
+    fn kerning(&self, left: u32, right: u32, height: f32) -> f32 {
+        match &self.kind {
+            FontKind::Bitmap(_) => 0.0,
+            FontKind::TrueType(font) => font.as_scaled(Self::scale(height)).kern(GlyphId(left as u16), GlyphId(right as u16)),
+        }
+    }
+
+    fn glyph(&self, id: u32, height: f32) -> Option<Rc<Glyph>> {
+        let mut cache = self.cache.borrow_mut();
+
+        cache.entry((id, height.to_bits()))
+            .or_insert_with(|| self.rasterize(id, height).map(Rc::new))
+            .clone()
+    }
+
+    fn rasterize(&self, id: u32, height: f32) -> Option<Glyph> {
+        match &self.kind {
+            FontKind::Bitmap(font) => {
+                let scale = Self::bitmap_scale(font, height);
+                let glyph = &font.glyphs[id as usize];
+
+                let [left, top, width, height] = [glyph.left, glyph.top, glyph.width, glyph.height]
+                    .map(|value| (value as f32 * scale).round() as i32);
+
+                if width <= 0 || height <= 0 {
+                    return None
+                }
+
+                let mut coverage = vec![0; (width * height) as usize];
+
+                for y in 0..height {
+                    for x in 0..width {
+                        if glyph.lit(x * glyph.width / width, y * glyph.height / height) {
+                            coverage[(y * width + x) as usize] = 255;
+                        }
+                    }
+                }
+
+                Some(Glyph { left, top, width, height, coverage })
+            },
+            FontKind::TrueType(font) => {
+                let glyph = GlyphId(id as u16).with_scale(Self::scale(height));
+                let outlined = font.outline_glyph(glyph)?;
+
+                let bounds = outlined.px_bounds();
+                let (width, height) = (bounds.width() as i32, bounds.height() as i32);
+                let mut coverage = vec![0; (width * height) as usize];
+
+                outlined.draw(|x, y, amount| {
+                    coverage[(y as i32 * width + x as i32) as usize] = (amount.clamp(0.0, 1.0) * 255.0).round() as u8;
+                });
+
+                Some(Glyph { left: bounds.min.x as i32, top: bounds.min.y as i32, width, height, coverage })
+            },
+        }
+    }
+
+    fn outline(&self, id: u32, height: f32, x: f32, y: f32, mut path: Path) -> Path {
+        match &self.kind {
+            FontKind::Bitmap(font) => {
+                // Bitmaps have no outlines, so every lit pixel becomes a square
+                let scale = Self::bitmap_scale(font, height);
+                let glyph = &font.glyphs[id as usize];
+
+                for yd in 0..glyph.height {
+                    for xd in 0..glyph.width {
+                        if glyph.lit(xd, yd) {
+                            let (left, top) = ((glyph.left + xd) as f32 * scale, (glyph.top + yd) as f32 * scale);
+                            path = path.rect(x + left, y + top, scale, scale);
+                        }
+                    }
+                }
+
+                path
+            },
+            FontKind::TrueType(font) => {
+                let Some(outline) = font.outline(GlyphId(id as u16)) else {
+                    return path
+                };
+
+                let scale = font.as_scaled(Self::scale(height));
+                let (sx, sy) = (scale.h_scale_factor(), scale.v_scale_factor());
+
+                // Font units point up from the baseline
+                let at = |point: ab_glyph::Point| (x + point.x * sx, y - point.y * sy);
+                let mut end = None;
+
+                for curve in outline.curves {
+                    let (start, next) = match curve {
+                        OutlineCurve::Line(from, to) => (from, to),
+                        OutlineCurve::Quad(from, _, to) => (from, to),
+                        OutlineCurve::Cubic(from, _, _, to) => (from, to),
+                    };
+
+                    if end != Some(start) {
+                        if end.is_some() {
+                            path = path.close();
+                        }
+
+                        let (px, py) = at(start);
+                        path = path.move_to(px, py);
+                    }
+
+                    path = match curve {
+                        OutlineCurve::Line(_, to) => {
+                            let (px, py) = at(to);
+                            path.line_to(px, py)
+                        },
+                        OutlineCurve::Quad(_, control, to) => {
+                            let ((cx, cy), (px, py)) = (at(control), at(to));
+                            path.quad_to(cx, cy, px, py)
+                        },
+                        OutlineCurve::Cubic(_, c1, c2, to) => {
+                            let ((c1x, c1y), (c2x, c2y), (px, py)) = (at(c1), at(c2), at(to));
+                            path.cubic_to(c1x, c1y, c2x, c2y, px, py)
+                        },
+                    };
+
+                    end = Some(next);
+                }
+
+                if end.is_some() {
+                    path = path.close();
+                }
+
+                path
+            },
+        }
+    }
+}
+
+/// Handle to a font, cheap to clone and shared between surfaces.
+/// Characters missing from the font are looked up in its fallbacks, in order
+#[derive(Clone)]
+pub struct Font {
+    faces: Rc<[Rc<Face>]>,
+}
+
+impl fmt::Debug for Font {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.faces.iter().map(|face| match face.kind {
+            FontKind::Bitmap(_) => "Bitmap",
+            FontKind::TrueType(_) => "TrueType",
+        })).finish()
+    }
+}
+
+impl Default for Font {
+    fn default() -> Self {
+        Self::builtin()
+    }
+}
+
+impl Font {
+    fn from_kind(kind: FontKind) -> Self {
+        Self {
+            faces: Rc::new([Rc::new(Face { kind, cache: RefCell::new(HashMap::new()) })])
+        }
+    }
+
+    /// The 8 by 8 bitmap font, with combining accents for latin letters
+    pub fn builtin() -> Self {
+        thread_local! {
+            static BUILTIN: Font = Font::baked(include_bytes!("font/default.fpfn"));
+        }
+
+        BUILTIN.with(Font::clone)
+    }
+
+    /// The 8 by 8 font of the Commodore 64
+    pub fn c64() -> Self {
+        thread_local! {
+            static C64: Font = Font::baked(include_bytes!("font/c64.fpfn"));
+        }
+
+        C64.with(Font::clone)
+    }
+
+    fn baked(data: &[u8]) -> Self {
+        let font = BitmapFont::parse(data).and_then(Result::ok).expect("Invalid baked font");
+        Self::from_kind(FontKind::Bitmap(font))
+    }
+
+    /// Parse a BDF, PSF1, PSF2, FPFN, TTF or OTF file
+    pub fn from_bytes(data: Vec<u8>) -> Result<Self, FontLoadingError> {
+        if let Some(font) = BitmapFont::parse(&data) {
+            return Ok(Self::from_kind(FontKind::Bitmap(font?)))
+        }
+
+        let font = FontVec::try_from_vec(data).map_err(|_| FontLoadingError::InvalidFont)?;
+        Ok(Self::from_kind(FontKind::TrueType(font)))
+    }
+
+    /// Convert a BDF or PSF file to the FPFN format, which loads without parsing text.
+    /// Outline fonts are not bitmaps and give InvalidFont
+    pub(crate) fn bake(data: &[u8]) -> Result<Vec<u8>, FontLoadingError> {
+        Ok(BitmapFont::parse(data).ok_or(FontLoadingError::InvalidFont)??.encode())
+    }
+
+    /// This font, drawing the characters it misses with fallback
+    pub fn with_fallback(&self, fallback: &Font) -> Font {
+        Self {
+            faces: self.faces.iter().chain(fallback.faces.iter()).cloned().collect()
+        }
+    }
+
+    /// Some face of the font has a glyph for char
+    pub fn has_glyph(&self, char: char) -> bool {
+        self.find(char).is_some()
+    }
+
+    fn face(&self, glyph: GlyphRef) -> &Face {
+        &self.faces[glyph.face]
+    }
+
+    fn find(&self, char: char) -> Option<GlyphRef> {
+        self.faces.iter().enumerate().find_map(|(face, data)| data.lookup(char).map(|id| GlyphRef { face, id }))
+    }
+
+    /// U+FFFD if any face has it, otherwise the notdef glyph of the first face
+    fn replacement(&self) -> GlyphRef {
+        self.find(char::REPLACEMENT_CHARACTER).unwrap_or(GlyphRef { face: 0, id: self.faces[0].notdef() })
+    }
+
+    /// Glyph of char, or the replacement glyph
+    fn resolve(&self, char: char) -> GlyphRef {
+        self.find(char).unwrap_or_else(|| self.replacement())
+    }
+
+    /// Base glyph followed by its marks. Returns None for whitespace without a glyph
+    fn cluster(&self, grapheme: &str) -> Option<Vec<GlyphRef>> {
+        // Prefer a single face holding every character, composed or decomposed
+        for form in [grapheme.nfc().collect::<String>(), grapheme.nfd().collect()] {
+            for (face, data) in self.faces.iter().enumerate() {
+                if let Some(ids) = form.chars().map(|char| data.lookup(char)).collect::<Option<Vec<_>>>() {
+                    return Some(ids.into_iter().map(|id| GlyphRef { face, id }).collect())
+                }
+            }
+        }
+
+        let mut chars = grapheme.nfd();
+        let base = chars.next()?;
+
+        if base.is_whitespace() {
+            return None
+        }
+
+        // Missing marks are dropped, a missing base becomes the replacement glyph
+        let mut cluster = vec![self.resolve(base)];
+        cluster.extend(chars.filter_map(|mark| self.find(mark)));
+        Some(cluster)
+    }
+
+    /// Distance from the top of a line to the baseline
+    pub fn ascent(&self, height: f32) -> f32 {
+        self.faces[0].ascent(height)
+    }
+
+    /// Distance from the baseline to the bottom of a line, positive
+    pub fn descent(&self, height: f32) -> f32 {
+        self.faces[0].descent(height)
+    }
+
+    /// Distance between the tops of two lines
+    pub fn line_height(&self, height: f32) -> f32 {
+        self.faces[0].line_height(height)
+    }
+
+    /// How far the pen moves after char
+    pub fn advance(&self, char: char, height: f32) -> f32 {
+        let glyph = self.resolve(char);
+        self.face(glyph).advance(glyph.id, height)
+    }
+
+    /// Adjustment of the advance between left and right, negative moves them closer
+    pub fn kerning(&self, left: char, right: char, height: f32) -> f32 {
+        self.kerning_between(self.resolve(left), self.resolve(right), height)
+    }
+
+    fn kerning_between(&self, left: GlyphRef, right: GlyphRef, height: f32) -> f32 {
+        if left.face == right.face {
+            self.face(left).kerning(left.id, right.id, height)
+        } else {
+            0.0
+        }
+    }
+
+    /// Advance of the widest line of text, with the ascent and descent of the font
+    pub fn measure(&self, text: &str, height: f32) -> TextMetrics {
+        TextMetrics {
+            width: self.place(text, height, |_, _, _, _| {}).x,
+            ascent: self.ascent(height),
+            descent: self.descent(height),
+        }
+    }
+
+    /// Place every grapheme of text, glyph is called with the pen position on the baseline of each glyph.
+    /// Returns the size of the text, rounded to whole pixels.
+    pub(crate) fn layout(&self, text: &str, height: f32, mut glyph: impl FnMut(i32, i32, GlyphRef)) -> Vec2 {
+        self.place(text, height, |_, x, y, placed| glyph(x, y, placed)).round()
+    }
+
+    /// Like layout, glyph is also given the byte index of the grapheme it belongs to
+    pub(crate) fn layout_indexed(&self, text: &str, height: f32, glyph: impl FnMut(usize, i32, i32, GlyphRef)) -> Vec2 {
+        self.place(text, height, glyph).round()
+    }
+
+    /// Lines only break at newlines. Marks are placed after the advance of their base,
+    /// tabs move to the next stop and other control characters are skipped
+    fn place(&self, text: &str, height: f32, mut glyph: impl FnMut(usize, i32, i32, GlyphRef)) -> Vec2<f32> {
+        let ascent = self.ascent(height).round() as i32;
+        let line_height = self.line_height(height);
+        let space = self.advance(' ', height);
+
+        let mut curr_x = 0.0;
+        let mut curr_y = 0.0;
+        let mut width: f32 = 0.0;
+        let mut previous = None;
+
+        for (index, grapheme) in text.grapheme_indices(true) {
+            let first = grapheme.chars().next().unwrap_or(' ');
+            let newline = first == '\n' || grapheme == "\r\n";
+
+            if newline {
+                width = width.max(curr_x);
+                curr_x = 0.0;
+                curr_y += line_height;
+                previous = None;
+                continue;
+            }
+
+            if first == '\t' {
+                let tab = space * TAB_WIDTH;
+                curr_x = ((curr_x / tab).floor() + 1.0) * tab;
+                previous = None;
+                continue;
+            }
+
+            if first.is_control() {
+                continue;
+            }
+
+            let Some(cluster) = self.cluster(grapheme) else {
+                curr_x += space;
+                previous = None;
+                continue;
+            };
+
+            let base = cluster[0];
+            if let Some(previous) = previous {
+                curr_x += self.kerning_between(previous, base, height);
+            }
+
+            let baseline = curr_y.round() as i32 + ascent;
+            glyph(index, curr_x.round() as i32, baseline, base);
+
+            curr_x += self.face(base).advance(base.id, height);
+
+            for mark in &cluster[1..] {
+                glyph(index, curr_x.round() as i32, baseline, *mark);
+            }
+
+            previous = Some(base);
+        }
+
+        Vec2::new(width.max(curr_x), curr_y + line_height)
+    }
+
+    /// Coverage of glyph at height pixels, None for empty glyphs
+    pub(crate) fn glyph(&self, glyph: GlyphRef, height: f32) -> Option<Rc<Glyph>> {
+        self.face(glyph).glyph(glyph.id, height)
+    }
+
+    /// Append the outline of glyph to path, with the pen at x and the baseline at y
+    pub(crate) fn outline(&self, glyph: GlyphRef, height: f32, x: f32, y: f32, path: Path) -> Path {
+        self.face(glyph).outline(glyph.id, height, x, y, path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::math::Vec2;
+
+    use super::Font;
+
+    /// Six glyphs on a 1000 unit em: I, A, V, o, space and a notdef box. A and V kern by -150 units
+    const BLOCKS: &[u8] = include_bytes!("../../snapshots/fonts/blocks.ttf");
+
+    /// Pen x, face and glyph id of everything placed
+    fn placed(font: &Font, text: &str, height: f32) -> Vec<(i32, usize, u32)> {
+        let mut placed = Vec::new();
+        font.layout(text, height, |x, _, glyph| placed.push((x, glyph.face, glyph.id)));
+        placed
+    }
+
+    #[test]
+    fn metrics() {
+        let font = Font::from_bytes(BLOCKS.to_vec()).unwrap();
+
+        assert_eq!(font.ascent(20.0), 16.0);
+        assert_eq!(font.descent(20.0), 4.0);
+        assert_eq!(font.advance('A', 20.0), 12.0);
+        assert_eq!(font.kerning('A', 'V', 20.0), -3.0);
+        assert_eq!(font.kerning('A', 'I', 20.0), 0.0);
+
+        // AV is kerned, the space has an empty glyph
+        assert_eq!(placed(&font, "AV I", 20.0), [(0, 0, 2), (9, 0, 3), (21, 0, 4), (27, 0, 1)]);
+        assert_eq!(font.layout("AV I", 20.0, |_, _, _| {}), Vec2::new(35, 20));
+
+        assert!(Font::from_bytes(vec![0; 16]).is_err());
+    }
+
+    #[test]
+    fn rasterize() {
+        let font = Font::from_bytes(BLOCKS.to_vec()).unwrap();
+
+        // I covers 100..300 by 0..700 units, so 2 by 7 whole pixels at height 10
+        let glyph = font.glyph(font.resolve('I'), 10.0).unwrap();
+        assert_eq!((glyph.left, glyph.top, glyph.width, glyph.height), (1, -7, 2, 7));
+        assert!(glyph.coverage.iter().all(|coverage| *coverage == 255));
+
+        // The edges of the triangle are anti aliased
+        let glyph = font.glyph(font.resolve('A'), 33.0).unwrap();
+        assert!(glyph.coverage.iter().any(|coverage| *coverage > 0 && *coverage < 255));
+
+        assert!(font.glyph(font.resolve(' '), 10.0).is_none());
+
+        let builtin = Font::builtin();
+        let glyph = builtin.glyph(builtin.resolve('I'), 24.0).unwrap();
+        assert_eq!((glyph.top, glyph.width), (-24, 24));
+    }
+
+    #[test]
+    fn unicode() {
+        let builtin = Font::builtin();
+
+        let id = |char| builtin.resolve(char).id;
+
+        // Tabs move to the next stop, other control characters take no room
+        assert_eq!(placed(&builtin, "a\tb\u{7}c", 8.0), [(0, 0, id('a')), (32, 0, id('b')), (40, 0, id('c'))]);
+
+        // Precomposed and decomposed accents both become a letter and a mark
+        assert_eq!(placed(&builtin, "é", 8.0), [(0, 0, id('e')), (8, 0, id('\u{301}'))]);
+        assert_eq!(placed(&builtin, "e\u{301}", 8.0), placed(&builtin, "é", 8.0));
+        assert_eq!(placed(&builtin, "日", 8.0), [(0, 0, id('?'))]);
+
+        // Missing characters come from the fallback, unknown ones are the notdef of the first font
+        let font = Font::from_bytes(BLOCKS.to_vec()).unwrap().with_fallback(&builtin);
+        assert!(font.has_glyph('x') && !font.has_glyph('日'));
+        assert_eq!(placed(&font, "Ax日", 8.0), [(0, 0, 2), (5, 1, id('x')), (13, 0, 0)]);
+    }
+}
+
+#[cfg(all(test, feature = "image"))]
+mod snapshots {
+    use crate::{colors, math::Vec2, DrawableSurface};
+    use crate::snapshot::{assert_snapshot, reference, render};
+
+    use super::Font;
+
+    #[test]
+    fn truetype() {
+        let font = Font::from_bytes(include_bytes!("../../snapshots/fonts/blocks.ttf").to_vec()).unwrap();
+
+        let surface = render(128, 48, |ctx| {
+            ctx.background(colors::BLACK);
+            ctx.fill_color(colors::WHITE);
+
+            // The builtin font follows the height too
+            ctx.text("Hi", 0, 0, 8);
+
+            ctx.set_font(font);
+            ctx.text("AVIo", 20, 0, 10);
+            ctx.text("AV Io", 0, 14, 25);
+
+            ctx.push();
+            ctx.translate(Vec2::new(96, 8));
+            ctx.rotate(std::f32::consts::FRAC_PI_6);
+            ctx.text("oA", 0, 0, 20);
+            ctx.pop();
+        });
+
+        assert_snapshot(reference("truetype"), &surface, 0);
+    }
+
+    #[test]
+    fn unicode() {
+        let blocks = Font::from_bytes(include_bytes!("../../snapshots/fonts/blocks.ttf").to_vec()).unwrap();
+
+        let surface = render(128, 56, |ctx| {
+            ctx.background(colors::BLACK);
+            ctx.fill_color(colors::WHITE);
+
+            // Accents are marks drawn over the base letter, the bell takes no room
+            ctx.text("Zoë Ångström", 0, 0, 8);
+            ctx.text("Çà\tñ\u{7}ÿ", 0, 12, 16);
+
+            // Letters missing from blocks fall back to the builtin font, 日 is in neither
+            ctx.set_font(blocks.with_fallback(&ctx.font()));
+            ctx.text("AVxo日", 0, 34, 16);
+        });
+
+        assert_snapshot(reference("unicode"), &surface, 0);
+    }
+}
@@ -0,0 +1,502 @@
+//! Fonts made of pixels, read from BDF, PSF1, PSF2 or the baked FPFN format.
+//!
+//! FPFN, Fritz Preloaded FoNt, is what the preloader turns the other formats into. All numbers are big endian.
+//!
+//! | Size | Content |
+//! |------|---------|
+//! | 4    | Magic `FPFN` |
+//! | 1    | Version, currently 1 |
+//! | 1    | Ascent in pixels |
+//! | 1    | Descent in pixels |
+//! | 1    | Zero |
+//! | 4    | Codepoint of the glyph drawn for missing characters, u32::MAX for none |
+//! | 4    | Number of glyphs |
+//!
+//! Every glyph follows as its codepoint (u32), then as i16 its advance, left and top offset from the pen on the baseline,
+//! width and height, then the rows of the bitmap. A row takes a byte per 8 pixels, the leftmost pixel in the highest bit.
+
+use std::collections::HashMap;
+
+use super::FontLoadingError;
+
+pub(crate) const FPFN_MAGIC: [u8; 4] = *b"FPFN";
+
+const VERSION: u8 = 1;
+
+const PSF1_MAGIC: [u8; 2] = [0x36, 0x04];
+const PSF2_MAGIC: [u8; 4] = [0x72, 0xb5, 0x4a, 0x86];
+
+/// Glyph sizes and offsets are stored as i16 in FPFN
+const MAX_SIZE: i32 = i16::MAX as i32;
+
+/// Bitmap of one character, placed relative to the pen on the baseline
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct BitmapGlyph {
+    pub advance: i32,
+    pub left: i32,
+    pub top: i32,
+    pub width: i32,
+    pub height: i32,
+
+    /// Rows of (width + 7) / 8 bytes
+    rows: Vec<u8>,
+}
+
+impl BitmapGlyph {
+    fn stride(width: i32) -> usize {
+        (width.max(0) as usize).div_ceil(8)
+    }
+
+    pub fn lit(&self, x: i32, y: i32) -> bool {
+        self.rows[y as usize * Self::stride(self.width) + x as usize / 8] & 0x80 >> (x % 8) != 0
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct BitmapFont {
+    pub ascent: i32,
+    pub descent: i32,
+    pub glyphs: Vec<BitmapGlyph>,
+    chars: HashMap<char, u32>,
+
+    /// Glyph drawn for missing characters
+    default: Option<char>,
+}
+
+fn take<'a>(data: &mut &'a [u8], length: usize) -> Result<&'a [u8], FontLoadingError> {
+    let (taken, rest) = data.split_at_checked(length).ok_or(FontLoadingError::Truncated)?;
+    *data = rest;
+    Ok(taken)
+}
+
+fn take_u32(data: &mut &[u8]) -> Result<u32, FontLoadingError> {
+    Ok(u32::from_be_bytes(take(data, 4)?.try_into().unwrap()))
+}
+
+fn take_u32_le(data: &mut &[u8]) -> Result<u32, FontLoadingError> {
+    Ok(u32::from_le_bytes(take(data, 4)?.try_into().unwrap()))
+}
+
+fn take_i16(data: &mut &[u8]) -> Result<i32, FontLoadingError> {
+    Ok(i16::from_be_bytes(take(data, 2)?.try_into().unwrap()) as i32)
+}
+
+impl BitmapFont {
+    fn new(ascent: i32, descent: i32, default: Option<char>) -> Self {
+        Self { ascent, descent, glyphs: Vec::new(), chars: HashMap::new(), default }
+    }
+
+    fn push(&mut self, chars: impl IntoIterator<Item = char>, glyph: BitmapGlyph) {
+        let id = self.glyphs.len() as u32;
+        self.glyphs.push(glyph);
+
+        for char in chars {
+            self.chars.entry(char).or_insert(id);
+        }
+    }
+
+    /// Read any supported bitmap format, None if data is none of them
+    pub fn parse(data: &[u8]) -> Option<Result<Self, FontLoadingError>> {
+        let font = if data.starts_with(&FPFN_MAGIC) {
+            Self::decode(data)
+        } else if data.starts_with(&PSF2_MAGIC) {
+            Self::parse_psf2(data)
+        } else if data.starts_with(&PSF1_MAGIC) {
+            Self::parse_psf1(data)
+        } else if data.starts_with(b"STARTFONT") {
+            std::str::from_utf8(data).map_err(|_| FontLoadingError::InvalidData).and_then(Self::parse_bdf)
+        } else {
+            return None
+        };
+
+        Some(font.and_then(|font| {
+            if font.glyphs.is_empty() {
+                return Err(FontLoadingError::InvalidData)
+            }
+
+            // FPFN stores the metrics in a byte each
+            if u8::try_from(font.ascent).is_err() || u8::try_from(font.descent).is_err() {
+                return Err(FontLoadingError::InvalidData)
+            }
+
+            Ok(font)
+        }))
+    }
+
+    pub fn lookup(&self, char: char) -> Option<u32> {
+        self.chars.get(&char).copied()
+    }
+
+    /// The default character of the font, or '?', or else the first glyph
+    pub fn notdef(&self) -> u32 {
+        self.default.and_then(|char| self.lookup(char))
+            .or_else(|| self.lookup('?'))
+            .unwrap_or(0)
+    }
+
+    /// Serialize as FPFN, every character of a glyph gets its own copy
+    pub fn encode(&self) -> Vec<u8> {
+        let mut chars: Vec<_> = self.chars.iter().collect();
+        chars.sort();
+
+        let mut data = Vec::new();
+        data.extend_from_slice(&FPFN_MAGIC);
+        data.extend_from_slice(&[VERSION, self.ascent as u8, self.descent as u8, 0]);
+        data.extend_from_slice(&self.default.map_or(u32::MAX, |char| char as u32).to_be_bytes());
+        data.extend_from_slice(&(chars.len() as u32).to_be_bytes());
+
+        for (char, id) in chars {
+            let glyph = &self.glyphs[*id as usize];
+
+            data.extend_from_slice(&(*char as u32).to_be_bytes());
+
+            for value in [glyph.advance, glyph.left, glyph.top, glyph.width, glyph.height] {
+                data.extend_from_slice(&(value as i16).to_be_bytes());
+            }
+
+            data.extend_from_slice(&glyph.rows);
+        }
+
+        data
+    }
+
+    fn decode(data: &[u8]) -> Result<Self, FontLoadingError> {
+        let mut data = data;
+        take(&mut data, 4)?;
+
+        let [version, ascent, descent, _] = take(&mut data, 4)?.try_into().unwrap();
+        if version != VERSION {
+            return Err(FontLoadingError::UnknownVersion(version))
+        }
+
+        let default = char::from_u32(take_u32(&mut data)?);
+        let mut font = Self::new(ascent as i32, descent as i32, default);
+
+        for _ in 0..take_u32(&mut data)? {
+            let char = char::from_u32(take_u32(&mut data)?).ok_or(FontLoadingError::InvalidData)?;
+            let (advance, left, top) = (take_i16(&mut data)?, take_i16(&mut data)?, take_i16(&mut data)?);
+            let (width, height) = (take_i16(&mut data)?, take_i16(&mut data)?);
+
+            let rows = take(&mut data, BitmapGlyph::stride(width) * height.max(0) as usize)?.to_vec();
+            font.push([char], BitmapGlyph { advance, left, top, width, height, rows });
+        }
+
+        if !data.is_empty() {
+            return Err(FontLoadingError::InvalidData)
+        }
+
+        Ok(font)
+    }
+
+    /// Glyphs of a PC Screen Font, the baseline is at the bottom of the cell.
+    /// Without a unicode table, glyph n is character n
+    fn psf_glyphs(data: &[u8], count: usize, width: i32, height: i32, table: Option<Vec<Vec<char>>>) -> Result<Self, FontLoadingError> {
+        let size = BitmapGlyph::stride(width) * height as usize;
+        let mut font = Self::new(height, 0, None);
+        let mut data = data;
+
+        for index in 0..count {
+            let rows = take(&mut data, size)?.to_vec();
+            let glyph = BitmapGlyph { advance: width, left: 0, top: -height, width, height, rows };
+
+            match &table {
+                Some(table) => font.push(table.get(index).cloned().unwrap_or_default(), glyph),
+                None => font.push(char::from_u32(index as u32), glyph),
+            }
+        }
+
+        Ok(font)
+    }
+
+    fn parse_psf1(data: &[u8]) -> Result<Self, FontLoadingError> {
+        let mut data = data;
+        take(&mut data, 2)?;
+
+        let [mode, height] = take(&mut data, 2)?.try_into().unwrap();
+        let count = if mode & 1 != 0 { 512 } else { 256 };
+
+        let (glyphs, mut rest) = data.split_at_checked(count * height as usize).ok_or(FontLoadingError::Truncated)?;
+
+        // Little endian u16 codepoints per glyph ending in 0xffff, sequences after 0xfffe are skipped
+        let table = (mode & 2 != 0).then(|| {
+            (0..count).map(|_| {
+                let mut chars = Vec::new();
+                let mut sequence = false;
+
+                while let Ok(value) = take(&mut rest, 2).map(|value| u16::from_le_bytes([value[0], value[1]])) {
+                    match value {
+                        0xffff => break,
+                        0xfffe => sequence = true,
+                        value if !sequence => chars.extend(char::from_u32(value as u32)),
+                        _ => {},
+                    }
+                }
+
+                chars
+            }).collect()
+        });
+
+        Self::psf_glyphs(glyphs, count, 8, height as i32, table)
+    }
+
+    fn parse_psf2(data: &[u8]) -> Result<Self, FontLoadingError> {
+        let mut header = data;
+        take(&mut header, 4)?;
+
+        let version = take_u32_le(&mut header)?;
+        if version != 0 {
+            return Err(FontLoadingError::UnknownVersion(version as u8))
+        }
+
+        let header_size = take_u32_le(&mut header)? as usize;
+        let flags = take_u32_le(&mut header)?;
+        let count = take_u32_le(&mut header)? as usize;
+        let size = take_u32_le(&mut header)? as usize;
+        let height = take_u32_le(&mut header)?;
+        let width = take_u32_le(&mut header)?;
+
+        if !(1..=MAX_SIZE as u32).contains(&height) || !(1..=MAX_SIZE as u32).contains(&width) {
+            return Err(FontLoadingError::InvalidData)
+        }
+
+        let (height, width) = (height as i32, width as i32);
+
+        if BitmapGlyph::stride(width).checked_mul(height as usize) != Some(size) {
+            return Err(FontLoadingError::InvalidData)
+        }
+
+        let glyphs = data.get(header_size..).ok_or(FontLoadingError::Truncated)?;
+        let end = count.checked_mul(size).ok_or(FontLoadingError::InvalidData)?;
+        let table = glyphs.get(end..).ok_or(FontLoadingError::Truncated)?;
+
+        // UTF-8 characters per glyph ending in 0xff, sequences after 0xfe are skipped
+        let table = (flags & 1 != 0).then(|| {
+            table.split(|byte| *byte == 0xff).take(count).map(|entry| {
+                let single = entry.split(|byte| *byte == 0xfe).next().unwrap_or_default();
+                String::from_utf8_lossy(single).chars().filter(|char| *char != char::REPLACEMENT_CHARACTER).collect()
+            }).collect()
+        });
+
+        Self::psf_glyphs(glyphs, count, width, height, table)
+    }
+
+    fn parse_bdf(text: &str) -> Result<Self, FontLoadingError> {
+        let mut lines = text.lines().enumerate();
+        let invalid = |line: usize| FontLoadingError::InvalidBdf { line: line + 1 };
+
+        let number = |value: Option<&str>, line: usize| value.and_then(|value| value.parse::<i32>().ok()).ok_or(invalid(line));
+
+        // Width, height and the offsets of the bottom left corner, sizes can not be negative
+        let bounding_box = |words: &mut std::str::SplitWhitespace, line: usize| {
+            let mut bounds = [0; 4];
+
+            for (index, value) in bounds.iter_mut().enumerate() {
+                *value = number(words.next(), line)?;
+
+                let min = if index < 2 { 0 } else { -MAX_SIZE };
+                if !(min..=MAX_SIZE).contains(value) {
+                    return Err(invalid(line))
+                }
+            }
+
+            Ok(bounds)
+        };
+
+        let mut bounds = [0; 4];
+        let (mut ascent, mut descent, mut default) = (None, None, None);
+        let mut font = Self::new(0, 0, None);
+
+        while let Some((index, line)) = lines.next() {
+            let mut words = line.split_whitespace();
+
+            match words.next() {
+                Some("FONTBOUNDINGBOX") => bounds = bounding_box(&mut words, index)?,
+                Some("FONT_ASCENT") => ascent = Some(number(words.next(), index)?),
+                Some("FONT_DESCENT") => descent = Some(number(words.next(), index)?),
+                Some("DEFAULT_CHAR") => default = char::from_u32(number(words.next(), index)? as u32),
+                Some("STARTCHAR") => {
+                    let [mut advance, mut encoding] = [bounds[0], -1];
+                    let [mut width, mut height, mut left, mut bottom] = bounds;
+
+                    // Properties until the bitmap
+                    loop {
+                        let (index, line) = lines.next().ok_or(FontLoadingError::Truncated)?;
+                        let mut words = line.split_whitespace();
+
+                        match words.next() {
+                            Some("ENCODING") => encoding = number(words.next(), index)?,
+                            Some("DWIDTH") => advance = number(words.next(), index)?,
+                            Some("BBX") => [width, height, left, bottom] = bounding_box(&mut words, index)?,
+                            Some("BITMAP") => break,
+                            _ => {},
+                        }
+                    }
+
+                    let stride = BitmapGlyph::stride(width);
+                    let mut rows = Vec::new();
+
+                    for _ in 0..height {
+                        let (index, line) = lines.next().ok_or(FontLoadingError::Truncated)?;
+                        let line = line.trim();
+
+                        for byte in 0..stride {
+                            let hex = line.get(byte * 2..byte * 2 + 2).unwrap_or("00");
+                            rows.push(u8::from_str_radix(hex, 16).map_err(|_| invalid(index))?);
+                        }
+                    }
+
+                    // Glyphs without a codepoint have an encoding of -1
+                    if let Some(char) = u32::try_from(encoding).ok().and_then(char::from_u32) {
+                        font.push([char], BitmapGlyph { advance, left, top: -(bottom + height), width, height, rows });
+                    }
+                },
+                _ => {},
+            }
+        }
+
+        font.ascent = ascent.unwrap_or(bounds[1] + bounds[3]);
+        font.descent = descent.unwrap_or(-bounds[3]);
+        font.default = default;
+
+        Ok(font)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BitmapFont, BitmapGlyph, FontLoadingError};
+
+    const BDF: &str = "STARTFONT 2.1
+FONT -test-narrow
+SIZE 6 75 75
+FONTBOUNDINGBOX 4 6 0 -1
+STARTPROPERTIES 2
+FONT_ASCENT 5
+FONT_DESCENT 1
+ENDPROPERTIES
+CHARS 2
+STARTCHAR i
+ENCODING 105
+DWIDTH 2 0
+BBX 1 5 0 0
+BITMAP
+80
+00
+80
+80
+80
+ENDCHAR
+STARTCHAR M
+ENCODING 77
+DWIDTH 6 0
+BBX 5 5 0 0
+BITMAP
+88
+D8
+A8
+88
+88
+ENDCHAR
+ENDFONT
+";
+
+    fn psf2() -> Vec<u8> {
+        let mut data = vec![0x72, 0xb5, 0x4a, 0x86];
+
+        // Version, header size, unicode table, two glyphs of 2 rows by 10 pixels
+        for value in [0, 32, 1, 2, 4, 2, 10] {
+            data.extend_from_slice(&u32::to_le_bytes(value));
+        }
+
+        data.extend_from_slice(&[0xff, 0xc0, 0x80, 0x00, 0x00, 0x00, 0x00, 0x00]);
+        // á shares the glyph of a, the sequence b with an accent is skipped
+        data.extend_from_slice("aá".as_bytes());
+        data.push(0xff);
+        data.extend_from_slice("b".as_bytes());
+        data.push(0xfe);
+        data.extend_from_slice("b\u{301}".as_bytes());
+        data.push(0xff);
+        data
+    }
+
+    #[test]
+    fn formats() {
+        let bdf = BitmapFont::parse(BDF.as_bytes()).unwrap().unwrap();
+        assert_eq!((bdf.ascent, bdf.descent), (5, 1));
+
+        let i = &bdf.glyphs[bdf.lookup('i').unwrap() as usize];
+        assert_eq!((i.advance, i.top, i.width), (2, -5, 1));
+        assert!(i.lit(0, 0) && !i.lit(0, 1));
+
+        let m = &bdf.glyphs[bdf.lookup('M').unwrap() as usize];
+        assert!(m.lit(1, 1) && m.lit(4, 4) && !m.lit(2, 4));
+
+        // Missing default and '?' fall back to the first glyph
+        assert_eq!(bdf.notdef(), 0);
+
+        let psf = BitmapFont::parse(&psf2()).unwrap().unwrap();
+        assert_eq!(psf.lookup('a'), Some(0));
+        assert_eq!(psf.lookup('á'), Some(0));
+        assert_eq!(psf.lookup('b'), Some(1));
+
+        let a = &psf.glyphs[0];
+        assert_eq!((a.width, a.height, a.advance), (10, 2, 10));
+        assert!(a.lit(9, 0) && a.lit(0, 1) && !a.lit(9, 1));
+
+        let mut psf1 = vec![0x36, 0x04, 0, 1];
+        psf1.extend((0..256).map(|index| index as u8));
+        let psf1 = BitmapFont::parse(&psf1).unwrap().unwrap();
+        assert_eq!(psf1.glyphs[psf1.lookup('A').unwrap() as usize], BitmapGlyph { advance: 8, left: 0, top: -1, width: 8, height: 1, rows: vec![b'A'] });
+
+        assert!(BitmapFont::parse(b"not a font").is_none());
+        assert!(BitmapFont::parse(&psf2()[..36]).unwrap().is_err());
+    }
+
+    #[test]
+    fn absurd_sizes() {
+        // Sizes come from the file, they must be rejected before anything is allocated for them
+        let bdf = "STARTFONT 2.1\nSTARTCHAR A\nENCODING 65\nBBX 2000000000 2000000000 0 0\nBITMAP\n";
+        assert!(matches!(BitmapFont::parse(bdf.as_bytes()), Some(Err(FontLoadingError::InvalidBdf { line: 4 }))));
+
+        let bdf = BDF.replace("BBX 1 5 0 0", "BBX -1 5 0 0");
+        assert!(matches!(BitmapFont::parse(bdf.as_bytes()), Some(Err(FontLoadingError::InvalidBdf { line: 13 }))));
+
+        // The metrics do not fit in FPFN
+        let bdf = BDF.replace("FONT_ASCENT 5", "FONT_ASCENT 300");
+        assert!(matches!(BitmapFont::parse(bdf.as_bytes()), Some(Err(FontLoadingError::InvalidData))));
+
+        for (height, width) in [(u32::MAX, 16u32), (0, 8), (2, 0), (2, 0x8000)] {
+            let mut psf = psf2();
+            psf[24..28].copy_from_slice(&height.to_le_bytes());
+            psf[28..32].copy_from_slice(&width.to_le_bytes());
+            assert!(matches!(BitmapFont::parse(&psf), Some(Err(FontLoadingError::InvalidData))));
+        }
+    }
+
+    #[test]
+    fn encode_decode() {
+        let bdf = BitmapFont::parse(BDF.as_bytes()).unwrap().unwrap();
+        let decoded = BitmapFont::parse(&bdf.encode()).unwrap().unwrap();
+
+        assert_eq!((decoded.ascent, decoded.descent), (5, 1));
+        assert_eq!(decoded.glyphs[decoded.lookup('M').unwrap() as usize], bdf.glyphs[bdf.lookup('M').unwrap() as usize]);
+    }
+}
+
+#[cfg(all(test, feature = "image"))]
+mod snapshots {
+    use crate::{colors, DrawableSurface, Font};
+    use crate::snapshot::{assert_snapshot, reference, render};
+
+    #[test]
+    fn bitmap_fonts() {
+        let surface = render(128, 40, |ctx| {
+            ctx.background(colors::BLACK);
+            ctx.fill_color(colors::WHITE);
+            ctx.text("Tekenen!", 0, 0, 16);
+            ctx.text_with_font(&Font::c64(), "Tekenen!", 0, 20, 16);
+        });
+
+        assert_snapshot(reference("bitmap_fonts"), &surface, 0);
+    }
+}
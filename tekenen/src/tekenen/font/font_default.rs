@@ -1,945 +0,0 @@
-pub const FIRST_CHAR: char = '!';
-pub const LAST_CHAR: char = '~';
-
-pub const FONT: [[[char; 8]; 8]; 94] = [
-    [
-        [' ', ' ', ' ', '#', '#', ' ', ' ', ' '],
-        [' ', ' ', ' ', '#', '#', ' ', ' ', ' '],
-        [' ', ' ', ' ', '#', '#', ' ', ' ', ' '],
-        [' ', ' ', ' ', '#', '#', ' ', ' ', ' '],
-        [' ', ' ', ' ', '#', '#', ' ', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', '#', '#', ' ', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-    ],
-    [
-        [' ', '#', '#', ' ', '#', '#', ' ', ' '],
-        [' ', '#', '#', ' ', '#', '#', ' ', ' '],
-        [' ', '#', ' ', ' ', '#', ' ', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-    ],
-    [
-        [' ', ' ', '#', ' ', ' ', '#', ' ', ' '],
-        [' ', ' ', '#', ' ', ' ', '#', ' ', ' '],
-        [' ', '#', '#', '#', '#', '#', '#', ' '],
-        [' ', ' ', '#', ' ', ' ', '#', ' ', ' '],
-        [' ', '#', '#', '#', '#', '#', '#', ' '],
-        [' ', ' ', '#', ' ', ' ', '#', ' ', ' '],
-        [' ', ' ', '#', ' ', ' ', '#', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-    ],
-    [
-        [' ', ' ', '#', '#', '#', '#', ' ', ' '],
-        [' ', '#', ' ', ' ', '#', ' ', '#', ' '],
-        [' ', '#', ' ', ' ', '#', ' ', ' ', ' '],
-        [' ', ' ', '#', '#', '#', '#', ' ', ' '],
-        [' ', ' ', ' ', '#', ' ', ' ', '#', ' '],
-        [' ', '#', ' ', '#', ' ', ' ', '#', ' '],
-        [' ', ' ', '#', '#', '#', '#', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-    ],
-    [
-        [' ', '#', '#', ' ', ' ', ' ', '#', ' '],
-        [' ', '#', '#', ' ', ' ', '#', '#', ' '],
-        [' ', ' ', ' ', ' ', '#', '#', ' ', ' '],
-        [' ', ' ', ' ', '#', '#', ' ', ' ', ' '],
-        [' ', ' ', '#', '#', ' ', ' ', ' ', ' '],
-        [' ', '#', '#', ' ', ' ', '#', '#', ' '],
-        [' ', '#', ' ', ' ', ' ', '#', '#', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-    ],
-    [
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', '#', '#', ' ', ' ', ' '],
-        [' ', ' ', '#', ' ', ' ', '#', ' ', ' '],
-        [' ', ' ', '#', '#', '#', ' ', ' ', ' '],
-        [' ', '#', ' ', ' ', ' ', '#', ' ', '#'],
-        [' ', '#', ' ', ' ', ' ', '#', '#', ' '],
-        [' ', ' ', '#', '#', '#', ' ', ' ', '#'],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-    ],
-    [
-        [' ', ' ', ' ', '#', '#', ' ', ' ', ' '],
-        [' ', ' ', ' ', '#', '#', ' ', ' ', ' '],
-        [' ', ' ', ' ', '#', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-    ],
-    [
-        [' ', ' ', ' ', ' ', '#', ' ', ' ', ' '],
-        [' ', ' ', ' ', '#', ' ', ' ', ' ', ' '],
-        [' ', ' ', '#', ' ', ' ', ' ', ' ', ' '],
-        [' ', ' ', '#', ' ', ' ', ' ', ' ', ' '],
-        [' ', ' ', '#', ' ', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', '#', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', ' ', '#', ' ', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-    ],
-    [
-        [' ', ' ', ' ', '#', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', ' ', '#', ' ', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', '#', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', '#', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', '#', ' ', ' '],
-        [' ', ' ', ' ', ' ', '#', ' ', ' ', ' '],
-        [' ', ' ', ' ', '#', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-    ],
-    [
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', '#', ' ', ' ', ' ', ' '],
-        [' ', '#', '#', '#', '#', '#', ' ', ' '],
-        [' ', ' ', ' ', '#', ' ', ' ', ' ', ' '],
-        [' ', ' ', '#', ' ', '#', ' ', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-    ],
-    [
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', '#', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', '#', ' ', ' ', ' ', ' '],
-        [' ', '#', '#', '#', '#', '#', ' ', ' '],
-        [' ', ' ', ' ', '#', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', '#', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-    ],
-    [
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', ' ', '#', '#', ' ', ' '],
-        [' ', ' ', ' ', '#', '#', ' ', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-    ],
-    [
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-        [' ', '#', '#', '#', '#', '#', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-    ],
-    [
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', '#', '#', ' ', ' ', ' '],
-        [' ', ' ', ' ', '#', '#', ' ', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-    ],
-    [
-        [' ', ' ', ' ', ' ', ' ', ' ', '#', ' '],
-        [' ', ' ', ' ', ' ', ' ', '#', '#', ' '],
-        [' ', ' ', ' ', ' ', '#', '#', ' ', ' '],
-        [' ', ' ', ' ', '#', '#', ' ', ' ', ' '],
-        [' ', ' ', '#', '#', ' ', ' ', ' ', ' '],
-        [' ', '#', '#', ' ', ' ', ' ', ' ', ' '],
-        [' ', '#', ' ', ' ', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-    ],
-    [
-        [' ', ' ', '#', '#', '#', '#', ' ', ' '],
-        [' ', '#', ' ', ' ', ' ', '#', '#', ' '],
-        [' ', '#', ' ', ' ', '#', ' ', '#', ' '],
-        [' ', '#', ' ', '#', ' ', ' ', '#', ' '],
-        [' ', '#', ' ', '#', ' ', ' ', '#', ' '],
-        [' ', '#', '#', ' ', ' ', ' ', '#', ' '],
-        [' ', ' ', '#', '#', '#', '#', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-    ],
-    [
-        [' ', ' ', ' ', '#', ' ', ' ', ' ', ' '],
-        [' ', ' ', '#', '#', ' ', ' ', ' ', ' '],
-        [' ', '#', ' ', '#', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', '#', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', '#', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', '#', ' ', ' ', ' ', ' '],
-        [' ', ' ', '#', '#', '#', ' ', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-    ],
-    [
-        [' ', ' ', '#', '#', '#', '#', ' ', ' '],
-        [' ', '#', ' ', ' ', ' ', ' ', '#', ' '],
-        [' ', '#', ' ', ' ', ' ', '#', ' ', ' '],
-        [' ', ' ', ' ', ' ', '#', ' ', ' ', ' '],
-        [' ', ' ', ' ', '#', ' ', ' ', ' ', ' '],
-        [' ', ' ', '#', ' ', ' ', ' ', ' ', ' '],
-        [' ', '#', '#', '#', '#', '#', '#', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-    ],
-    [
-        [' ', '#', '#', '#', '#', '#', '#', ' '],
-        [' ', ' ', ' ', ' ', ' ', '#', ' ', ' '],
-        [' ', ' ', ' ', ' ', '#', ' ', ' ', ' '],
-        [' ', ' ', ' ', '#', '#', '#', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', '#', ' '],
-        [' ', '#', ' ', ' ', ' ', ' ', '#', ' '],
-        [' ', ' ', '#', '#', '#', '#', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-    ],
-    [
-        [' ', ' ', ' ', ' ', '#', '#', '#', ' '],
-        [' ', ' ', ' ', '#', ' ', ' ', '#', ' '],
-        [' ', ' ', '#', ' ', ' ', ' ', '#', ' '],
-        [' ', '#', '#', '#', '#', '#', '#', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', '#', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', '#', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', '#', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-    ],
-    [
-        [' ', '#', '#', '#', '#', '#', '#', ' '],
-        [' ', '#', ' ', ' ', ' ', ' ', ' ', ' '],
-        [' ', '#', ' ', ' ', ' ', ' ', ' ', ' '],
-        [' ', '#', '#', '#', '#', '#', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', '#', ' '],
-        [' ', '#', ' ', ' ', ' ', ' ', '#', ' '],
-        [' ', ' ', '#', '#', '#', '#', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-    ],
-    [
-        [' ', ' ', ' ', ' ', ' ', '#', '#', ' '],
-        [' ', ' ', ' ', '#', '#', ' ', ' ', ' '],
-        [' ', ' ', '#', ' ', ' ', ' ', ' ', ' '],
-        [' ', '#', '#', '#', '#', '#', ' ', ' '],
-        [' ', '#', ' ', ' ', ' ', ' ', '#', ' '],
-        [' ', '#', ' ', ' ', ' ', ' ', '#', ' '],
-        [' ', ' ', '#', '#', '#', '#', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-    ],
-    [
-        [' ', '#', '#', '#', '#', '#', '#', ' '],
-        [' ', '#', ' ', ' ', ' ', ' ', '#', ' '],
-        [' ', ' ', ' ', ' ', ' ', '#', ' ', ' '],
-        [' ', ' ', ' ', ' ', '#', ' ', ' ', ' '],
-        [' ', ' ', ' ', '#', ' ', ' ', ' ', ' '],
-        [' ', ' ', '#', ' ', ' ', ' ', ' ', ' '],
-        [' ', ' ', '#', ' ', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-    ],
-    [
-        [' ', ' ', '#', '#', '#', '#', ' ', ' '],
-        [' ', '#', ' ', ' ', ' ', ' ', '#', ' '],
-        [' ', '#', ' ', ' ', ' ', ' ', '#', ' '],
-        [' ', ' ', '#', '#', '#', '#', ' ', ' '],
-        [' ', '#', ' ', ' ', ' ', ' ', '#', ' '],
-        [' ', '#', ' ', ' ', ' ', ' ', '#', ' '],
-        [' ', ' ', '#', '#', '#', '#', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-    ],
-    [
-        [' ', ' ', '#', '#', '#', '#', ' ', ' '],
-        [' ', '#', ' ', ' ', ' ', ' ', '#', ' '],
-        [' ', '#', ' ', ' ', ' ', ' ', '#', ' '],
-        [' ', ' ', '#', '#', '#', '#', '#', ' '],
-        [' ', ' ', ' ', ' ', ' ', '#', ' ', ' '],
-        [' ', ' ', ' ', '#', '#', ' ', ' ', ' '],
-        [' ', '#', '#', ' ', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-    ],
-    [
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', '#', '#', ' ', ' ', ' '],
-        [' ', ' ', ' ', '#', '#', ' ', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', '#', '#', ' ', ' ', ' '],
-        [' ', ' ', ' ', '#', '#', ' ', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-    ],
-    [
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', ' ', '#', '#', ' ', ' '],
-        [' ', ' ', ' ', ' ', '#', '#', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', ' ', '#', '#', ' ', ' '],
-        [' ', ' ', ' ', '#', '#', ' ', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-    ],
-    [
-        [' ', ' ', ' ', ' ', '#', '#', ' ', ' '],
-        [' ', ' ', ' ', '#', ' ', ' ', ' ', ' '],
-        [' ', ' ', '#', ' ', ' ', ' ', ' ', ' '],
-        [' ', '#', ' ', ' ', ' ', ' ', ' ', ' '],
-        [' ', ' ', '#', ' ', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', '#', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', ' ', '#', '#', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-    ],
-    [
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-        [' ', '#', '#', '#', '#', '#', '#', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-        [' ', '#', '#', '#', '#', '#', '#', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-    ],
-    [
-        [' ', '#', '#', ' ', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', '#', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', ' ', '#', ' ', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', '#', ' ', ' '],
-        [' ', ' ', ' ', ' ', '#', ' ', ' ', ' '],
-        [' ', ' ', ' ', '#', ' ', ' ', ' ', ' '],
-        [' ', '#', '#', ' ', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-    ],
-    [
-        [' ', ' ', '#', '#', '#', '#', ' ', ' '],
-        [' ', '#', ' ', ' ', ' ', ' ', '#', ' '],
-        [' ', ' ', ' ', ' ', ' ', '#', ' ', ' '],
-        [' ', ' ', ' ', ' ', '#', ' ', ' ', ' '],
-        [' ', ' ', ' ', '#', '#', ' ', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', '#', '#', ' ', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-    ],
-    [
-        [' ', ' ', '#', '#', '#', '#', ' ', ' '],
-        [' ', '#', ' ', ' ', ' ', ' ', '#', ' '],
-        [' ', '#', ' ', ' ', '#', '#', '#', ' '],
-        [' ', '#', ' ', '#', ' ', ' ', '#', ' '],
-        [' ', '#', ' ', ' ', '#', '#', '#', ' '],
-        [' ', '#', ' ', ' ', ' ', ' ', ' ', ' '],
-        [' ', ' ', '#', '#', '#', '#', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-    ],
-    [
-        [' ', ' ', '#', '#', '#', '#', ' ', ' '],
-        [' ', '#', ' ', ' ', ' ', ' ', '#', ' '],
-        [' ', '#', ' ', ' ', ' ', ' ', '#', ' '],
-        [' ', '#', '#', '#', '#', '#', '#', ' '],
-        [' ', '#', ' ', ' ', ' ', ' ', '#', ' '],
-        [' ', '#', ' ', ' ', ' ', ' ', '#', ' '],
-        ['#', '#', '#', ' ', ' ', '#', '#', '#'],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-    ],
-    [
-        [' ', '#', '#', '#', '#', '#', ' ', ' '],
-        [' ', '#', ' ', ' ', ' ', ' ', '#', ' '],
-        [' ', '#', ' ', ' ', ' ', ' ', '#', ' '],
-        [' ', '#', '#', '#', '#', '#', '#', ' '],
-        [' ', '#', ' ', ' ', ' ', ' ', '#', ' '],
-        [' ', '#', ' ', ' ', ' ', ' ', '#', ' '],
-        [' ', '#', '#', '#', '#', '#', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-    ],
-    [
-        [' ', ' ', '#', '#', '#', '#', ' ', ' '],
-        [' ', '#', ' ', ' ', ' ', ' ', '#', ' '],
-        [' ', '#', ' ', ' ', ' ', ' ', ' ', ' '],
-        [' ', '#', ' ', ' ', ' ', ' ', ' ', ' '],
-        [' ', '#', ' ', ' ', ' ', ' ', ' ', ' '],
-        [' ', '#', ' ', ' ', ' ', ' ', '#', ' '],
-        [' ', ' ', '#', '#', '#', '#', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-    ],
-    [
-        [' ', '#', '#', '#', '#', '#', ' ', ' '],
-        [' ', '#', ' ', ' ', ' ', ' ', '#', ' '],
-        [' ', '#', ' ', ' ', ' ', ' ', '#', ' '],
-        [' ', '#', ' ', ' ', ' ', ' ', '#', ' '],
-        [' ', '#', ' ', ' ', ' ', ' ', '#', ' '],
-        [' ', '#', ' ', ' ', ' ', ' ', '#', ' '],
-        [' ', '#', '#', '#', '#', '#', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-    ],
-    [
-        [' ', '#', '#', '#', '#', '#', '#', ' '],
-        [' ', ' ', '#', ' ', ' ', ' ', '#', ' '],
-        [' ', ' ', '#', ' ', ' ', ' ', ' ', ' '],
-        [' ', ' ', '#', '#', '#', '#', ' ', ' '],
-        [' ', ' ', '#', ' ', ' ', ' ', ' ', ' '],
-        [' ', ' ', '#', ' ', ' ', ' ', '#', ' '],
-        [' ', '#', '#', '#', '#', '#', '#', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-    ],
-    [
-        [' ', '#', '#', '#', '#', '#', '#', ' '],
-        [' ', ' ', '#', ' ', ' ', ' ', '#', ' '],
-        [' ', ' ', '#', ' ', ' ', ' ', ' ', ' '],
-        [' ', ' ', '#', '#', '#', ' ', ' ', ' '],
-        [' ', ' ', '#', ' ', ' ', ' ', ' ', ' '],
-        [' ', ' ', '#', ' ', ' ', ' ', ' ', ' '],
-        [' ', '#', '#', '#', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-    ],
-    [
-        [' ', ' ', '#', '#', '#', '#', ' ', ' '],
-        [' ', '#', ' ', ' ', ' ', ' ', '#', ' '],
-        [' ', '#', ' ', ' ', ' ', ' ', ' ', ' '],
-        [' ', '#', ' ', ' ', ' ', '#', '#', '#'],
-        [' ', '#', ' ', ' ', ' ', ' ', '#', ' '],
-        [' ', '#', ' ', ' ', ' ', ' ', '#', ' '],
-        [' ', ' ', '#', '#', '#', '#', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-    ],
-    [
-        [' ', '#', ' ', ' ', ' ', ' ', '#', ' '],
-        [' ', '#', ' ', ' ', ' ', ' ', '#', ' '],
-        [' ', '#', ' ', ' ', ' ', ' ', '#', ' '],
-        [' ', '#', '#', '#', '#', '#', '#', ' '],
-        [' ', '#', ' ', ' ', ' ', ' ', '#', ' '],
-        [' ', '#', ' ', ' ', ' ', ' ', '#', ' '],
-        [' ', '#', ' ', ' ', ' ', ' ', '#', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-    ],
-    [
-        [' ', ' ', '#', '#', '#', ' ', ' ', ' '],
-        [' ', ' ', ' ', '#', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', '#', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', '#', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', '#', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', '#', ' ', ' ', ' ', ' '],
-        [' ', ' ', '#', '#', '#', ' ', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-    ],
-    [
-        [' ', ' ', '#', '#', '#', '#', '#', ' '],
-        [' ', '#', ' ', ' ', ' ', '#', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', '#', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', '#', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', '#', ' ', ' '],
-        [' ', '#', ' ', ' ', ' ', '#', ' ', ' '],
-        [' ', ' ', '#', '#', '#', ' ', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-    ],
-    [
-        [' ', '#', '#', '#', ' ', '#', '#', '#'],
-        [' ', ' ', '#', ' ', ' ', ' ', '#', ' '],
-        [' ', ' ', '#', ' ', ' ', '#', ' ', ' '],
-        [' ', ' ', '#', '#', '#', ' ', ' ', ' '],
-        [' ', ' ', '#', ' ', ' ', '#', ' ', ' '],
-        [' ', ' ', '#', ' ', ' ', ' ', '#', ' '],
-        [' ', '#', '#', '#', ' ', '#', '#', '#'],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-    ],
-    [
-        [' ', '#', '#', '#', ' ', ' ', ' ', ' '],
-        [' ', ' ', '#', ' ', ' ', ' ', ' ', ' '],
-        [' ', ' ', '#', ' ', ' ', ' ', ' ', ' '],
-        [' ', ' ', '#', ' ', ' ', ' ', ' ', ' '],
-        [' ', ' ', '#', ' ', ' ', ' ', ' ', ' '],
-        [' ', ' ', '#', ' ', ' ', ' ', '#', ' '],
-        [' ', '#', '#', '#', '#', '#', '#', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-    ],
-    [
-        [' ', '#', ' ', ' ', ' ', ' ', '#', ' '],
-        [' ', '#', '#', ' ', ' ', '#', '#', ' '],
-        [' ', '#', ' ', '#', '#', ' ', '#', ' '],
-        [' ', '#', ' ', ' ', ' ', ' ', '#', ' '],
-        [' ', '#', ' ', ' ', ' ', ' ', '#', ' '],
-        [' ', '#', ' ', ' ', ' ', ' ', '#', ' '],
-        ['#', '#', '#', ' ', ' ', '#', '#', '#'],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-    ],
-    [
-        [' ', '#', ' ', ' ', ' ', ' ', '#', ' '],
-        [' ', '#', '#', ' ', ' ', ' ', '#', ' '],
-        [' ', '#', ' ', '#', ' ', ' ', '#', ' '],
-        [' ', '#', ' ', ' ', '#', ' ', '#', ' '],
-        [' ', '#', ' ', ' ', ' ', '#', '#', ' '],
-        [' ', '#', ' ', ' ', ' ', ' ', '#', ' '],
-        [' ', '#', ' ', ' ', ' ', ' ', '#', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-    ],
-    [
-        [' ', ' ', '#', '#', '#', '#', ' ', ' '],
-        [' ', '#', ' ', ' ', ' ', ' ', '#', ' '],
-        [' ', '#', ' ', ' ', ' ', ' ', '#', ' '],
-        [' ', '#', ' ', ' ', ' ', ' ', '#', ' '],
-        [' ', '#', ' ', ' ', ' ', ' ', '#', ' '],
-        [' ', '#', ' ', ' ', ' ', ' ', '#', ' '],
-        [' ', ' ', '#', '#', '#', '#', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-    ],
-    [
-        [' ', '#', '#', '#', '#', '#', ' ', ' '],
-        [' ', ' ', '#', ' ', ' ', ' ', '#', ' '],
-        [' ', ' ', '#', ' ', ' ', ' ', '#', ' '],
-        [' ', ' ', '#', '#', '#', '#', ' ', ' '],
-        [' ', ' ', '#', ' ', ' ', ' ', ' ', ' '],
-        [' ', ' ', '#', ' ', ' ', ' ', ' ', ' '],
-        [' ', '#', '#', '#', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-    ],
-    [
-        [' ', ' ', '#', '#', '#', '#', ' ', ' '],
-        [' ', '#', ' ', ' ', ' ', ' ', '#', ' '],
-        [' ', '#', ' ', ' ', ' ', ' ', '#', ' '],
-        [' ', '#', ' ', ' ', ' ', ' ', '#', ' '],
-        [' ', '#', ' ', ' ', '#', ' ', '#', ' '],
-        [' ', '#', ' ', ' ', ' ', '#', ' ', ' '],
-        [' ', ' ', '#', '#', '#', ' ', '#', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-    ],
-    [
-        [' ', '#', '#', '#', '#', '#', ' ', ' '],
-        [' ', ' ', '#', ' ', ' ', ' ', '#', ' '],
-        [' ', ' ', '#', ' ', ' ', ' ', '#', ' '],
-        [' ', ' ', '#', '#', '#', '#', ' ', ' '],
-        [' ', ' ', '#', ' ', '#', ' ', ' ', ' '],
-        [' ', ' ', '#', ' ', ' ', '#', ' ', ' '],
-        [' ', '#', '#', '#', ' ', ' ', '#', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-    ],
-    [
-        [' ', ' ', '#', '#', '#', '#', ' ', ' '],
-        [' ', '#', ' ', ' ', ' ', ' ', '#', ' '],
-        [' ', '#', ' ', ' ', ' ', ' ', ' ', ' '],
-        [' ', ' ', '#', '#', '#', '#', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', '#', ' '],
-        [' ', '#', ' ', ' ', ' ', ' ', '#', ' '],
-        [' ', ' ', '#', '#', '#', '#', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-    ],
-    [
-        ['#', '#', '#', '#', '#', '#', '#', ' '],
-        ['#', ' ', ' ', '#', ' ', ' ', '#', ' '],
-        [' ', ' ', ' ', '#', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', '#', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', '#', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', '#', ' ', ' ', ' ', ' '],
-        [' ', ' ', '#', '#', '#', ' ', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-    ],
-    [
-        ['#', '#', '#', ' ', ' ', '#', '#', '#'],
-        [' ', '#', ' ', ' ', ' ', ' ', '#', ' '],
-        [' ', '#', ' ', ' ', ' ', ' ', '#', ' '],
-        [' ', '#', ' ', ' ', ' ', ' ', '#', ' '],
-        [' ', '#', ' ', ' ', ' ', ' ', '#', ' '],
-        [' ', '#', ' ', ' ', ' ', ' ', '#', ' '],
-        [' ', ' ', '#', '#', '#', '#', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-    ],
-    [
-        ['#', '#', '#', ' ', '#', '#', '#', ' '],
-        [' ', '#', ' ', ' ', ' ', '#', ' ', ' '],
-        [' ', '#', ' ', ' ', ' ', '#', ' ', ' '],
-        [' ', ' ', '#', ' ', '#', ' ', ' ', ' '],
-        [' ', ' ', '#', ' ', '#', ' ', ' ', ' '],
-        [' ', ' ', ' ', '#', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', '#', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-    ],
-    [
-        ['#', '#', '#', ' ', ' ', '#', '#', '#'],
-        [' ', '#', ' ', ' ', ' ', ' ', '#', ' '],
-        [' ', '#', ' ', ' ', ' ', ' ', '#', ' '],
-        [' ', '#', ' ', ' ', ' ', ' ', '#', ' '],
-        [' ', '#', ' ', '#', '#', ' ', '#', ' '],
-        [' ', '#', '#', ' ', ' ', '#', '#', ' '],
-        [' ', '#', ' ', ' ', ' ', ' ', '#', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-    ],
-    [
-        ['#', '#', ' ', ' ', ' ', '#', '#', ' '],
-        [' ', '#', ' ', ' ', ' ', '#', ' ', ' '],
-        [' ', ' ', '#', ' ', '#', ' ', ' ', ' '],
-        [' ', ' ', ' ', '#', ' ', ' ', ' ', ' '],
-        [' ', ' ', '#', ' ', '#', ' ', ' ', ' '],
-        [' ', '#', ' ', ' ', ' ', '#', ' ', ' '],
-        ['#', '#', ' ', ' ', ' ', '#', '#', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-    ],
-    [
-        ['#', '#', ' ', ' ', ' ', '#', '#', ' '],
-        [' ', '#', ' ', ' ', ' ', '#', ' ', ' '],
-        [' ', ' ', '#', ' ', '#', ' ', ' ', ' '],
-        [' ', ' ', ' ', '#', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', '#', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', '#', ' ', ' ', ' ', ' '],
-        [' ', ' ', '#', '#', '#', ' ', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-    ],
-    [
-        [' ', '#', '#', '#', '#', '#', '#', ' '],
-        [' ', '#', ' ', ' ', ' ', '#', ' ', ' '],
-        [' ', ' ', ' ', ' ', '#', ' ', ' ', ' '],
-        [' ', ' ', ' ', '#', ' ', ' ', ' ', ' '],
-        [' ', ' ', '#', ' ', ' ', ' ', ' ', ' '],
-        [' ', '#', ' ', ' ', ' ', ' ', '#', ' '],
-        [' ', '#', '#', '#', '#', '#', '#', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-    ],
-    [
-        [' ', ' ', '#', '#', '#', '#', ' ', ' '],
-        [' ', ' ', '#', ' ', ' ', ' ', ' ', ' '],
-        [' ', ' ', '#', ' ', ' ', ' ', ' ', ' '],
-        [' ', ' ', '#', ' ', ' ', ' ', ' ', ' '],
-        [' ', ' ', '#', ' ', ' ', ' ', ' ', ' '],
-        [' ', ' ', '#', ' ', ' ', ' ', ' ', ' '],
-        [' ', ' ', '#', '#', '#', '#', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-    ],
-    [
-        [' ', '#', ' ', ' ', ' ', ' ', ' ', ' '],
-        [' ', '#', '#', ' ', ' ', ' ', ' ', ' '],
-        [' ', ' ', '#', '#', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', '#', '#', ' ', ' ', ' '],
-        [' ', ' ', ' ', ' ', '#', '#', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', '#', '#', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', '#', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-    ],
-    [
-        [' ', ' ', '#', '#', '#', '#', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', '#', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', '#', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', '#', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', '#', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', '#', ' ', ' '],
-        [' ', ' ', '#', '#', '#', '#', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-    ],
-    [
-        [' ', ' ', ' ', '#', ' ', ' ', ' ', ' '],
-        [' ', ' ', '#', ' ', '#', ' ', ' ', ' '],
-        [' ', '#', ' ', ' ', ' ', '#', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-    ],
-    [
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-        [' ', '#', '#', '#', '#', '#', '#', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-    ],
-    [
-        [' ', ' ', ' ', '#', '#', ' ', ' ', ' '],
-        [' ', ' ', ' ', ' ', '#', '#', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-    ],
-    [
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-        [' ', ' ', '#', '#', '#', '#', ' ', ' '],
-        [' ', '#', ' ', ' ', ' ', ' ', '#', ' '],
-        [' ', '#', ' ', ' ', ' ', ' ', '#', ' '],
-        [' ', '#', ' ', ' ', ' ', '#', '#', ' '],
-        [' ', ' ', '#', '#', '#', ' ', '#', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-    ],
-    [
-        [' ', '#', ' ', ' ', ' ', ' ', ' ', ' '],
-        [' ', '#', ' ', ' ', ' ', ' ', ' ', ' '],
-        [' ', '#', '#', '#', '#', '#', ' ', ' '],
-        [' ', '#', ' ', ' ', ' ', ' ', '#', ' '],
-        [' ', '#', ' ', ' ', ' ', ' ', '#', ' '],
-        [' ', '#', ' ', ' ', ' ', ' ', '#', ' '],
-        [' ', '#', '#', '#', '#', '#', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-    ],
-    [
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-        [' ', ' ', '#', '#', '#', '#', ' ', ' '],
-        [' ', '#', ' ', ' ', ' ', ' ', '#', ' '],
-        [' ', '#', ' ', ' ', ' ', ' ', ' ', ' '],
-        [' ', '#', ' ', ' ', ' ', ' ', '#', ' '],
-        [' ', ' ', '#', '#', '#', '#', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-    ],
-    [
-        [' ', ' ', ' ', ' ', ' ', ' ', '#', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', '#', ' '],
-        [' ', ' ', '#', '#', '#', '#', '#', ' '],
-        [' ', '#', ' ', ' ', ' ', ' ', '#', ' '],
-        [' ', '#', ' ', ' ', ' ', ' ', '#', ' '],
-        [' ', '#', ' ', ' ', ' ', ' ', '#', ' '],
-        [' ', ' ', '#', '#', '#', '#', '#', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-    ],
-    [
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-        [' ', ' ', '#', '#', '#', '#', ' ', ' '],
-        [' ', '#', ' ', ' ', ' ', ' ', '#', ' '],
-        [' ', '#', '#', '#', '#', '#', '#', ' '],
-        [' ', '#', ' ', ' ', ' ', ' ', ' ', ' '],
-        [' ', ' ', '#', '#', '#', '#', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-    ],
-    [
-        [' ', ' ', ' ', ' ', '#', '#', '#', ' '],
-        [' ', ' ', ' ', '#', ' ', ' ', ' ', ' '],
-        [' ', ' ', '#', '#', '#', '#', ' ', ' '],
-        [' ', ' ', ' ', '#', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', '#', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', '#', ' ', ' ', ' ', ' '],
-        [' ', ' ', '#', '#', '#', ' ', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-    ],
-    [
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-        [' ', ' ', '#', '#', '#', '#', ' ', ' '],
-        [' ', '#', ' ', ' ', ' ', ' ', '#', ' '],
-        [' ', '#', ' ', ' ', ' ', ' ', '#', ' '],
-        [' ', ' ', '#', '#', '#', '#', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', '#', ' '],
-        [' ', ' ', '#', '#', '#', '#', ' ', ' '],
-    ],
-    [
-        [' ', '#', ' ', ' ', ' ', ' ', ' ', ' '],
-        [' ', '#', ' ', ' ', ' ', ' ', ' ', ' '],
-        [' ', '#', '#', '#', '#', '#', ' ', ' '],
-        [' ', '#', ' ', ' ', ' ', ' ', '#', ' '],
-        [' ', '#', ' ', ' ', ' ', ' ', '#', ' '],
-        [' ', '#', ' ', ' ', ' ', ' ', '#', ' '],
-        [' ', '#', ' ', ' ', ' ', ' ', '#', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-    ],
-    [
-        [' ', ' ', ' ', '#', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-        [' ', ' ', '#', '#', '#', ' ', ' ', ' '],
-        [' ', ' ', ' ', '#', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', '#', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', '#', ' ', ' ', ' ', ' '],
-        [' ', ' ', '#', '#', '#', ' ', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-    ],
-    [
-        [' ', ' ', ' ', '#', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-        [' ', ' ', '#', '#', '#', ' ', ' ', ' '],
-        [' ', ' ', ' ', ' ', '#', ' ', ' ', ' '],
-        [' ', ' ', ' ', ' ', '#', ' ', ' ', ' '],
-        [' ', ' ', ' ', ' ', '#', ' ', ' ', ' '],
-        [' ', ' ', ' ', ' ', '#', ' ', ' ', ' '],
-        [' ', ' ', '#', '#', ' ', ' ', ' ', ' '],
-    ],
-    [
-        ['#', '#', '#', ' ', ' ', ' ', ' ', ' '],
-        [' ', '#', ' ', ' ', ' ', ' ', ' ', ' '],
-        [' ', '#', ' ', ' ', '#', '#', ' ', ' '],
-        [' ', '#', ' ', '#', ' ', ' ', ' ', ' '],
-        [' ', '#', '#', ' ', '#', ' ', ' ', ' '],
-        [' ', '#', ' ', ' ', ' ', '#', ' ', ' '],
-        ['#', '#', '#', ' ', ' ', ' ', '#', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-    ],
-    [
-        [' ', ' ', '#', '#', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', '#', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', '#', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', '#', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', '#', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', '#', ' ', ' ', ' ', ' '],
-        [' ', ' ', '#', '#', '#', ' ', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-    ],
-    [
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-        [' ', ' ', '#', '#', ' ', '#', '#', ' '],
-        [' ', '#', ' ', ' ', '#', ' ', ' ', '#'],
-        [' ', '#', ' ', ' ', '#', ' ', ' ', '#'],
-        [' ', '#', ' ', ' ', ' ', ' ', ' ', '#'],
-        [' ', '#', ' ', ' ', ' ', ' ', ' ', '#'],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-    ],
-    [
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-        [' ', '#', '#', '#', '#', '#', ' ', ' '],
-        [' ', '#', ' ', ' ', ' ', ' ', '#', ' '],
-        [' ', '#', ' ', ' ', ' ', ' ', '#', ' '],
-        [' ', '#', ' ', ' ', ' ', ' ', '#', ' '],
-        [' ', '#', ' ', ' ', ' ', ' ', '#', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-    ],
-    [
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-        [' ', ' ', '#', '#', '#', '#', ' ', ' '],
-        [' ', '#', ' ', ' ', ' ', ' ', '#', ' '],
-        [' ', '#', ' ', ' ', ' ', ' ', '#', ' '],
-        [' ', '#', ' ', ' ', ' ', ' ', '#', ' '],
-        [' ', ' ', '#', '#', '#', '#', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-    ],
-    [
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-        [' ', '#', '#', '#', '#', '#', ' ', ' '],
-        [' ', '#', ' ', ' ', ' ', ' ', '#', ' '],
-        [' ', '#', ' ', ' ', ' ', ' ', '#', ' '],
-        [' ', '#', '#', '#', '#', '#', ' ', ' '],
-        [' ', '#', ' ', ' ', ' ', ' ', ' ', ' '],
-        ['#', '#', '#', ' ', ' ', ' ', ' ', ' '],
-    ],
-    [
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-        [' ', ' ', '#', '#', '#', '#', '#', ' '],
-        [' ', '#', ' ', ' ', ' ', ' ', '#', ' '],
-        [' ', '#', ' ', ' ', ' ', ' ', '#', ' '],
-        [' ', ' ', '#', '#', '#', '#', '#', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', '#', ' '],
-        [' ', ' ', ' ', ' ', ' ', '#', '#', '#'],
-    ],
-    [
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-        [' ', ' ', '#', '#', '#', '#', ' ', ' '],
-        [' ', '#', ' ', ' ', ' ', ' ', '#', ' '],
-        [' ', '#', ' ', ' ', ' ', ' ', ' ', ' '],
-        [' ', '#', ' ', ' ', ' ', ' ', ' ', ' '],
-        ['#', '#', '#', ' ', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-    ],
-    [
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-        [' ', ' ', '#', '#', '#', '#', ' ', ' '],
-        [' ', '#', '#', ' ', ' ', ' ', '#', ' '],
-        [' ', ' ', ' ', '#', '#', ' ', ' ', ' '],
-        [' ', '#', ' ', ' ', ' ', '#', '#', ' '],
-        [' ', ' ', '#', '#', '#', '#', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-    ],
-    [
-        [' ', ' ', ' ', '#', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', '#', ' ', ' ', ' ', ' '],
-        [' ', ' ', '#', '#', '#', '#', ' ', ' '],
-        [' ', ' ', ' ', '#', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', '#', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', '#', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', ' ', '#', '#', '#', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-    ],
-    [
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-        [' ', '#', ' ', ' ', ' ', ' ', '#', ' '],
-        [' ', '#', ' ', ' ', ' ', ' ', '#', ' '],
-        [' ', '#', ' ', ' ', ' ', ' ', '#', ' '],
-        [' ', '#', ' ', ' ', ' ', ' ', '#', ' '],
-        [' ', ' ', '#', '#', '#', '#', ' ', '#'],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-    ],
-    [
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-        [' ', '#', ' ', ' ', ' ', ' ', '#', ' '],
-        [' ', '#', ' ', ' ', ' ', ' ', '#', ' '],
-        [' ', '#', ' ', ' ', ' ', ' ', '#', ' '],
-        [' ', ' ', '#', ' ', ' ', '#', ' ', ' '],
-        [' ', ' ', ' ', '#', '#', ' ', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-    ],
-    [
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-        [' ', '#', ' ', ' ', '#', ' ', ' ', '#'],
-        [' ', '#', ' ', ' ', '#', ' ', ' ', '#'],
-        [' ', '#', ' ', ' ', '#', ' ', ' ', '#'],
-        [' ', '#', ' ', ' ', '#', ' ', ' ', '#'],
-        [' ', ' ', '#', '#', ' ', '#', '#', '#'],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-    ],
-    [
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-        [' ', '#', ' ', ' ', ' ', '#', ' ', ' '],
-        [' ', ' ', '#', ' ', '#', ' ', ' ', ' '],
-        [' ', ' ', ' ', '#', ' ', ' ', ' ', ' '],
-        [' ', ' ', '#', ' ', '#', ' ', ' ', ' '],
-        [' ', '#', ' ', ' ', ' ', '#', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-    ],
-    [
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-        [' ', '#', ' ', ' ', ' ', ' ', '#', ' '],
-        [' ', '#', ' ', ' ', ' ', ' ', '#', ' '],
-        [' ', '#', ' ', ' ', ' ', ' ', '#', ' '],
-        [' ', ' ', '#', '#', '#', '#', '#', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', '#', ' '],
-        [' ', ' ', '#', '#', '#', '#', ' ', ' '],
-    ],
-    [
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-        [' ', '#', '#', '#', '#', '#', '#', ' '],
-        [' ', ' ', ' ', ' ', ' ', '#', ' ', ' '],
-        [' ', ' ', ' ', '#', '#', ' ', ' ', ' '],
-        [' ', ' ', '#', ' ', ' ', ' ', ' ', ' '],
-        [' ', '#', '#', '#', '#', '#', '#', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-    ],
-    [
-        [' ', ' ', ' ', '#', '#', '#', ' ', ' '],
-        [' ', ' ', '#', ' ', ' ', ' ', ' ', ' '],
-        [' ', ' ', '#', ' ', ' ', ' ', ' ', ' '],
-        [' ', '#', ' ', ' ', ' ', ' ', ' ', ' '],
-        [' ', ' ', '#', ' ', ' ', ' ', ' ', ' '],
-        [' ', ' ', '#', ' ', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', '#', '#', '#', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-    ],
-    [
-        [' ', ' ', ' ', '#', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', '#', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', '#', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', '#', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', '#', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', '#', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', '#', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-    ],
-    [
-        [' ', ' ', '#', '#', '#', ' ', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', '#', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', '#', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', '#', ' '],
-        [' ', ' ', ' ', ' ', ' ', '#', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', '#', ' ', ' '],
-        [' ', ' ', '#', '#', '#', ' ', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-    ],
-    [
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-        [' ', ' ', '#', '#', ' ', ' ', '#', ' '],
-        [' ', '#', ' ', ' ', '#', '#', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-        [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
-    ],
-];
@@ -0,0 +1,432 @@
+use std::rc::Rc;
+
+use crate::{math::{Affine, Vec2}, shapes::rect::Rect};
+
+use super::{Color, ColorSpace, Pixel, Sampling, Surface};
+
+/// Colors in the lookup table of a gradient, enough that neighbouring entries differ by at most one step
+const GRADIENT_STEPS: usize = 256;
+
+/// What the inside of shapes and text is filled with
+#[derive(Debug, Clone, PartialEq)]
+pub enum Paint {
+    Solid(Pixel),
+    Gradient(Gradient),
+    Pattern(Pattern),
+}
+
+impl Paint {
+    /// The same paint for shapes that are moved through matrix
+    pub fn transformed(&self, matrix: &Affine) -> Paint {
+        match self {
+            Self::Solid(color) => Self::Solid(*color),
+            Self::Gradient(gradient) => Self::Gradient(Gradient { transform: *matrix * gradient.transform, ..gradient.clone() }),
+            Self::Pattern(pattern) => Self::Pattern(Pattern { transform: *matrix * pattern.transform, ..pattern.clone() }),
+        }
+    }
+
+    /// Color of every pixel, images in patterns are read with sampling
+    pub(crate) fn shader(&self, sampling: Sampling) -> Shader {
+        match self {
+            Self::Solid(color) => Shader::Solid(*color),
+            Self::Gradient(gradient) => {
+                let Some(inverse) = gradient.transform.inverse() else {
+                    return Shader::Solid([0, 0, 0, 0])
+                };
+
+                let steps = (0..GRADIENT_STEPS)
+                    .map(|step| gradient.color_at(step as f32 / (GRADIENT_STEPS - 1) as f32).to_pixel())
+                    .collect();
+
+                Shader::Gradient { inverse, kind: gradient.kind, spread: gradient.spread, steps }
+            },
+            Self::Pattern(pattern) => {
+                let Some(inverse) = pattern.transform.inverse() else {
+                    return Shader::Solid([0, 0, 0, 0])
+                };
+
+                if pattern.image.width() == 0 || pattern.image.height() == 0 {
+                    return Shader::Solid([0, 0, 0, 0])
+                }
+
+                // Extent of one destination pixel in the image, box sampling averages over it
+                let (across, down) = (inverse.transform_vector(Vec2::new(1.0, 0.0)), inverse.transform_vector(Vec2::new(0.0, 1.0)));
+                let footprint = Vec2::new(across.x.abs() + down.x.abs(), across.y.abs() + down.y.abs());
+
+                Shader::Pattern { inverse, image: Rc::clone(&pattern.image), spread: pattern.spread, sampling, footprint }
+            },
+        }
+    }
+}
+
+impl From<Pixel> for Paint {
+    fn from(color: Pixel) -> Self {
+        Self::Solid(color)
+    }
+}
+
+impl From<Color> for Paint {
+    fn from(color: Color) -> Self {
+        Self::Solid(color.to_pixel())
+    }
+}
+
+impl From<Gradient> for Paint {
+    fn from(gradient: Gradient) -> Self {
+        Self::Gradient(gradient)
+    }
+}
+
+impl From<Pattern> for Paint {
+    fn from(pattern: Pattern) -> Self {
+        Self::Pattern(pattern)
+    }
+}
+
+/// What happens outside of the range of a gradient, or outside of the image of a pattern
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SpreadMode {
+    /// Continue with the color at the edge
+    #[default]
+    Pad,
+
+    /// Start over from the other edge
+    Repeat,
+
+    /// Go back and forth, mirrored every other time
+    Reflect,
+}
+
+impl SpreadMode {
+    /// Position within 0..length
+    fn apply(self, position: f32, length: f32) -> f32 {
+        match self {
+            Self::Pad => position.clamp(0.0, length),
+            Self::Repeat => position.rem_euclid(length),
+            Self::Reflect => length - (position.rem_euclid(2.0 * length) - length).abs(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GradientKind {
+    /// Offset 0 at start and 1 at end, the color is the same across the line between them
+    Linear { start: Vec2<f32>, end: Vec2<f32> },
+
+    /// Offset 0 at center and 1 on the circle of radius
+    Radial { center: Vec2<f32>, radius: f32 },
+
+    /// Offset goes from 0 to 1 clockwise around center, starting at angle radians from the x axis
+    Conic { center: Vec2<f32>, angle: f32 },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ColorStop {
+    pub offset: f32,
+    pub color: Color,
+}
+
+/// Colors that change smoothly between stops
+#[derive(Debug, Clone, PartialEq)]
+pub struct Gradient {
+    pub kind: GradientKind,
+
+    /// Sorted by offset, stops at the same offset give a hard edge
+    stops: Vec<ColorStop>,
+
+    pub spread: SpreadMode,
+
+    /// Space colors between stops are mixed in, defaults to sRGB
+    pub space: ColorSpace,
+
+    /// From gradient coordinates to the coordinates of shapes
+    pub transform: Affine,
+}
+
+impl Gradient {
+    fn new(kind: GradientKind) -> Self {
+        Self {
+            kind,
+            stops: Vec::new(),
+            spread: SpreadMode::default(),
+            space: ColorSpace::default(),
+            transform: Affine::identity(),
+        }
+    }
+
+    pub fn linear(start: impl Into<Vec2<f32>>, end: impl Into<Vec2<f32>>) -> Self {
+        Self::new(GradientKind::Linear { start: start.into(), end: end.into() })
+    }
+
+    pub fn radial(center: impl Into<Vec2<f32>>, radius: f32) -> Self {
+        Self::new(GradientKind::Radial { center: center.into(), radius })
+    }
+
+    /// Clockwise on screen, angle in radians
+    pub fn conic(center: impl Into<Vec2<f32>>, angle: f32) -> Self {
+        Self::new(GradientKind::Conic { center: center.into(), angle })
+    }
+
+    /// Add a stop after the stops with the same offset
+    pub fn stop(mut self, offset: f32, color: impl Into<Color>) -> Self {
+        let index = self.stops.partition_point(|stop| stop.offset <= offset);
+        self.stops.insert(index, ColorStop { offset, color: color.into() });
+        self
+    }
+
+    pub fn with_spread(mut self, spread: SpreadMode) -> Self {
+        self.spread = spread;
+        self
+    }
+
+    pub fn with_space(mut self, space: ColorSpace) -> Self {
+        self.space = space;
+        self
+    }
+
+    pub fn with_transform(mut self, transform: Affine) -> Self {
+        self.transform = transform;
+        self
+    }
+
+    pub fn stops(&self) -> &[ColorStop] {
+        &self.stops
+    }
+
+    /// Color at offset from 0 to 1, transparent without stops
+    pub fn color_at(&self, offset: f32) -> Color {
+        let (Some(first), Some(last)) = (self.stops.first(), self.stops.last()) else {
+            return Color::default()
+        };
+
+        if offset <= first.offset {
+            return first.color
+        }
+
+        if offset >= last.offset {
+            return last.color
+        }
+
+        let after = self.stops.partition_point(|stop| stop.offset <= offset);
+        let (from, to) = (self.stops[after - 1], self.stops[after]);
+        let t = (offset - from.offset) / (to.offset - from.offset);
+
+        from.color.mix(to.color, t, self.space)
+    }
+}
+
+/// An image repeated over the plane, spread decides what is outside of it
+#[derive(Debug, Clone)]
+pub struct Pattern {
+    pub image: Rc<Surface>,
+
+    /// Defaults to Repeat, Pad repeats the outer pixels
+    pub spread: SpreadMode,
+
+    /// From image pixels to the coordinates of shapes
+    pub transform: Affine,
+}
+
+impl Pattern {
+    pub fn new(image: impl Into<Rc<Surface>>) -> Self {
+        Self {
+            image: image.into(),
+            spread: SpreadMode::Repeat,
+            transform: Affine::identity(),
+        }
+    }
+
+    pub fn with_spread(mut self, spread: SpreadMode) -> Self {
+        self.spread = spread;
+        self
+    }
+
+    pub fn with_transform(mut self, transform: Affine) -> Self {
+        self.transform = transform;
+        self
+    }
+}
+
+impl PartialEq for Pattern {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.image, &other.image) && self.spread == other.spread && self.transform == other.transform
+    }
+}
+
+/// A paint ready to be evaluated at every pixel of a fill
+pub(crate) enum Shader {
+    Solid(Pixel),
+
+    Gradient {
+        inverse: Affine,
+        kind: GradientKind,
+        spread: SpreadMode,
+        steps: Vec<Pixel>,
+    },
+
+    Pattern {
+        inverse: Affine,
+        image: Rc<Surface>,
+        spread: SpreadMode,
+        sampling: Sampling,
+        footprint: Vec2<f32>,
+    },
+}
+
+impl Shader {
+    /// Color of pixel x, y. Gradients are evaluated at the pixel center, which is at whole coordinates like for the shapes they fill.
+    /// Patterns are sampled in the middle of their texels
+    pub fn color_at(&self, x: i32, y: i32) -> Pixel {
+        match self {
+            Self::Solid(color) => *color,
+            Self::Gradient { inverse, kind, spread, steps } => {
+                let point = *inverse * Vec2::new(x as f32, y as f32);
+
+                let offset = match *kind {
+                    GradientKind::Linear { start, end } => {
+                        let direction = end - start;
+                        let length = direction.x * direction.x + direction.y * direction.y;
+
+                        if length == 0.0 {
+                            0.0
+                        } else {
+                            let from = point - start;
+                            (from.x * direction.x + from.y * direction.y) / length
+                        }
+                    },
+                    GradientKind::Radial { center, radius } => {
+                        let from = point - center;
+                        from.x.hypot(from.y) / radius
+                    },
+                    GradientKind::Conic { center, angle } => {
+                        let from = point - center;
+                        ((from.y.atan2(from.x) - angle) / std::f32::consts::TAU).rem_euclid(1.0)
+                    },
+                };
+
+                let offset = if offset.is_nan() { 0.0 } else { spread.apply(offset, 1.0) };
+                steps[(offset * (steps.len() - 1) as f32).round() as usize]
+            },
+            Self::Pattern { inverse, image, spread, sampling, footprint } => {
+                let point = *inverse * Vec2::new(x as f32 + 0.5, y as f32 + 0.5);
+                let (width, height) = (image.width(), image.height());
+
+                let at = Vec2::new(spread.apply(point.x, width as f32), spread.apply(point.y, height as f32));
+                sampling.sample(image, Rect::new(0, 0, width, height), at, *footprint)
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{colors, math::{Affine, Vec2}, DrawableSurface, Surface, SurfaceView};
+
+    use super::{Gradient, Paint, Pattern, SpreadMode};
+
+    #[test]
+    fn gradients() {
+        let gradient = Gradient::linear(Vec2::new(0.0, 0.0), Vec2::new(10.0, 0.0))
+            .stop(1.0, colors::BLUE)
+            .stop(0.0, colors::RED)
+            .stop(0.5, colors::WHITE)
+            .stop(0.5, colors::BLACK);
+
+        // Stops are sorted, the second stop at 0.5 comes after the first
+        assert_eq!(gradient.color_at(0.25).to_pixel(), [255, 128, 128, 255]);
+        assert_eq!(gradient.color_at(0.75).to_pixel(), [0, 0, 128, 255]);
+        assert_eq!(gradient.color_at(-1.0).to_pixel(), colors::RED);
+
+        let shader = Paint::from(gradient.clone()).shader(Default::default());
+        assert_eq!(shader.color_at(-5, 0), colors::RED);
+        assert_eq!(shader.color_at(12, 7), colors::BLUE);
+
+        let repeat = Paint::from(gradient.clone().with_spread(SpreadMode::Repeat)).shader(Default::default());
+        assert_eq!(repeat.color_at(10, 0), repeat.color_at(0, 0));
+
+        let reflect = Paint::from(gradient.with_spread(SpreadMode::Reflect)).shader(Default::default());
+        assert_eq!(reflect.color_at(11, 0), reflect.color_at(9, 0));
+
+        // A quarter turn clockwise from the x axis is straight down on screen
+        let conic = Gradient::conic(Vec2::new(0.0, 0.0), 0.0).stop(0.0, colors::BLACK).stop(0.25, colors::WHITE).stop(1.0, colors::WHITE);
+        assert_eq!(Paint::from(conic).shader(Default::default()).color_at(-1, 100), colors::WHITE);
+
+        let radial = Gradient::radial(Vec2::new(0.0, 0.0), 10.0).stop(0.5, colors::WHITE).stop(1.0, colors::BLACK);
+        let moved = Paint::from(radial).transformed(&Affine::translation(100.0, 0.0)).shader(Default::default());
+        assert_eq!(moved.color_at(99, 0), colors::WHITE);
+        assert_eq!(moved.color_at(80, 0), colors::BLACK);
+    }
+
+    #[test]
+    fn stops_on_pixel_centers() {
+        // From the center of the first pixel of the rect to the center of its last pixel
+        let view = SurfaceView::new(16, 1, Surface::new(16, 1).into());
+        view.fill_paint(Gradient::linear(Vec2::new(4.0, 0.0), Vec2::new(13.0, 0.0)).stop(0.0, colors::RED).stop(1.0, colors::BLUE));
+        view.rect(4, 0, 10, 1);
+
+        let row = view.get_surface().as_slice().to_vec();
+        assert_eq!((row[4], row[13]), (colors::RED, colors::BLUE));
+    }
+
+    #[test]
+    fn pattern() {
+        let image = Surface::from_pixels(2, 1, vec![colors::RED, colors::BLUE]);
+        let shader = Paint::from(Pattern::new(image.clone())).shader(Default::default());
+        assert_eq!([0, 1, 2, 3, -1].map(|x| shader.color_at(x, 5)), [colors::RED, colors::BLUE, colors::RED, colors::BLUE, colors::BLUE]);
+
+        let mirrored = Paint::from(Pattern::new(image.clone()).with_spread(SpreadMode::Reflect)).shader(Default::default());
+        assert_eq!([1, 2, 3, 4].map(|x| mirrored.color_at(x, 0)), [colors::BLUE, colors::BLUE, colors::RED, colors::RED]);
+
+        let padded = Paint::from(Pattern::new(image).with_spread(SpreadMode::Pad)).shader(Default::default());
+        assert_eq!(padded.color_at(10, 0), colors::BLUE);
+    }
+}
+
+#[cfg(all(test, feature = "image"))]
+mod snapshots {
+    use crate::{colors, math::Vec2, Color, DrawableSurface};
+    use crate::snapshot::{assert_snapshot, checkerboard, reference, render};
+
+    use super::{ColorSpace, Gradient, Pattern, SpreadMode};
+
+    #[test]
+    fn paint() {
+        let surface = render(128, 64, |ctx| {
+            ctx.background(colors::BLACK);
+
+            // Pad, repeat and reflect past the end of the gradient
+            for (row, spread) in [SpreadMode::Pad, SpreadMode::Repeat, SpreadMode::Reflect].into_iter().enumerate() {
+                let y = row as i32 * 10 + 2;
+                ctx.fill_paint(Gradient::linear(Vec2::new(12, 0), Vec2::new(28, 0)).stop(0.0, colors::RED).stop(1.0, colors::BLUE).with_spread(spread));
+                ctx.rect(2, y, 36, 8);
+            }
+
+            // The same stops mixed in OKLab instead of sRGB
+            ctx.fill_paint(Gradient::linear(Vec2::new(2, 0), Vec2::new(38, 0)).stop(0.0, colors::RED).stop(1.0, colors::BLUE).with_space(ColorSpace::Oklab));
+            ctx.rect(2, 32, 36, 8);
+
+            ctx.fill_paint(Gradient::radial(Vec2::new(56, 20), 16.0).stop(0.0, colors::WHITE).stop(0.5, colors::YELLOW).stop(1.0, Color::from(colors::YELLOW).with_alpha(0.0)));
+            ctx.circle(56, 20, 16);
+
+            ctx.fill_paint(Gradient::conic(Vec2::new(92, 20), 0.0).stop(0.0, colors::RED).stop(1.0 / 3.0, colors::LIME).stop(2.0 / 3.0, colors::BLUE).stop(1.0, colors::RED));
+            ctx.circle(92, 20, 16);
+
+            ctx.fill_paint(Pattern::new(checkerboard()));
+            ctx.rect(2, 44, 36, 18);
+
+            // Paints turn with the shape they fill
+            ctx.push();
+            ctx.translate(Vec2::new(84, 52));
+            ctx.rotate(std::f32::consts::FRAC_PI_4);
+            ctx.fill_paint(Gradient::linear(Vec2::new(-8, 0), Vec2::new(8, 0)).stop(0.0, colors::AQUA).stop(1.0, colors::FUCHSIA).with_spread(SpreadMode::Reflect));
+            ctx.rect(-16, -4, 32, 8);
+            ctx.pop();
+
+            // Text takes the fill too
+            ctx.fill_paint(Gradient::linear(Vec2::new(0, 44), Vec2::new(0, 60)).stop(0.0, colors::YELLOW).stop(1.0, colors::RED));
+            ctx.text("Aa", 44, 44, 16);
+        });
+
+        assert_snapshot(reference("paint"), &surface, 0);
+    }
+}
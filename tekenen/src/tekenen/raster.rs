@@ -0,0 +1,725 @@
+//! Rasterizers, they only compute which pixels to plot and with how much coverage (0..=255).
+//! Blending the color into the surface is up to the caller.
+
+use std::ops::RangeInclusive;
+
+use crate::shapes::{polygon::FillRule, rect::Rect};
+
+use super::{LineCap, LineJoin};
+
+/// Aliased shapes are sampled slightly up and to the left of the pixel center,
+/// so an edge running exactly through pixel centers is only drawn on one side
+const ALIASED_BIAS: f32 = 1.0 / 64.0;
+
+/// Sub scanlines per row of pixels when anti aliasing polygons
+const SUBSAMPLES: usize = 16;
+
+/// Corners whose miter would be longer than this many stroke widths are beveled instead
+pub const MITER_LIMIT: f32 = 4.0;
+
+fn coverage(value: f32) -> u8 {
+    (value.clamp(0.0, 1.0) * 255.0).round() as u8
+}
+
+/// Fractional part, also for negative numbers
+fn fraction(value: f32) -> f32 {
+    value - value.floor()
+}
+
+/// Pixels from..=to, shrunk to the pixels start..start + length
+pub(super) fn clamp_range(from: i32, to: i32, start: i32, length: i32) -> RangeInclusive<i32> {
+    from.max(start)..=to.min(start + length - 1)
+}
+
+/// Visit every pixel around the bounds inside the clip, distance gives the signed distance from a point to the edge, negative is inside
+fn distance_field(
+    (x_min, y_min): (f32, f32),
+    (x_max, y_max): (f32, f32),
+    anti_alias: bool,
+    clip: Rect<i32>,
+    distance: impl Fn(f32, f32) -> f32,
+    mut plot: impl FnMut(i32, i32, u8)
+) {
+    let rows = clamp_range((y_min - 1.0).floor() as i32, (y_max + 1.0).ceil() as i32, clip.position.y, clip.size.y);
+    let columns = clamp_range((x_min - 1.0).floor() as i32, (x_max + 1.0).ceil() as i32, clip.position.x, clip.size.x);
+
+    for y in rows {
+        for x in columns.clone() {
+            let value = if anti_alias {
+                coverage(0.5 - distance(x as f32, y as f32))
+            } else if distance(x as f32 - ALIASED_BIAS, y as f32 - ALIASED_BIAS) <= 0.0 {
+                255
+            } else {
+                0
+            };
+
+            if value > 0 {
+                plot(x, y, value);
+            }
+        }
+    }
+}
+
+/// Signed distance to the edge of an ellipse centered on the origin,
+/// exact for circles and a first order approximation for ellipses
+fn ellipse_distance(dx: f32, dy: f32, rx: f32, ry: f32) -> f32 {
+    if rx == ry {
+        return (dx * dx + dy * dy).sqrt() - rx
+    }
+
+    let f = dx * dx / (rx * rx) + dy * dy / (ry * ry) - 1.0;
+    let gradient = 2.0 * ((dx / (rx * rx)).powi(2) + (dy / (ry * ry)).powi(2)).sqrt();
+
+    if gradient == 0.0 { -rx.min(ry) } else { f / gradient }
+}
+
+/// Direction from a to b with length 1, zero if both points are the same
+fn direction(a: (f32, f32), b: (f32, f32)) -> (f32, f32) {
+    let (dx, dy) = (b.0 - a.0, b.1 - a.1);
+    let length = (dx * dx + dy * dy).sqrt();
+
+    if length == 0.0 { (0.0, 0.0) } else { (dx / length, dy / length) }
+}
+
+/// Bresenham, every pixel is plotted exactly once, including both endpoints
+pub fn line(x1: i32, y1: i32, x2: i32, y2: i32, mut plot: impl FnMut(i32, i32, u8)) {
+    let dx = (x2 - x1).abs();
+    let dy = -(y2 - y1).abs();
+    let sx = if x1 < x2 { 1 } else { -1 };
+    let sy = if y1 < y2 { 1 } else { -1 };
+
+    let mut error = dx + dy;
+    let (mut x, mut y) = (x1, y1);
+
+    loop {
+        plot(x, y, 255);
+
+        if x == x2 && y == y2 {
+            break
+        }
+
+        let e2 = 2 * error;
+
+        if e2 >= dy {
+            error += dy;
+            x += sx;
+        }
+
+        if e2 <= dx {
+            error += dx;
+            y += sy;
+        }
+    }
+}
+
+/// Xiaolin Wu's line, integer coordinates are the center of a pixel
+pub fn line_anti_aliased(x1: f32, y1: f32, x2: f32, y2: f32, mut plot: impl FnMut(i32, i32, u8)) {
+    let steep = (y2 - y1).abs() > (x2 - x1).abs();
+
+    let (mut x1, mut y1, mut x2, mut y2) = if steep {
+        (y1, x1, y2, x2)
+    } else {
+        (x1, y1, x2, y2)
+    };
+
+    if x1 > x2 {
+        (x1, x2) = (x2, x1);
+        (y1, y2) = (y2, y1);
+    }
+
+    let mut plot = |x: i32, y: i32, value: f32| {
+        if steep {
+            plot(y, x, coverage(value))
+        } else {
+            plot(x, y, coverage(value))
+        }
+    };
+
+    let dx = x2 - x1;
+    let gradient = if dx == 0.0 { 1.0 } else { (y2 - y1) / dx };
+
+    // Both endpoints are partially covered horizontally
+    let mut endpoint = |x: f32, y: f32, gap: f32| {
+        let x_end = x.round();
+        let y_end = y + gradient * (x_end - x);
+
+        let y_pixel = y_end.floor();
+        let part = y_end - y_pixel;
+
+        plot(x_end as i32, y_pixel as i32, (1.0 - part) * gap);
+        plot(x_end as i32, y_pixel as i32 + 1, part * gap);
+
+        (x_end as i32, y_end)
+    };
+
+    let (x_start, y_start) = endpoint(x1, y1, 1.0 - fraction(x1 + 0.5));
+    let (x_stop, _) = endpoint(x2, y2, fraction(x2 + 0.5));
+
+    let mut y = y_start + gradient;
+
+    for x in (x_start + 1)..x_stop {
+        let y_pixel = y.floor();
+        let part = y - y_pixel;
+
+        plot(x, y_pixel as i32, 1.0 - part);
+        plot(x, y_pixel as i32 + 1, part);
+
+        y += gradient;
+    }
+}
+
+/// Filled ellipse centered on the pixel (xc, yc)
+pub fn ellipse(xc: i32, yc: i32, rx: i32, ry: i32, clip: Rect<i32>, mut plot: impl FnMut(i32, i32, u8)) {
+    if rx <= 0 || ry <= 0 {
+        return
+    }
+
+    // Wide enough for any radius the pixels can be addressed with
+    let (rx2, ry2) = (rx as i128 * rx as i128, ry as i128 * ry as i128);
+    let columns = clamp_range(xc.saturating_sub(rx), xc.saturating_add(rx), clip.position.x, clip.size.x);
+
+    for y in clamp_range(yc.saturating_sub(ry), yc.saturating_add(ry), clip.position.y, clip.size.y) {
+        for x in columns.clone() {
+            let dx = x as i128 - xc as i128;
+            let dy = y as i128 - yc as i128;
+
+            if dx * dx * ry2 + dy * dy * rx2 <= rx2 * ry2 {
+                plot(x, y, 255);
+            }
+        }
+    }
+}
+
+/// Filled ellipse centered on the pixel (xc, yc), the edge is covered proportionally
+pub fn ellipse_anti_aliased(xc: f32, yc: f32, rx: f32, ry: f32, clip: Rect<i32>, plot: impl FnMut(i32, i32, u8)) {
+    if rx <= 0.0 || ry <= 0.0 {
+        return
+    }
+
+    distance_field(
+        (xc - rx, yc - ry),
+        (xc + rx, yc + ry),
+        true,
+        clip,
+        |x, y| ellipse_distance(x - xc, y - yc, rx, ry),
+        plot
+    )
+}
+
+/// Outline of an ellipse, width is centered on the edge
+pub fn ellipse_stroke((xc, yc): (f32, f32), (rx, ry): (f32, f32), width: f32, anti_alias: bool, clip: Rect<i32>, plot: impl FnMut(i32, i32, u8)) {
+    if rx <= 0.0 || ry <= 0.0 || width <= 0.0 {
+        return
+    }
+
+    let half = width / 2.0;
+
+    distance_field(
+        (xc - rx - half, yc - ry - half),
+        (xc + rx + half, yc + ry + half),
+        anti_alias,
+        clip,
+        |x, y| ellipse_distance(x - xc, y - yc, rx, ry).abs() - half,
+        plot
+    )
+}
+
+/// Filled circle with a fractional center and radius
+pub fn disc(xc: f32, yc: f32, radius: f32, anti_alias: bool, clip: Rect<i32>, plot: impl FnMut(i32, i32, u8)) {
+    distance_field(
+        (xc - radius, yc - radius),
+        (xc + radius, yc + radius),
+        anti_alias,
+        clip,
+        |x, y| ((x - xc).powi(2) + (y - yc).powi(2)).sqrt() - radius,
+        plot
+    )
+}
+
+/// Filled convex polygon, the points can be in either winding order
+pub fn convex_polygon(points: &[(f32, f32)], anti_alias: bool, clip: Rect<i32>, plot: impl FnMut(i32, i32, u8)) {
+    if points.len() < 3 {
+        return
+    }
+
+    let edges = || points.iter().zip(points.iter().cycle().skip(1));
+
+    // Twice the signed area, tells on which side of the edges the inside is
+    let area: f32 = edges().map(|(a, b)| a.0 * b.1 - b.0 * a.1).sum();
+
+    if area == 0.0 {
+        return
+    }
+
+    // Every edge as a point on the edge and the outward normal
+    let planes: Vec<_> = edges().filter_map(|(&a, &b)| {
+        let (dx, dy) = direction(a, b);
+
+        if dx == 0.0 && dy == 0.0 {
+            None
+        } else {
+            Some((a, (dy * area.signum(), -dx * area.signum())))
+        }
+    }).collect();
+
+    let x_min = points.iter().map(|p| p.0).fold(f32::INFINITY, f32::min);
+    let y_min = points.iter().map(|p| p.1).fold(f32::INFINITY, f32::min);
+    let x_max = points.iter().map(|p| p.0).fold(f32::NEG_INFINITY, f32::max);
+    let y_max = points.iter().map(|p| p.1).fold(f32::NEG_INFINITY, f32::max);
+
+    distance_field((x_min, y_min), (x_max, y_max), anti_alias, clip, |x, y| {
+        planes.iter()
+            .map(|((ax, ay), (nx, ny))| (x - ax) * nx + (y - ay) * ny)
+            .fold(f32::NEG_INFINITY, f32::max)
+    }, plot)
+}
+
+/// Filled polygon made of one or more closed contours, it may be concave or intersect itself.
+/// The fill rule decides what is inside, so contours can also cut holes.
+/// Every row is sampled at one or more horizontal scanlines, crossings with the edges split the scanline in spans.
+/// Only the rows and columns inside the clip are visited.
+pub fn polygon(contours: &[Vec<(f32, f32)>], fill_rule: FillRule, anti_alias: bool, clip: Rect<i32>, mut plot: impl FnMut(i32, i32, u8)) {
+    // Edges pointing down with their original direction, horizontal edges never cross a scanline
+    let edges: Vec<_> = contours.iter()
+        .filter(|points| points.len() > 2)
+        .flat_map(|points| points.iter().zip(points.iter().cycle().skip(1)))
+        .filter(|(a, b)| a.1 != b.1)
+        .map(|(&a, &b)| if a.1 < b.1 { (a, b, 1) } else { (b, a, -1) })
+        .collect();
+
+    if edges.is_empty() {
+        return
+    }
+
+    let points: Vec<_> = edges.iter().flat_map(|(a, b, _)| [*a, *b]).collect();
+
+    let rows = clamp_range(
+        points.iter().map(|p| p.1).fold(f32::INFINITY, f32::min).floor() as i32 - 1,
+        points.iter().map(|p| p.1).fold(f32::NEG_INFINITY, f32::max).ceil() as i32 + 1,
+        clip.position.y,
+        clip.size.y
+    );
+
+    let (x_min, x_max) = clamp_range(
+        points.iter().map(|p| p.0).fold(f32::INFINITY, f32::min).floor() as i32 - 1,
+        points.iter().map(|p| p.0).fold(f32::NEG_INFINITY, f32::max).ceil() as i32 + 1,
+        clip.position.x,
+        clip.size.x
+    ).into_inner();
+
+    if rows.is_empty() || x_min > x_max {
+        return
+    }
+
+    // Offsets of the scanlines from the center of the row
+    let samples: Vec<f32> = if anti_alias {
+        (0..SUBSAMPLES).map(|i| (i as f32 + 0.5) / SUBSAMPLES as f32 - 0.5).collect()
+    } else {
+        vec![-ALIASED_BIAS]
+    };
+
+    let weight = 1.0 / samples.len() as f32;
+    let mut row = vec![0.0f32; (x_max - x_min + 1) as usize];
+    let mut crossings = Vec::new();
+
+    for y in rows {
+        row.fill(0.0);
+
+        for offset in &samples {
+            let scanline = y as f32 + offset;
+
+            crossings.clear();
+
+            // Half open, so a vertex shared by two edges is only counted once
+            for &(a, b, winding) in &edges {
+                if scanline >= a.1 && scanline < b.1 {
+                    crossings.push((a.0 + (scanline - a.1) * (b.0 - a.0) / (b.1 - a.1), winding));
+                }
+            }
+
+            crossings.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+            let mut winding = 0;
+
+            for pair in crossings.windows(2) {
+                winding += pair[0].1;
+
+                let inside = match fill_rule {
+                    FillRule::NonZero => winding != 0,
+                    FillRule::EvenOdd => winding % 2 != 0,
+                };
+
+                if !inside {
+                    continue
+                }
+
+                let (start, end) = (pair[0].0, pair[1].0);
+
+                if anti_alias {
+                    // Pixel x spans from x - 0.5 to x + 0.5, add the overlap with the span
+                    for x in ((start + 0.5).floor() as i32).max(x_min)..=((end + 0.5).ceil() as i32).min(x_max) {
+                        let overlap = (end.min(x as f32 + 0.5) - start.max(x as f32 - 0.5)).max(0.0);
+                        row[(x - x_min) as usize] += overlap * weight;
+                    }
+                } else {
+                    for x in ((start + ALIASED_BIAS).ceil() as i32).max(x_min)..((end + ALIASED_BIAS).ceil() as i32).min(x_max + 1) {
+                        row[(x - x_min) as usize] = 1.0;
+                    }
+                }
+            }
+        }
+
+        for (index, value) in row.iter().enumerate() {
+            let value = coverage(*value);
+
+            if value > 0 {
+                plot(x_min + index as i32, y, value);
+            }
+        }
+    }
+}
+
+/// Stroke along the points, the width is centered on the line.
+/// The stroke is built from overlapping pieces, so the same pixel can be plotted more than once,
+/// the coverage is collected in the mask to avoid blending the overlaps twice. Only pixels inside the mask are visited.
+pub fn stroke(
+    points: &[(f32, f32)],
+    closed: bool,
+    width: f32,
+    cap: LineCap,
+    join: LineJoin,
+    anti_alias: bool,
+    mask: &mut Mask
+) {
+    let clip = mask.bounds();
+    let mut plot = |x, y, value| mask.plot(x, y, value);
+
+    let mut points = points.to_vec();
+    points.dedup();
+
+    if closed && points.len() > 1 && points.first() == points.last() {
+        points.pop();
+    }
+
+    if points.is_empty() || width <= 0.0 {
+        return
+    }
+
+    let count = if closed && points.len() > 1 { points.len() } else { points.len() - 1 };
+    let segments: Vec<_> = (0..count).map(|i| (points[i], points[(i + 1) % points.len()])).collect();
+
+    // Thin strokes look best with the regular line algorithms
+    if width <= 1.0 {
+        for &(a, b) in &segments {
+            if anti_alias {
+                line_anti_aliased(a.0, a.1, b.0, b.1, |x, y, value| plot(x, y, (value as f32 * width) as u8))
+            } else {
+                line(a.0.round() as i32, a.1.round() as i32, b.0.round() as i32, b.1.round() as i32, &mut plot)
+            }
+        }
+
+        if segments.is_empty() && cap != LineCap::Butt {
+            plot(points[0].0.round() as i32, points[0].1.round() as i32, coverage(width));
+        }
+
+        return
+    }
+
+    let half = width / 2.0;
+
+    // A single point only has caps
+    if segments.is_empty() {
+        let (x, y) = points[0];
+
+        match cap {
+            LineCap::Butt => {},
+            LineCap::Round => disc(x, y, half, anti_alias, clip, &mut plot),
+            LineCap::Square => convex_polygon(&[
+                (x - half, y - half), (x + half, y - half), (x + half, y + half), (x - half, y + half)
+            ], anti_alias, clip, &mut plot),
+        }
+
+        return
+    }
+
+    for (index, &(mut a, mut b)) in segments.iter().enumerate() {
+        let (dx, dy) = direction(a, b);
+
+        if !closed && cap == LineCap::Square {
+            if index == 0 {
+                a = (a.0 - dx * half, a.1 - dy * half);
+            }
+
+            if index == segments.len() - 1 {
+                b = (b.0 + dx * half, b.1 + dy * half);
+            }
+        }
+
+        let (nx, ny) = (-dy * half, dx * half);
+
+        convex_polygon(&[
+            (a.0 + nx, a.1 + ny), (b.0 + nx, b.1 + ny), (b.0 - nx, b.1 - ny), (a.0 - nx, a.1 - ny)
+        ], anti_alias, clip, &mut plot);
+    }
+
+    if !closed && cap == LineCap::Round {
+        let (first, last) = (points[0], points[points.len() - 1]);
+
+        disc(first.0, first.1, half, anti_alias, clip, &mut plot);
+        disc(last.0, last.1, half, anti_alias, clip, &mut plot);
+    }
+
+    // Every corner between two segments, a closed stroke also has a corner at the start
+    let corners = if closed { 0..segments.len() } else { 1..segments.len() };
+
+    for index in corners {
+        let (previous, next) = (segments[(index + segments.len() - 1) % segments.len()], segments[index]);
+        let vertex = next.0;
+
+        let d0 = direction(previous.0, previous.1);
+        let d1 = direction(next.0, next.1);
+
+        let cross = d0.0 * d1.1 - d0.1 * d1.0;
+        let dot = d0.0 * d1.0 + d0.1 * d1.1;
+
+        // Continuing straight, nothing to fill
+        if cross.abs() < 1e-6 && dot > 0.0 {
+            continue
+        }
+
+        if join == LineJoin::Round {
+            disc(vertex.0, vertex.1, half, anti_alias, clip, &mut plot);
+            continue
+        }
+
+        // Offsets to the outer edges of both segments
+        let side = if cross > 0.0 { -half } else { half };
+        let o0 = (-d0.1 * side, d0.0 * side);
+        let o1 = (-d1.1 * side, d1.0 * side);
+
+        let p0 = (vertex.0 + o0.0, vertex.1 + o0.1);
+        let p1 = (vertex.0 + o1.0, vertex.1 + o1.1);
+
+        // Cosine of half the angle between the offsets
+        let bisector = (o0.0 + o1.0, o0.1 + o1.1);
+        let length = (bisector.0 * bisector.0 + bisector.1 * bisector.1).sqrt();
+        let cos_half = length / width;
+
+        if join == LineJoin::Miter && cos_half > 0.0 && 1.0 / cos_half <= MITER_LIMIT {
+            let distance = half / cos_half / length;
+            let miter = (vertex.0 + bisector.0 * distance, vertex.1 + bisector.1 * distance);
+
+            convex_polygon(&[vertex, p0, miter, p1], anti_alias, clip, &mut plot);
+        } else {
+            convex_polygon(&[vertex, p0, p1], anti_alias, clip, &mut plot);
+        }
+    }
+}
+
+/// Coverage buffer, plotting the same pixel again keeps the highest coverage
+pub struct Mask {
+    x: i32,
+    y: i32,
+    width: i32,
+    height: i32,
+    coverage: Vec<u8>,
+}
+
+impl Mask {
+    /// Mask for the pixels x_min..x_max and y_min..y_max, pixels outside are ignored
+    pub fn new(x_min: i32, y_min: i32, x_max: i32, y_max: i32) -> Self {
+        let width = (x_max - x_min).max(0);
+        let height = (y_max - y_min).max(0);
+
+        Self {
+            x: x_min,
+            y: y_min,
+            width,
+            height,
+            coverage: vec![0; (width * height) as usize],
+        }
+    }
+
+    /// The pixels the mask holds
+    pub fn bounds(&self) -> Rect<i32> {
+        Rect::new(self.x, self.y, self.width, self.height)
+    }
+
+    pub fn plot(&mut self, x: i32, y: i32, value: u8) {
+        let (x, y) = (x - self.x, y - self.y);
+
+        if x < 0 || y < 0 || x >= self.width || y >= self.height {
+            return
+        }
+
+        let index = (y * self.width + x) as usize;
+        self.coverage[index] = self.coverage[index].max(value);
+    }
+
+    /// Visit every pixel with some coverage
+    pub fn for_each(&self, mut plot: impl FnMut(i32, i32, u8)) {
+        for (index, &value) in self.coverage.iter().enumerate() {
+            if value > 0 {
+                let index = index as i32;
+                plot(self.x + index % self.width, self.y + index / self.width, value);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{shapes::rect::Rect, LineCap, LineJoin};
+
+    use super::{ellipse, polygon, stroke, FillRule, Mask};
+
+    fn covered(points: &[(f32, f32)], closed: bool, width: f32, cap: LineCap) -> Vec<(i32, i32)> {
+        let mut mask = Mask::new(-10, -10, 20, 20);
+        stroke(points, closed, width, cap, LineJoin::Miter, false, &mut mask);
+
+        let mut pixels = Vec::new();
+        mask.for_each(|x, y, _| pixels.push((x, y)));
+        pixels
+    }
+
+    #[test]
+    fn thick_lines_cover_whole_pixels() {
+        // Edges through pixel centers are biased, the pixel below and to the right of the edge is covered
+        let pixels = covered(&[(0.0, 0.0), (3.0, 0.0)], false, 2.0, LineCap::Butt);
+        assert_eq!(pixels, vec![(1, 0), (2, 0), (3, 0), (1, 1), (2, 1), (3, 1)]);
+
+        let pixels = covered(&[(0.0, 0.0), (2.0, 0.0)], false, 3.0, LineCap::Square);
+        assert_eq!(pixels.len(), 5 * 3);
+    }
+
+    #[test]
+    fn closed_outline_has_no_gaps() {
+        let square = [(0.0, 0.0), (4.0, 0.0), (4.0, 4.0), (0.0, 4.0)];
+
+        for width in [1.0, 3.0] {
+            let pixels = covered(&square, true, width, LineCap::Butt);
+            let border = width as i32 / 2;
+
+            for y in -border..=4 + border {
+                for x in -border..=4 + border {
+                    let inside = (border + 1..4 - border).contains(&x) && (border + 1..4 - border).contains(&y);
+                    assert_eq!(pixels.contains(&(x, y)), !inside, "width {width} at [{x}, {y}]");
+                }
+            }
+        }
+    }
+    #[test]
+    fn huge_shapes_only_visit_the_clip() {
+        let huge = vec![(-1e9, -1e9), (1e9, -1e9), (1e9, 1e9), (-1e9, 1e9)];
+        let clip = Rect::new(2, 3, 4, 5);
+
+        for anti_alias in [false, true] {
+            let mut pixels = Vec::new();
+            polygon(std::slice::from_ref(&huge), FillRule::NonZero, anti_alias, clip, |x, y, _| pixels.push((x, y)));
+            assert_eq!(pixels.len(), 4 * 5);
+            assert!(pixels.iter().all(|&(x, y)| (2..6).contains(&x) && (3..8).contains(&y)));
+
+            // The mask only holds the clip, but the stroke is much larger
+            let mut mask = Mask::new(2, 3, 6, 8);
+            stroke(&[(-1e9, 5.0), (1e9, 5.0)], false, 2.0, LineCap::Round, LineJoin::Round, anti_alias, &mut mask);
+
+            let mut pixels = Vec::new();
+            mask.for_each(|x, y, _| pixels.push((x, y)));
+            assert_eq!(pixels.len(), 4 * if anti_alias { 3 } else { 2 });
+        }
+
+        let mut pixels = Vec::new();
+        ellipse(0, 0, 1_000_000_000, 1_000_000_000, clip, |x, y, _| pixels.push((x, y)));
+        assert_eq!(pixels.len(), 4 * 5);
+    }
+}
+
+#[cfg(all(test, feature = "image"))]
+mod snapshots {
+    use crate::{colors, DrawableSurface, LineCap, LineJoin};
+    use crate::snapshot::{assert_snapshot, reference, render};
+
+    #[test]
+    fn line() {
+        let surface = render(64, 64, |ctx| {
+            ctx.background(colors::BLACK);
+            ctx.fill_color(colors::WHITE);
+            ctx.line(2, 2, 61, 20);
+            ctx.line(2, 61, 20, 2);
+            ctx.line(32, 10, 32, 60);
+            ctx.line(10, 40, 60, 40);
+            ctx.line(60, 62, 4, 30);
+        });
+
+        assert_snapshot(reference("line"), &surface, 0);
+    }
+
+    #[test]
+    fn circle() {
+        let surface = render(64, 64, |ctx| {
+            ctx.background(colors::BLACK);
+            ctx.fill_color(colors::RED);
+            ctx.circle(20, 20, 15);
+            ctx.fill_color(colors::GREEN);
+            ctx.circle(45, 45, 8);
+            ctx.circle(60, 10, 1);
+        });
+
+        assert_snapshot(reference("circle"), &surface, 0);
+    }
+
+    #[test]
+    fn anti_aliased() {
+        let surface = render(64, 64, |ctx| {
+            ctx.anti_alias(true);
+            ctx.background(colors::BLACK);
+            ctx.fill_color(colors::WHITE);
+            ctx.line(2, 2, 61, 20);
+            ctx.line(2, 61, 20, 2);
+            ctx.fill_color(colors::RED);
+            ctx.circle(20, 40, 12);
+            ctx.fill_color(colors::GREEN);
+            ctx.ellipse(46, 44, 14, 6);
+        });
+
+        assert_snapshot(reference("anti_aliased"), &surface, 0);
+    }
+
+    #[test]
+    fn stroke() {
+        let surface = render(96, 64, |ctx| {
+            ctx.background(colors::BLACK);
+
+            ctx.stroke_color(colors::WHITE);
+            ctx.stroke_width(6.0);
+
+            for (y, cap) in [(8, LineCap::Butt), (20, LineCap::Round), (32, LineCap::Square)] {
+                ctx.line_cap(cap);
+                ctx.line(10, y, 40, y);
+            }
+
+            ctx.no_fill();
+            ctx.stroke_width(4.0);
+
+            for (x, join) in [(52, LineJoin::Miter), (66, LineJoin::Round), (80, LineJoin::Bevel)] {
+                ctx.line_join(join);
+                ctx.rect(x, 6, 10, 26);
+            }
+
+            ctx.fill_color(colors::RED);
+            ctx.stroke_color(colors::GREEN);
+            ctx.stroke_width(1.0);
+            ctx.rect(6, 44, 16, 14);
+
+            ctx.anti_alias(true);
+            ctx.stroke_width(3.0);
+            ctx.circle(40, 50, 9);
+
+            ctx.no_fill();
+            ctx.line_cap(LineCap::Round);
+            ctx.line(58, 58, 90, 42);
+        });
+
+        assert_snapshot(reference("stroke"), &surface, 0);
+    }
+}
@@ -0,0 +1,258 @@
+use crate::{math::Vec2, shapes::rect::Rect};
+
+use super::{Pixel, Surface};
+
+/// How the pixels of an image are picked when it is drawn scaled or transformed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Sampling {
+    /// Closest source pixel, keeps pixel art sharp
+    #[default]
+    Nearest,
+
+    /// Linear mix of the four closest source pixels
+    Bilinear,
+
+    /// Catmull-Rom spline through the sixteen closest source pixels, sharper than bilinear
+    Bicubic,
+
+    /// Average of every source pixel under the destination pixel, for downscaling.
+    /// Gives the same result as a mipmap, without building one first
+    Box,
+}
+
+/// Color with premultiplied alpha, so transparent pixels do not darken their neighbours
+type Premultiplied = [f32; 4];
+
+fn premultiply(color: Pixel) -> Premultiplied {
+    let alpha = color[3] as f32 / 255.0;
+    [color[0] as f32 * alpha, color[1] as f32 * alpha, color[2] as f32 * alpha, color[3] as f32]
+}
+
+fn unpremultiply(color: Premultiplied) -> Pixel {
+    let alpha = color[3].clamp(0.0, 255.0);
+
+    if alpha == 0.0 {
+        return [0, 0, 0, 0]
+    }
+
+    let channel = |value: f32| (value * 255.0 / alpha).round().clamp(0.0, 255.0) as u8;
+    [channel(color[0]), channel(color[1]), channel(color[2]), alpha.round() as u8]
+}
+
+fn add_weighted(sum: &mut Premultiplied, color: Premultiplied, weight: f32) {
+    for (sum, channel) in sum.iter_mut().zip(color) {
+        *sum += channel * weight
+    }
+}
+
+/// Catmull-Rom weights of the four pixels around a sample, t is the distance past the second one
+fn cubic_weights(t: f32) -> [f32; 4] {
+    let (t2, t3) = (t * t, t * t * t);
+
+    [
+        (-t3 + 2.0 * t2 - t) / 2.0,
+        (3.0 * t3 - 5.0 * t2 + 2.0) / 2.0,
+        (-3.0 * t3 + 4.0 * t2 + t) / 2.0,
+        (t3 - t2) / 2.0,
+    ]
+}
+
+/// Box sampling reads at most this many source pixels in each direction,
+/// near singular transformations would otherwise average whole images for every destination pixel
+const MAX_BOX_FOOTPRINT: f32 = 64.0;
+
+/// Overlap between the pixel starting at start and the span from..to
+fn overlap(start: i32, from: f32, to: f32) -> f32 {
+    (to.min(start as f32 + 1.0) - from.max(start as f32)).max(0.0)
+}
+
+impl Sampling {
+    /// Color of image around at, in image coordinates where pixel x, y spans x..x+1.
+    /// Only pixels inside source are read, the edges are repeated outward.
+    /// Footprint is the size of a destination pixel in source pixels, used by box sampling.
+    pub(crate) fn sample(&self, image: &Surface, source: Rect<i32>, at: Vec2<f32>, footprint: Vec2<f32>) -> Pixel {
+        let (x_min, y_min) = (source.position.x, source.position.y);
+        let (x_max, y_max) = (x_min + source.size.x - 1, y_min + source.size.y - 1);
+
+        let texel = |x: i32, y: i32| {
+            let (x, y) = (x.clamp(x_min, x_max), y.clamp(y_min, y_max));
+            image.pixels[(y * image.width() + x) as usize]
+        };
+
+        match self {
+            Self::Nearest => texel(at.x.floor() as i32, at.y.floor() as i32),
+            Self::Bilinear => {
+                let (u, v) = (at.x - 0.5, at.y - 0.5);
+                let (x, y) = (u.floor(), v.floor());
+                let (tx, ty) = (u - x, v - y);
+                let (x, y) = (x as i32, y as i32);
+
+                let mut sum = [0.0; 4];
+                add_weighted(&mut sum, premultiply(texel(x, y)), (1.0 - tx) * (1.0 - ty));
+                add_weighted(&mut sum, premultiply(texel(x + 1, y)), tx * (1.0 - ty));
+                add_weighted(&mut sum, premultiply(texel(x, y + 1)), (1.0 - tx) * ty);
+                add_weighted(&mut sum, premultiply(texel(x + 1, y + 1)), tx * ty);
+
+                unpremultiply(sum)
+            },
+            Self::Bicubic => {
+                let (u, v) = (at.x - 0.5, at.y - 0.5);
+                let (x, y) = (u.floor(), v.floor());
+                let (wx, wy) = (cubic_weights(u - x), cubic_weights(v - y));
+                let (x, y) = (x as i32, y as i32);
+
+                let mut sum = [0.0; 4];
+
+                for (j, wy) in wy.iter().enumerate() {
+                    for (i, wx) in wx.iter().enumerate() {
+                        add_weighted(&mut sum, premultiply(texel(x + i as i32 - 1, y + j as i32 - 1)), wx * wy);
+                    }
+                }
+
+                // The spline overshoots near hard edges, keep the color valid
+                let alpha = sum[3].clamp(0.0, 255.0);
+                let [r, g, b, _] = sum.map(|channel| channel.clamp(0.0, alpha));
+
+                unpremultiply([r, g, b, alpha])
+            },
+            Self::Box => {
+                // Never smaller than a source pixel, so upscaling still mixes neighbours smoothly
+                let half = Vec2::new(footprint.x.clamp(1.0, MAX_BOX_FOOTPRINT), footprint.y.clamp(1.0, MAX_BOX_FOOTPRINT)) / 2.0;
+                let (from, to) = (at - half, at + half);
+
+                // Only the part of the footprint on the source is read, entirely outside the edge is repeated
+                let (left, right) = (from.x.max(x_min as f32), to.x.min(x_max as f32 + 1.0));
+                let (top, bottom) = (from.y.max(y_min as f32), to.y.min(y_max as f32 + 1.0));
+
+                if left >= right || top >= bottom {
+                    return texel(at.x.floor() as i32, at.y.floor() as i32)
+                }
+
+                let mut sum = [0.0; 4];
+                let mut total = 0.0;
+
+                for y in top.floor() as i32..bottom.ceil() as i32 {
+                    let height = overlap(y, top, bottom);
+
+                    for x in left.floor() as i32..right.ceil() as i32 {
+                        let weight = overlap(x, left, right) * height;
+
+                        add_weighted(&mut sum, premultiply(texel(x, y)), weight);
+                        total += weight;
+                    }
+                }
+
+                if total == 0.0 {
+                    return [0, 0, 0, 0]
+                }
+
+                unpremultiply(sum.map(|channel| channel / total))
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{math::Vec2, shapes::rect::Rect, tekenen::Surface};
+
+    use super::Sampling;
+
+    fn gradient() -> Surface {
+        Surface::from_pixels(2, 2, vec![[0, 0, 0, 255], [200, 0, 0, 255], [0, 100, 0, 255], [0, 0, 0, 0]])
+    }
+
+    #[test]
+    fn filters() {
+        let image = gradient();
+        let all = Rect::new(0, 0, 2, 2);
+        let center = Vec2::new(1.0, 1.0);
+
+        assert_eq!(Sampling::Nearest.sample(&image, all, Vec2::new(1.5, 0.5), Vec2::new(1.0, 1.0)), [200, 0, 0, 255]);
+
+        // The transparent pixel only lowers the alpha, it does not darken the color
+        assert_eq!(Sampling::Bilinear.sample(&image, all, center, Vec2::new(1.0, 1.0)), [67, 33, 0, 191]);
+        assert_eq!(Sampling::Box.sample(&image, all, center, Vec2::new(2.0, 2.0)), [67, 33, 0, 191]);
+
+        // Exactly on a pixel center every filter returns that pixel
+        for sampling in [Sampling::Nearest, Sampling::Bilinear, Sampling::Bicubic, Sampling::Box] {
+            assert_eq!(sampling.sample(&image, all, Vec2::new(0.5, 0.5), Vec2::new(1.0, 1.0)), [0, 0, 0, 255]);
+        }
+    }
+
+    #[test]
+    fn source_edges_repeat() {
+        let image = gradient();
+        let right = Rect::new(1, 0, 1, 1);
+
+        // Pixels outside of the source are never mixed in
+        assert_eq!(Sampling::Bilinear.sample(&image, right, Vec2::new(1.0, 0.5), Vec2::new(1.0, 1.0)), [200, 0, 0, 255]);
+        assert_eq!(Sampling::Bicubic.sample(&image, right, Vec2::new(1.2, 0.9), Vec2::new(1.0, 1.0)), [200, 0, 0, 255]);
+    }
+
+    #[test]
+    fn box_footprint_stays_on_the_source() {
+        let image = gradient();
+        let all = Rect::new(0, 0, 2, 2);
+
+        // A near singular transformation, only the source pixels are averaged
+        assert_eq!(Sampling::Box.sample(&image, all, Vec2::new(1.0, 1.0), Vec2::new(1e9, 1e9)), [67, 33, 0, 191]);
+        assert_eq!(Sampling::Box.sample(&image, Rect::new(1, 0, 1, 1), Vec2::new(1.0, 1.0), Vec2::new(1e9, 1e9)), [200, 0, 0, 255]);
+
+        // Far past the edge the edge pixel is repeated
+        assert_eq!(Sampling::Box.sample(&image, all, Vec2::new(-1e6, 0.5), Vec2::new(2.0, 2.0)), [0, 0, 0, 255]);
+    }
+}
+
+#[cfg(all(test, feature = "image"))]
+mod snapshots {
+    use crate::{colors, shapes::rect::Rect, DrawableSurface, Surface};
+    use crate::snapshot::{assert_snapshot, checkerboard, reference, render};
+
+    use super::Sampling;
+
+    #[test]
+    fn draw_image_at() {
+        let image = checkerboard();
+
+        let surface = render(64, 32, |ctx| {
+            ctx.background(colors::BLACK);
+            ctx.draw_image(1, 1, &image);
+            ctx.draw_image_at(8, 1, 12, 12, &image);
+            ctx.draw_image_at(24, 1, 30, 7, &image);
+            ctx.draw_image_scaled(24, 12, 4.0, &image);
+        });
+
+        assert_snapshot(reference("draw_image_at"), &surface, 0);
+    }
+
+    #[test]
+    fn image_sampling() {
+        let image = checkerboard();
+
+        // One pixel wide stripes, downscaled they should blend to purple
+        let mut stripes = Surface::new(16, 16);
+        for y in 0..16 {
+            for x in 0..16 {
+                stripes.set_pixel(x, y, if x % 2 == 0 { colors::RED } else { colors::BLUE });
+            }
+        }
+
+        let surface = render(96, 40, |ctx| {
+            ctx.background(colors::BLACK);
+
+            for (column, sampling) in [Sampling::Nearest, Sampling::Bilinear, Sampling::Bicubic, Sampling::Box].into_iter().enumerate() {
+                let x = column as i32 * 24 + 2;
+
+                ctx.image_sampling(sampling);
+                ctx.draw_image_at(x, 2, 20, 20, &image);
+                ctx.draw_image_at(x, 26, 7, 7, &stripes);
+
+                // Bottom right corner with the transparent pixel, mirrored horizontally
+                ctx.draw_image_region(Rect::new(2, 2, 2, 2), x + 21, 26, -12, 12, &image);
+            }
+        });
+
+        assert_snapshot(reference("image_sampling"), &surface, 0);
+    }
+}
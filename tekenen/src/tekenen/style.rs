@@ -0,0 +1,57 @@
+use super::{colors, Paint, Pixel};
+
+/// Shape of the open ends of a stroke
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LineCap {
+    /// Stop exactly at the end point
+    #[default]
+    Butt,
+
+    /// Half a circle around the end point
+    Round,
+
+    /// Extend past the end point by half the stroke width
+    Square,
+}
+
+/// Shape of the corner where two segments of a stroke meet
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LineJoin {
+    /// Extend the outer edges until they meet, falls back to bevel for very sharp corners
+    #[default]
+    Miter,
+
+    /// Circle around the corner
+    Round,
+
+    /// Cut off the corner with a straight line
+    Bevel,
+}
+
+/// Everything that decides how a shape is painted
+#[derive(Debug, Clone, PartialEq)]
+pub struct Style {
+    /// Paint of the inside of a shape, None to only draw the outline
+    pub fill: Option<Paint>,
+
+    /// Color of the outline of a shape, None to only draw the inside
+    pub stroke: Option<Pixel>,
+
+    /// Width of the outline in pixels, centered on the edge of the shape
+    pub stroke_width: f32,
+
+    pub line_cap: LineCap,
+    pub line_join: LineJoin,
+}
+
+impl Default for Style {
+    fn default() -> Self {
+        Self {
+            fill: Some(Paint::Solid(colors::WHITE)),
+            stroke: None,
+            stroke_width: 1.0,
+            line_cap: LineCap::default(),
+            line_join: LineJoin::default(),
+        }
+    }
+}
@@ -0,0 +1,483 @@
+use std::{fmt, ops::Range};
+
+use unicode_segmentation::UnicodeSegmentation;
+
+use super::{Font, Pixel};
+
+/// Horizontal pixels synthetic italics lean over per pixel above the baseline
+pub(crate) const ITALIC_SLANT: f32 = 0.2;
+
+/// Where text is placed horizontally relative to x
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TextAlign {
+    /// Lines start at x
+    #[default]
+    Left,
+
+    /// Lines are centered on x
+    Center,
+
+    /// Lines end at x
+    Right,
+}
+
+/// Where text is placed vertically relative to y
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TextBaseline {
+    /// The top of the first line is at y
+    #[default]
+    Top,
+
+    /// The block of lines is centered on y
+    Middle,
+
+    /// The baseline of the first line is at y
+    Alphabetic,
+
+    /// The bottom of the last line is at y
+    Bottom,
+}
+
+/// Size of text in pixels
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct TextMetrics {
+    /// How far the pen moves over the widest line
+    pub width: f32,
+
+    /// Height above the baseline
+    pub ascent: f32,
+
+    /// Depth below the baseline, positive
+    pub descent: f32,
+}
+
+/// How `DrawableSurface::text_layout` breaks and places text
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TextLayout {
+    pub align: TextAlign,
+    pub baseline: TextBaseline,
+
+    /// Wrap words onto a new line past this width, words wider than it are broken between graphemes
+    pub max_width: Option<i32>,
+
+    /// Lines past this count are dropped
+    pub max_lines: Option<usize>,
+
+    /// Distance between lines as a multiple of the line height of the font
+    pub line_spacing: f32,
+
+    /// End the last line with an ellipsis when lines were dropped
+    pub ellipsis: bool,
+}
+
+impl Default for TextLayout {
+    fn default() -> Self {
+        Self {
+            align: TextAlign::default(),
+            baseline: TextBaseline::default(),
+            max_width: None,
+            max_lines: None,
+            line_spacing: 1.0,
+            ellipsis: false,
+        }
+    }
+}
+
+impl TextLayout {
+    /// Lines of text after wrapping and truncating
+    pub fn lines(&self, font: &Font, text: &str, height: f32) -> Vec<String> {
+        let (ranges, ellipsis) = self.ranges(font, text, height);
+        let mut lines: Vec<String> = ranges.into_iter().map(|range| text[range].to_string()).collect();
+
+        if let (Some(ellipsis), Some(last)) = (ellipsis, lines.last_mut()) {
+            last.push_str(ellipsis);
+        }
+
+        lines
+    }
+
+    /// Lines of rich text after wrapping and truncating, the ellipsis takes the style of the text before it
+    pub fn rich_lines(&self, font: &Font, text: &RichText, height: f32) -> Vec<RichText> {
+        let (ranges, ellipsis) = self.ranges(font, text.as_str(), height);
+        let mut lines: Vec<RichText> = ranges.into_iter().map(|range| text.slice(range)).collect();
+
+        if let (Some(ellipsis), Some(last)) = (ellipsis, lines.last_mut()) {
+            let style = last.runs().last().map(|(_, style)| style).unwrap_or_default();
+            last.push(ellipsis, style);
+        }
+
+        lines
+    }
+
+    /// Byte ranges of the lines in text, and the ellipsis to end the last line with
+    fn ranges(&self, font: &Font, text: &str, height: f32) -> (Vec<Range<usize>>, Option<&'static str>) {
+        let width = |range: Range<usize>| font.measure(&text[range], height).width;
+        let trim_end = |range: Range<usize>| range.start..range.start + text[range].trim_end().len();
+        let mut lines = Vec::new();
+        let mut start = 0;
+
+        for paragraph in text.split('\n') {
+            let offset = start;
+            start += paragraph.len() + 1;
+
+            let Some(max_width) = self.max_width else {
+                lines.push(offset..offset + paragraph.len());
+                continue;
+            };
+
+            let max_width = max_width as f32;
+            let mut line = offset..offset;
+
+            for (index, segment) in paragraph.split_word_bound_indices() {
+                let index = offset + index;
+                let end = index + segment.len();
+
+                if width(line.start..end) <= max_width {
+                    line.end = end;
+                    continue;
+                }
+
+                // Spaces that do not fit end the line and are dropped
+                if segment.chars().all(char::is_whitespace) {
+                    lines.push(trim_end(line));
+                    line = end..end;
+                    continue;
+                }
+
+                if !text[line.clone()].trim().is_empty() {
+                    lines.push(trim_end(line));
+                }
+                line = index..index;
+
+                for (grapheme_index, grapheme) in segment.grapheme_indices(true) {
+                    let grapheme_end = index + grapheme_index + grapheme.len();
+
+                    if line.start < line.end && width(line.start..grapheme_end) > max_width {
+                        lines.push(line);
+                        line = index + grapheme_index..index + grapheme_index;
+                    }
+
+                    line.end = grapheme_end;
+                }
+            }
+
+            lines.push(trim_end(line));
+        }
+
+        let Some(max_lines) = self.max_lines.filter(|max_lines| lines.len() > *max_lines) else {
+            return (lines, None)
+        };
+
+        lines.truncate(max_lines);
+
+        let (true, Some(last)) = (self.ellipsis, lines.last_mut()) else {
+            return (lines, None)
+        };
+
+        let ellipsis = if font.has_glyph('…') { "…" } else { "..." };
+
+        // Make room for the ellipsis one grapheme at a time
+        while let Some(max_width) = self.max_width {
+            if last.start == last.end || font.measure(&(text[last.clone()].to_string() + ellipsis), height).width <= max_width as f32 {
+                break
+            }
+
+            last.end = last.start + text[last.clone()].grapheme_indices(true).next_back().map_or(0, |(index, _)| index);
+        }
+
+        *last = trim_end(last.clone());
+
+        (lines, Some(ellipsis))
+    }
+}
+
+/// Color and decoration of a run of rich text
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct TextStyle {
+    /// Color of the glyphs and lines, None to use the fill color
+    pub color: Option<Pixel>,
+
+    /// Color of the box behind the run, as high as a line
+    pub background: Option<Pixel>,
+
+    pub underline: bool,
+    pub strikethrough: bool,
+
+    /// Glyphs are drawn again a few pixels to the right, the advance does not change
+    pub bold: bool,
+
+    /// Glyphs lean to the right around the baseline
+    pub italic: bool,
+}
+
+impl TextStyle {
+    pub fn color(color: Pixel) -> Self {
+        Self { color: Some(color), ..Default::default() }
+    }
+
+    /// Distance between the copies of a bold glyph
+    pub(crate) fn bold_offset(height: f32) -> f32 {
+        (height / 16.0).round().max(1.0)
+    }
+}
+
+/// Placement of underlines and strikethroughs, relative to the baseline
+pub(crate) struct Decorations {
+    pub underline: f32,
+    pub strikethrough: f32,
+    pub thickness: f32,
+}
+
+impl Decorations {
+    pub fn new(font: &Font, height: f32) -> Self {
+        let thickness = (height / 16.0).round().max(1.0);
+
+        Self {
+            underline: (font.descent(height) / 2.0).round().max(1.0),
+            strikethrough: -(font.ascent(height) * 0.3).round() - (thickness / 2.0).floor(),
+            thickness,
+        }
+    }
+}
+
+/// Text made of runs that each have their own style
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct RichText {
+    text: String,
+
+    /// Byte ranges into text, in order and without gaps
+    runs: Vec<(Range<usize>, TextStyle)>,
+}
+
+impl RichText {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append a run, it merges with the last run if the styles are the same
+    pub fn push(&mut self, text: &str, style: TextStyle) {
+        if text.is_empty() {
+            return
+        }
+
+        let start = self.text.len();
+        self.text.push_str(text);
+
+        match self.runs.last_mut() {
+            Some((range, last)) if *last == style => range.end = self.text.len(),
+            _ => self.runs.push((start..self.text.len(), style)),
+        }
+    }
+
+    /// Append a run
+    pub fn with(mut self, text: &str, style: TextStyle) -> Self {
+        self.push(text, style);
+        self
+    }
+
+    /// Append a run with the default style
+    pub fn plain(self, text: &str) -> Self {
+        self.with(text, TextStyle::default())
+    }
+
+    /// All text without styles
+    pub fn as_str(&self) -> &str {
+        &self.text
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.text.is_empty()
+    }
+
+    /// Text and style of every run
+    pub fn runs(&self) -> impl Iterator<Item = (&str, TextStyle)> {
+        self.runs.iter().map(|(range, style)| (&self.text[range.clone()], *style))
+    }
+
+    /// Byte range and style of every run
+    pub(crate) fn run_ranges(&self) -> &[(Range<usize>, TextStyle)] {
+        &self.runs
+    }
+
+    /// Style of the run containing byte index
+    pub fn style_at(&self, index: usize) -> TextStyle {
+        self.run_at(index).map_or_else(TextStyle::default, |run| self.runs[run].1)
+    }
+
+    /// Position of the run containing byte index
+    pub(crate) fn run_at(&self, index: usize) -> Option<usize> {
+        self.runs.iter().position(|(range, _)| range.contains(&index))
+    }
+
+    /// Part of the text with the styles it had, range must lie on char boundaries
+    pub fn slice(&self, range: Range<usize>) -> RichText {
+        let mut slice = RichText::new();
+
+        for (run, style) in &self.runs {
+            let start = run.start.max(range.start);
+            let end = run.end.min(range.end);
+
+            if start < end {
+                slice.push(&self.text[start..end], *style);
+            }
+        }
+
+        slice
+    }
+
+    /// Left and right edge of every run laid out in font, with its style
+    pub(crate) fn run_extents(&self, font: &Font, height: f32) -> Vec<(f32, f32, TextStyle)> {
+        let advance = |end: usize| font.measure(&self.text[..end], height).width.round();
+
+        self.runs.iter().map(|(range, style)| (advance(range.start), advance(range.end), *style)).collect()
+    }
+
+    /// Split at newlines, which are dropped
+    pub(crate) fn lines(&self) -> Vec<RichText> {
+        let mut start = 0;
+
+        self.text.split('\n').map(|line| {
+            let slice = self.slice(start..start + line.len());
+            start += line.len() + 1;
+            slice
+        }).collect()
+    }
+}
+
+impl From<&str> for RichText {
+    fn from(text: &str) -> Self {
+        RichText::new().plain(text)
+    }
+}
+
+impl From<String> for RichText {
+    fn from(text: String) -> Self {
+        RichText::from(text.as_str())
+    }
+}
+
+impl fmt::Display for RichText {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Font;
+
+    use super::{RichText, TextLayout, TextStyle};
+
+    #[test]
+    fn wrap_and_truncate() {
+        // Every character of the builtin font is 8 pixels wide at height 8
+        let font = Font::builtin();
+        let layout = TextLayout { max_width: Some(48), ..Default::default() };
+
+        assert_eq!(layout.lines(&font, "one two three", 8.0), ["one", "two", "three"]);
+        assert_eq!(layout.lines(&font, "to be or not", 8.0), ["to be", "or not"]);
+        assert_eq!(layout.lines(&font, "abcdefghij\nk", 8.0), ["abcdef", "ghij", "k"]);
+
+        let layout = TextLayout { max_lines: Some(2), ellipsis: true, ..layout };
+        assert_eq!(layout.lines(&font, "one two three", 8.0), ["one", "two..."]);
+        assert_eq!(layout.lines(&font, "abcdefghijklm", 8.0), ["abcdef", "ghi..."]);
+
+        let single = TextLayout { max_lines: Some(1), ellipsis: true, ..Default::default() };
+        assert_eq!(single.lines(&font, "first\nsecond", 8.0), ["first..."]);
+    }
+
+    #[test]
+    fn rich_text() {
+        let bold = TextStyle { bold: true, ..Default::default() };
+        let text = RichText::new().plain("one ").plain("two ").with("three", bold);
+
+        // Runs with the same style merge
+        assert_eq!(text.runs().collect::<Vec<_>>(), [("one two ", TextStyle::default()), ("three", bold)]);
+        assert_eq!(text.slice(4..10).runs().collect::<Vec<_>>(), [("two ", TextStyle::default()), ("th", bold)]);
+
+        let font = Font::builtin();
+        let layout = TextLayout { max_width: Some(48), max_lines: Some(2), ellipsis: true, ..Default::default() };
+        let lines = layout.rich_lines(&font, &text, 8.0);
+
+        assert_eq!(lines.iter().map(RichText::to_string).collect::<Vec<_>>(), ["one", "two..."]);
+        assert_eq!(lines[1].runs().last(), Some(("two...", TextStyle::default())));
+
+        let lines = TextLayout { max_width: Some(48), ..Default::default() }.rich_lines(&font, &text, 8.0);
+        assert_eq!(lines[2].runs().collect::<Vec<_>>(), [("three", bold)]);
+    }
+}
+
+#[cfg(all(test, feature = "image"))]
+mod snapshots {
+    use crate::{colors, math::Vec2, DrawableSurface};
+    use crate::snapshot::{assert_snapshot, reference, render};
+
+    use super::{RichText, TextAlign, TextBaseline, TextLayout, TextStyle};
+
+    #[test]
+    fn text() {
+        let surface = render(128, 40, |ctx| {
+            ctx.background(colors::BLACK);
+            ctx.fill_color(colors::WHITE);
+            ctx.text("Tekenen!", 0, 0, 16);
+            ctx.text("{[0-9]}", 4, 20, 16);
+        });
+
+        assert_snapshot(reference("text"), &surface, 0);
+    }
+
+    #[test]
+    fn text_layout() {
+        let surface = render(128, 64, |ctx| {
+            ctx.background(colors::BLACK);
+            ctx.stroke_color(colors::RED);
+            ctx.line(64, 0, 64, 63);
+
+            ctx.fill_color(colors::WHITE);
+
+            // Wrapped at spaces and centered on the line
+            let centered = TextLayout { align: TextAlign::Center, max_width: Some(64), line_spacing: 1.5, ..Default::default() };
+            ctx.text_layout("wrapped and centered text", 64, 2, 8, &centered);
+
+            // The last line ends on the right edge with an ellipsis
+            let cut = TextLayout { align: TextAlign::Right, baseline: TextBaseline::Bottom, max_width: Some(72), max_lines: Some(1), ellipsis: true, ..Default::default() };
+            ctx.text_layout("truncated line", 126, 62, 8, &cut);
+
+            let size = ctx.text_layout("Hi", 2, 48, 16, &TextLayout { baseline: TextBaseline::Alphabetic, ..Default::default() });
+            assert_eq!(size, Vec2::new(32, 16));
+        });
+
+        assert_snapshot(reference("text_layout"), &surface, 0);
+    }
+
+    #[test]
+    fn rich_text() {
+        let keyword = TextStyle { color: Some(colors::YELLOW), bold: true, ..Default::default() };
+        let marked = TextStyle { color: Some(colors::BLACK), background: Some(colors::AQUA), ..Default::default() };
+        let lines = TextStyle { underline: true, strikethrough: true, ..TextStyle::color(colors::RED) };
+        let italic = TextStyle { italic: true, ..Default::default() };
+
+        let surface = render(128, 64, |ctx| {
+            ctx.background(colors::BLACK);
+            ctx.fill_color(colors::WHITE);
+
+            let text = RichText::new().with("let", keyword).plain(" x = ").with("42", marked);
+            ctx.rich_text(&text, 0, 0, 12);
+
+            let text = RichText::new().with("gone", lines).plain(" and ").with("slanted", italic);
+            ctx.rich_text(&text, 0, 16, 8);
+
+            // Runs keep their style when wrapped, outlines are used when rotated
+            let text = RichText::new().plain("one ").with("two three", lines);
+            ctx.rich_text_layout(&text, 0, 28, 8, &TextLayout { max_width: Some(48), ..Default::default() });
+
+            ctx.push();
+            ctx.translate(Vec2::new(64, 63));
+            ctx.rotate(-std::f32::consts::FRAC_PI_6);
+            ctx.rich_text(&RichText::new().with("bold", keyword).with("!", lines), 0, -10, 10);
+            ctx.pop();
+        });
+
+        assert_snapshot(reference("rich_text"), &surface, 0);
+    }
+}